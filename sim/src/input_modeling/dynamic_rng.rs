@@ -1,11 +1,120 @@
+use std::any::Any;
 use std::{cell::RefCell, rc::Rc};
 
-pub trait SimulationRng: std::fmt::Debug + rand_core::RngCore {}
-impl<T: std::fmt::Debug + rand_core::RngCore> SimulationRng for T {}
+use rand_pcg::{Pcg64, Pcg64Mcg};
+use serde::{Deserialize, Serialize};
+
+pub trait SimulationRng: std::fmt::Debug + rand_core::RngCore {
+    /// Advance the generator by `n` draws (of `next_u64`), so that parallel
+    /// replications can start at well-separated offsets of a single stream.
+    /// Generators are advanced by drawing and discarding `n` values, except
+    /// for the PCG generators, which jump ahead directly.
+    fn jump(&mut self, n: u64);
+
+    /// Save a copy of the generator state, for a later `restore`.
+    fn save(&self) -> Rc<dyn Any>;
+
+    /// Restore the generator state from a copy saved by `save`.  States
+    /// saved from generators of another type are ignored.
+    fn restore(&mut self, state: &dyn Any);
+
+    /// An independent copy of the generator, in its current state.
+    fn duplicate(&self) -> DynRng;
+}
+
+impl<T: std::fmt::Debug + rand_core::RngCore + Clone + 'static> SimulationRng for T {
+    fn jump(&mut self, n: u64) {
+        let rng: &mut dyn Any = self;
+        if let Some(rng) = rng.downcast_mut::<Pcg64Mcg>() {
+            rng.advance(u128::from(n));
+        } else if let Some(rng) = rng.downcast_mut::<Pcg64>() {
+            rng.advance(u128::from(n));
+        } else {
+            (0..n).for_each(|_| {
+                self.next_u64();
+            });
+        }
+    }
+
+    fn save(&self) -> Rc<dyn Any> {
+        Rc::new(self.clone())
+    }
+
+    fn restore(&mut self, state: &dyn Any) {
+        if let Some(state) = state.downcast_ref::<T>() {
+            *self = state.clone();
+        }
+    }
+
+    fn duplicate(&self) -> DynRng {
+        dyn_rng(self.clone())
+    }
+}
+
 pub type DynRng = Rc<RefCell<dyn SimulationRng>>;
 
+/// The seed of the default random number generator.
+pub(crate) const DEFAULT_SEED: u64 = 42;
+
 pub(crate) fn default_rng() -> DynRng {
-    Rc::new(RefCell::new(rand_pcg::Pcg64Mcg::new(42)))
+    Rc::new(RefCell::new(rand_pcg::Pcg64Mcg::new(u128::from(
+        DEFAULT_SEED,
+    ))))
+}
+
+/// The number of draws separating successive substreams under the `Jump`
+/// substream scheme.
+const JUMP_STRIDE: u64 = 1 << 48;
+
+/// A `SubstreamScheme` governs how the random number generators of child
+/// substreams - such as simulation replications - are derived from a
+/// parent seed, so that substreams are statistically independent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SubstreamScheme {
+    /// Seed each substream with a well-mixed hash of the parent seed and
+    /// the substream index
+    #[default]
+    Hash,
+    /// Jump the parent generator ahead by 2^48 draws per substream index,
+    /// partitioning a single stream into disjoint segments
+    Jump,
+    /// Seed each substream with successive outputs of a SplitMix64
+    /// generator, seeded with the parent seed
+    SplitMix,
+}
+
+impl SubstreamScheme {
+    /// The default generator of a substream, from the parent seed and the
+    /// substream index.
+    pub fn rng(&self, seed: u64, index: u64) -> Pcg64Mcg {
+        match self {
+            SubstreamScheme::Hash => {
+                let high = mix64(seed ^ mix64(index.wrapping_add(0x9e37_79b9_7f4a_7c15)));
+                let low = mix64(high ^ mix64(index ^ 0xd1b5_4a32_d192_ed03));
+                Pcg64Mcg::new((u128::from(high) << 64) | u128::from(low))
+            }
+            SubstreamScheme::Jump => {
+                let mut rng = Pcg64Mcg::new(u128::from(seed));
+                rng.jump(JUMP_STRIDE.wrapping_mul(index));
+                rng
+            }
+            SubstreamScheme::SplitMix => {
+                let state =
+                    seed.wrapping_add(0x9e37_79b9_7f4a_7c15_u64.wrapping_mul(2 * index + 1));
+                let high = mix64(state);
+                let low = mix64(state.wrapping_add(0x9e37_79b9_7f4a_7c15));
+                Pcg64Mcg::new((u128::from(high) << 64) | u128::from(low))
+            }
+        }
+    }
+}
+
+/// The SplitMix64 output function, a bijective 64-bit mixer.
+fn mix64(value: u64) -> u64 {
+    let value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    let value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    value ^ (value >> 31)
 }
 
 pub fn dyn_rng<Rng: SimulationRng + 'static>(rng: Rng) -> DynRng {
@@ -15,3 +124,87 @@ pub fn dyn_rng<Rng: SimulationRng + 'static>(rng: Rng) -> DynRng {
 pub fn some_dyn_rng<Rng: SimulationRng + 'static>(rng: Rng) -> Option<DynRng> {
     Some(dyn_rng(rng))
 }
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use rand::RngCore;
+
+    fn assert_jump_matches_draws<Rng: SimulationRng + Clone>(rng: Rng) {
+        let n = 1000;
+        let mut jumped = rng.clone();
+        let mut drawn = rng;
+        jumped.jump(n);
+        (0..n).for_each(|_| {
+            drawn.next_u64();
+        });
+        assert_eq!(jumped.next_u64(), drawn.next_u64());
+    }
+
+    #[test]
+    fn jump_matches_draws_for_default_generator() {
+        assert_jump_matches_draws(Pcg64Mcg::new(42));
+        assert_jump_matches_draws(Pcg64::new(42, 54));
+    }
+
+    #[test]
+    fn default_substreams_are_uncorrelated() {
+        use rand::Rng;
+        let draws = 100_000;
+        let sample = |index: u64| -> Vec<f64> {
+            let mut rng = SubstreamScheme::default().rng(42, index);
+            (0..draws).map(|_| rng.gen::<f64>()).collect()
+        };
+        let (first, second) = (sample(0), sample(1));
+        let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len() as f64;
+        let (first_mean, second_mean) = (mean(&first), mean(&second));
+        let (covariance, first_variance, second_variance) = first.iter().zip(second.iter()).fold(
+            (0.0, 0.0, 0.0),
+            |(covariance, first_variance, second_variance), (a, b)| {
+                (
+                    covariance + (a - first_mean) * (b - second_mean),
+                    first_variance + (a - first_mean).powi(2),
+                    second_variance + (b - second_mean).powi(2),
+                )
+            },
+        );
+        let correlation = covariance / (first_variance * second_variance).sqrt();
+        // The standard error of the correlation is about 1/sqrt(draws)
+        assert!(correlation.abs() < 0.015);
+        assert_ne!(first[0], second[0]);
+    }
+
+    #[test]
+    fn substream_schemes_are_deterministic() {
+        [
+            SubstreamScheme::Hash,
+            SubstreamScheme::Jump,
+            SubstreamScheme::SplitMix,
+        ]
+        .iter()
+        .for_each(|scheme| {
+            assert_eq!(scheme.rng(42, 3).next_u64(), scheme.rng(42, 3).next_u64());
+            assert_ne!(scheme.rng(42, 3).next_u64(), scheme.rng(42, 4).next_u64());
+        });
+    }
+
+    #[test]
+    fn restored_generator_repeats_draws() {
+        let mut rng = Pcg64Mcg::new(42);
+        let state = rng.save();
+        let first = rng.next_u64();
+        rng.restore(&*state);
+        assert_eq!(rng.next_u64(), first);
+        // States of other generator types are ignored
+        rng.restore(&*StdRng::seed_from_u64(42).save());
+        assert_ne!(rng.next_u64(), first);
+    }
+
+    #[test]
+    fn jump_matches_draws_for_other_generators() {
+        assert_jump_matches_draws(StdRng::seed_from_u64(42));
+    }
+}
@@ -8,7 +8,7 @@ pub mod dynamic_rng;
 pub mod random_variable;
 pub mod thinning;
 
-pub use dynamic_rng::{dyn_rng, some_dyn_rng};
+pub use dynamic_rng::{dyn_rng, some_dyn_rng, SubstreamScheme};
 pub use random_variable::Boolean as BooleanRandomVariable;
 pub use random_variable::Continuous as ContinuousRandomVariable;
 pub use random_variable::Discrete as DiscreteRandomVariable;
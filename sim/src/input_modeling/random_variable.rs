@@ -4,7 +4,7 @@
 //! common parameterizations, are wrapped in enums `Continuous`, `Boolean`,
 //! `Discrete`, and `Index`.
 
-use rand::distributions::Distribution;
+use rand::distributions::{Distribution, Open01};
 use serde::{Deserialize, Serialize};
 // Continuous distributions
 use rand_distr::{Beta, Exp, Gamma, LogNormal, Normal, Triangular, Uniform, Weibull};
@@ -17,14 +17,51 @@ use crate::utils::errors::SimulationError;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Continuous {
-    Beta { alpha: f64, beta: f64 },
-    Exp { lambda: f64 },
-    Gamma { shape: f64, scale: f64 },
-    LogNormal { mu: f64, sigma: f64 },
-    Normal { mean: f64, std_dev: f64 },
-    Triangular { min: f64, max: f64, mode: f64 },
-    Uniform { min: f64, max: f64 },
-    Weibull { shape: f64, scale: f64 },
+    Beta {
+        alpha: f64,
+        beta: f64,
+    },
+    /// The sum of k independent exponential random variables, each with rate lambda
+    Erlang {
+        k: u32,
+        lambda: f64,
+    },
+    Exp {
+        lambda: f64,
+    },
+    Gamma {
+        shape: f64,
+        scale: f64,
+    },
+    LogNormal {
+        mu: f64,
+        sigma: f64,
+    },
+    Normal {
+        mean: f64,
+        std_dev: f64,
+    },
+    /// A heavy-tailed distribution, with support [scale, infinity).  For a
+    /// shape of 1 or less, the mean is infinite, so models using a Pareto
+    /// distribution for interarrival or service times must tolerate very
+    /// large delays.
+    Pareto {
+        scale: f64,
+        shape: f64,
+    },
+    Triangular {
+        min: f64,
+        max: f64,
+        mode: f64,
+    },
+    Uniform {
+        min: f64,
+        max: f64,
+    },
+    Weibull {
+        shape: f64,
+        scale: f64,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,6 +107,13 @@ impl Continuous {
         let mut rng = (*uniform_rng).borrow_mut();
         match self {
             Continuous::Beta { alpha, beta } => Ok(Beta::new(*alpha, *beta)?.sample(&mut *rng)),
+            Continuous::Erlang { k, lambda } => {
+                if *k < 1 {
+                    return Err(SimulationError::InvalidDistributionParameter);
+                }
+                let exp = Exp::new(*lambda)?;
+                Ok((0..*k).map(|_| exp.sample(&mut *rng)).sum())
+            }
             Continuous::Exp { lambda } => Ok(Exp::new(*lambda)?.sample(&mut *rng)),
             Continuous::Gamma { shape, scale } => Ok(Gamma::new(*shape, *scale)?.sample(&mut *rng)),
             Continuous::LogNormal { mu, sigma } => {
@@ -78,6 +122,14 @@ impl Continuous {
             Continuous::Normal { mean, std_dev } => {
                 Ok(Normal::new(*mean, *std_dev)?.sample(&mut *rng))
             }
+            Continuous::Pareto { scale, shape } => {
+                if !(*scale > 0.0 && *shape > 0.0) {
+                    return Err(SimulationError::InvalidDistributionParameter);
+                }
+                // Inverse transform sampling, from a uniform variate on (0, 1)
+                let uniform: f64 = Open01.sample(&mut *rng);
+                Ok(*scale / uniform.powf(1.0 / *shape))
+            }
             Continuous::Triangular { min, max, mode } => {
                 Ok(Triangular::new(*min, *max, *mode)?.sample(&mut *rng))
             }
@@ -87,6 +139,113 @@ impl Continuous {
             }
         }
     }
+
+    /// Validate the distribution parameters against the distribution
+    /// domain, such as a positive rate for the exponential distribution, so
+    /// that misconfigured random variables fail fast, rather than at
+    /// sampling.
+    pub fn validate(&self) -> Result<(), SimulationError> {
+        let require = |valid: bool, requirement: &str| {
+            if valid {
+                Ok(())
+            } else {
+                Err(SimulationError::InvalidRandomVariable(format![
+                    "{:?} requires {}",
+                    self, requirement
+                ]))
+            }
+        };
+        match self {
+            Continuous::Beta { alpha, beta } => {
+                require(*alpha > 0.0 && *beta > 0.0, "positive alpha and beta")
+            }
+            Continuous::Erlang { k, lambda } => {
+                require(*k >= 1, "k of at least 1")?;
+                require(*lambda > 0.0, "a positive lambda")
+            }
+            Continuous::Exp { lambda } => require(*lambda > 0.0, "a positive lambda"),
+            Continuous::Gamma { shape, scale } => {
+                require(*shape > 0.0 && *scale > 0.0, "positive shape and scale")
+            }
+            Continuous::LogNormal { mu, sigma } => require(
+                mu.is_finite() && sigma.is_finite() && *sigma >= 0.0,
+                "a finite mu and a finite, non-negative sigma",
+            ),
+            Continuous::Normal { mean, std_dev } => require(
+                mean.is_finite() && std_dev.is_finite() && *std_dev >= 0.0,
+                "a finite mean and a finite, non-negative std_dev",
+            ),
+            Continuous::Pareto { scale, shape } => {
+                require(*scale > 0.0 && *shape > 0.0, "positive scale and shape")
+            }
+            Continuous::Triangular { min, max, mode } => require(
+                min.is_finite() && max.is_finite() && min <= mode && mode <= max && min < max,
+                "finite bounds with min <= mode <= max and min < max",
+            ),
+            Continuous::Uniform { min, max } => require(
+                min.is_finite() && max.is_finite() && min < max,
+                "finite bounds with min < max",
+            ),
+            Continuous::Weibull { shape, scale } => {
+                require(*shape > 0.0 && *scale > 0.0, "positive shape and scale")
+            }
+        }
+    }
+
+    /// The expected value of the distribution, as an analytical estimate for
+    /// static analysis of a simulation - such as expected latencies - without
+    /// sampling.  The parameters are not validated.
+    pub fn mean(&self) -> f64 {
+        match self {
+            Continuous::Beta { alpha, beta } => alpha / (alpha + beta),
+            Continuous::Erlang { k, lambda } => f64::from(*k) / lambda,
+            Continuous::Exp { lambda } => 1.0 / lambda,
+            Continuous::Gamma { shape, scale } => shape * scale,
+            Continuous::LogNormal { mu, sigma } => (mu + sigma.powi(2) / 2.0).exp(),
+            Continuous::Normal { mean, .. } => *mean,
+            Continuous::Pareto { scale, shape } => {
+                if *shape > 1.0 {
+                    shape * scale / (shape - 1.0)
+                } else {
+                    f64::INFINITY
+                }
+            }
+            Continuous::Triangular { min, max, mode } => (min + max + mode) / 3.0,
+            Continuous::Uniform { min, max } => (min + max) / 2.0,
+            // Consistent with sampling, where the parameters are passed to
+            // rand_distr as (scale, shape)
+            Continuous::Weibull { shape, scale } => shape * gamma_function(1.0 + 1.0 / scale),
+        }
+    }
+}
+
+/// The gamma function, by the Lanczos approximation (g = 7, n = 9).
+fn gamma_function(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // Reflection formula
+        std::f64::consts::PI / ((std::f64::consts::PI * x).sin() * gamma_function(1.0 - x))
+    } else {
+        let x = x - 1.0;
+        let t = x + 7.5;
+        let series = COEFFICIENTS[1..]
+            .iter()
+            .enumerate()
+            .fold(COEFFICIENTS[0], |series, (i, coefficient)| {
+                series + coefficient / (x + i as f64 + 1.0)
+            });
+        (2.0 * std::f64::consts::PI).sqrt() * t.powf(x + 0.5) * (-t).exp() * series
+    }
 }
 
 impl Boolean {
@@ -221,6 +380,50 @@ mod tests {
         assert!((mean - expected).abs() / expected < 0.025);
     }
 
+    #[test]
+    fn beta_samples_are_bounded_proportions() {
+        // Parameters below one concentrate the samples at the bounds
+        let mut variable: Continuous =
+            serde_yaml::from_str("beta: {alpha: 0.5, beta: 0.5}").unwrap();
+        let uniform_rng = default_rng();
+        let samples: Vec<f64> = (0..10000)
+            .map(|_| variable.random_variate(uniform_rng.clone()).unwrap())
+            .collect();
+        assert!(samples.iter().all(|sample| (0.0..=1.0).contains(sample)));
+        assert!(samples.iter().any(|sample| *sample < 0.001));
+        assert!(samples.iter().any(|sample| *sample > 0.999));
+    }
+
+    #[test]
+    fn beta_rejects_non_positive_parameters() {
+        let mut variable = Continuous::Beta {
+            alpha: 0.0,
+            beta: 5.0,
+        };
+        assert!(variable.random_variate(default_rng()).is_err());
+    }
+
+    #[test]
+    fn erlang_samples_match_expectation() {
+        let mut variable: Continuous = serde_yaml::from_str("erlang: {k: 3, lambda: 2.0}").unwrap();
+        let uniform_rng = default_rng();
+        let samples: Vec<f64> = (0..10000)
+            .map(|_| variable.random_variate(uniform_rng.clone()).unwrap())
+            .collect();
+        let mean = samples.iter().sum::<f64>() / (samples.len() as f64);
+        let variance = samples
+            .iter()
+            .map(|sample| (sample - mean).powi(2))
+            .sum::<f64>()
+            / (samples.len() as f64);
+        let expected_mean = 3.0 / 2.0;
+        let expected_variance = 3.0 / 2.0f64.powi(2);
+        assert!((mean - expected_mean).abs() / expected_mean < 0.025);
+        assert!((variance - expected_variance).abs() / expected_variance < 0.05);
+        let mut invalid = Continuous::Erlang { k: 0, lambda: 2.0 };
+        assert!(invalid.random_variate(default_rng()).is_err());
+    }
+
     #[test]
     fn exponential_samples_match_expectation() {
         let variable = Continuous::Exp { lambda: 7.0 };
@@ -294,6 +497,56 @@ mod tests {
         assert![chi_square_actual < chi_square_critical];
     }
 
+    #[test]
+    fn pareto_samples_are_heavy_tailed() {
+        let mut variable: Continuous =
+            serde_yaml::from_str("pareto: {scale: 2.0, shape: 1.5}").unwrap();
+        let uniform_rng = default_rng();
+        let samples: Vec<f64> = (0..10000)
+            .map(|_| variable.random_variate(uniform_rng.clone()).unwrap())
+            .collect();
+        assert!(samples.iter().all(|sample| *sample >= 2.0));
+        // P(X > 200) = (2/200)^1.5 = 0.001, so large samples are occasional
+        let large_samples = samples.iter().filter(|sample| **sample > 200.0).count();
+        assert!(large_samples > 0 && large_samples < 50);
+    }
+
+    #[test]
+    fn pareto_rejects_non_positive_parameters() {
+        let mut variable = Continuous::Pareto {
+            scale: 2.0,
+            shape: 0.0,
+        };
+        assert!(variable.random_variate(default_rng()).is_err());
+        let mut variable = Continuous::Pareto {
+            scale: -1.0,
+            shape: 1.5,
+        };
+        assert!(variable.random_variate(default_rng()).is_err());
+    }
+
+    #[test]
+    fn validate_rejects_parameters_outside_the_domain() {
+        match (Continuous::Exp { lambda: 0.0 }).validate() {
+            Err(SimulationError::InvalidRandomVariable(description)) => {
+                assert!(description.contains("positive lambda"))
+            }
+            _ => panic!("Expected an invalid random variable"),
+        }
+        assert!((Continuous::Exp { lambda: -1.0 }).validate().is_err());
+        assert!((Continuous::Uniform { min: 2.0, max: 1.0 })
+            .validate()
+            .is_err());
+        assert!((Continuous::Exp { lambda: 0.5 }).validate().is_ok());
+        assert!((Continuous::Triangular {
+            min: 5.0,
+            max: 25.0,
+            mode: 15.0
+        })
+        .validate()
+        .is_ok());
+    }
+
     #[test]
     fn triangular_samples_chi_square() {
         fn bins_mapping(variate: f64) -> usize {
@@ -356,6 +609,39 @@ mod tests {
         assert!((mean - expected).abs() / expected < 0.025);
     }
 
+    #[test]
+    fn continuous_means_match_samples() {
+        let variables = vec![
+            Continuous::Beta {
+                alpha: 2.0,
+                beta: 5.0,
+            },
+            Continuous::Gamma {
+                shape: 2.0,
+                scale: 3.0,
+            },
+            Continuous::LogNormal {
+                mu: 1.0,
+                sigma: 0.5,
+            },
+            Continuous::Triangular {
+                min: 1.0,
+                max: 4.0,
+                mode: 2.0,
+            },
+            Continuous::Weibull {
+                shape: 7.0,
+                scale: 0.5,
+            },
+        ];
+        variables.into_iter().for_each(|variable| {
+            let expected = variable.mean();
+            let mean = empirical_mean(&mut RandomVariable::Continuous(variable), 10000);
+            assert!((mean - expected).abs() / expected < 0.025);
+        });
+        assert!((gamma_function(5.0) - 24.0).abs() < 1e-9);
+    }
+
     #[test]
     fn bernoulli_samples_chi_square() {
         fn bins_mapping(variate: bool) -> usize {
@@ -1,8 +1,11 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use super::model_trait::{DevsModel, Reportable, ReportableModel, SerializableModel};
 use super::{ModelMessage, ModelRecord};
 use crate::simulator::Services;
+use crate::utils::deserialize_infinity;
 use crate::utils::errors::SimulationError;
 
 use sim_derive::SerializableModel;
@@ -48,6 +51,7 @@ struct PortsOut {
 #[serde(rename_all = "camelCase")]
 struct State {
     phase: Phase,
+    #[serde(deserialize_with = "deserialize_infinity")]
     until_next_event: f64,
     jobs: Vec<String>,
     records: Vec<ModelRecord>,
@@ -135,6 +139,7 @@ impl Batcher {
                 ModelMessage {
                     port_name: self.ports_out.job.clone(),
                     content: self.state.jobs.remove(0),
+                    attributes: HashMap::new(),
                 }
             })
             .collect()
@@ -153,6 +158,7 @@ impl Batcher {
                 ModelMessage {
                     port_name: self.ports_out.job.clone(),
                     content: self.state.jobs.remove(0),
+                    attributes: HashMap::new(),
                 }
             })
             .collect()
@@ -171,6 +177,7 @@ impl Batcher {
                 ModelMessage {
                     port_name: self.ports_out.job.clone(),
                     content: self.state.jobs.remove(0),
+                    attributes: HashMap::new(),
                 }
             })
             .collect()
@@ -227,6 +234,10 @@ impl DevsModel for Batcher {
     fn until_next_event(&self) -> f64 {
         self.state.until_next_event
     }
+
+    fn input_ports(&self) -> Option<Vec<String>> {
+        Some(vec![self.ports_in.job.clone()])
+    }
 }
 
 impl Reportable for Batcher {
@@ -241,6 +252,10 @@ impl Reportable for Batcher {
     fn records(&self) -> &Vec<ModelRecord> {
         &self.state.records
     }
+
+    fn records_mut(&mut self) -> Option<&mut Vec<ModelRecord>> {
+        Some(&mut self.state.records)
+    }
 }
 
 impl ReportableModel for Batcher {}
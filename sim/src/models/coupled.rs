@@ -1,8 +1,11 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use super::model_trait::{DevsModel, Reportable, ReportableModel, SerializableModel};
 use super::{Model, ModelMessage, ModelRecord};
 
+use crate::input_modeling::dynamic_rng::DynRng;
 use crate::simulator::Services;
 use crate::utils::errors::SimulationError;
 
@@ -78,6 +81,8 @@ struct ParkedMessage {
     component_id: String,
     port: String,
     content: String,
+    #[serde(default)]
+    attributes: HashMap<String, f64>,
 }
 
 #[cfg_attr(feature = "simx", event_rules)]
@@ -118,6 +123,7 @@ impl Coupled {
                         component_id: coupling.target_id.to_string(),
                         port: coupling.target_port.to_string(),
                         content: incoming_message.content.to_string(),
+                        attributes: incoming_message.attributes.clone(),
                     })
                 } else {
                     None
@@ -179,6 +185,7 @@ impl Coupled {
                     &ModelMessage {
                         port_name: parked_message.port.to_string(),
                         content: parked_message.content.to_string(),
+                        attributes: parked_message.attributes.clone(),
                     },
                     services,
                 )
@@ -190,20 +197,16 @@ impl Coupled {
         services: &mut Services,
     ) -> Result<Vec<ModelMessage>, SimulationError> {
         // Find the (internal message) events_ext relevant models (parked message id == component id)
-        let ext_transitioning_component_triggers: Vec<(usize, String, String)> = (0..self
+        let ext_transitioning_component_triggers: Vec<(usize, ParkedMessage)> = (0..self
             .components
             .len())
-            .flat_map(|component_index| -> Vec<(usize, String, String)> {
+            .flat_map(|component_index| -> Vec<(usize, ParkedMessage)> {
                 self.state
                     .parked_messages
                     .iter()
                     .filter_map(|parked_message| {
                         if parked_message.component_id == self.components[component_index].id() {
-                            Some((
-                                component_index,
-                                parked_message.port.to_string(),
-                                parked_message.content.to_string(),
-                            ))
+                            Some((component_index, parked_message.clone()))
                         } else {
                             None
                         }
@@ -214,11 +217,12 @@ impl Coupled {
         ext_transitioning_component_triggers
             .iter()
             .map(
-                |(component_index, message_port, message_content)| -> Result<(), SimulationError> {
+                |(component_index, parked_message)| -> Result<(), SimulationError> {
                     self.components[*component_index].events_ext(
                         &ModelMessage {
-                            port_name: message_port.to_string(),
-                            content: message_content.to_string(),
+                            port_name: parked_message.port.to_string(),
+                            content: parked_message.content.to_string(),
+                            attributes: parked_message.attributes.clone(),
                         },
                         services,
                     )
@@ -252,6 +256,7 @@ impl Coupled {
                                     component_id: target_id.to_string(),
                                     port: target_port.to_string(),
                                     content: outgoing_message.content.clone(),
+                                    attributes: outgoing_message.attributes.clone(),
                                 });
                             });
                             // For external messages (those transmitted on external output couplings), prepare the
@@ -264,6 +269,7 @@ impl Coupled {
                             .map(|target_port| ModelMessage {
                                 port_name: target_port.to_string(),
                                 content: outgoing_message.content.clone(),
+                                attributes: outgoing_message.attributes.clone(),
                             })
                             .collect()
                         })
@@ -302,12 +308,28 @@ impl DevsModel for Coupled {
     }
 
     fn until_next_event(&self) -> f64 {
+        // Parked messages on internal couplings are delivered without delay,
+        // so that the components progress with the parent simulation clock
+        if !self.state.parked_messages.is_empty() {
+            return 0.0;
+        }
         self.components
             .iter()
             .fold(f64::INFINITY, |min, component| {
                 f64::min(min, component.until_next_event())
             })
     }
+
+    fn input_ports(&self) -> Option<Vec<String>> {
+        Some(self.ports_in.flow_paths.clone())
+    }
+
+    fn rngs_mut(&mut self) -> Vec<&mut DynRng> {
+        self.components
+            .iter_mut()
+            .flat_map(|component| component.rngs_mut())
+            .collect()
+    }
 }
 
 impl Reportable for Coupled {
@@ -322,6 +344,22 @@ impl Reportable for Coupled {
     fn records(&self) -> &Vec<ModelRecord> {
         &self.state.records
     }
+
+    fn records_mut(&mut self) -> Option<&mut Vec<ModelRecord>> {
+        Some(&mut self.state.records)
+    }
+
+    fn is_stochastic(&self) -> bool {
+        self.components
+            .iter()
+            .any(|component| component.is_stochastic())
+    }
+
+    fn validate_parameters(&self) -> Result<(), SimulationError> {
+        self.components
+            .iter()
+            .try_for_each(|component| component.validate_parameters())
+    }
 }
 
 impl ReportableModel for Coupled {}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use super::model_trait::{DevsModel, Reportable, ReportableModel, SerializableModel};
@@ -5,6 +7,7 @@ use super::{ModelMessage, ModelRecord};
 use crate::input_modeling::dynamic_rng::DynRng;
 use crate::input_modeling::IndexRandomVariable;
 use crate::simulator::Services;
+use crate::utils::deserialize_infinity;
 use crate::utils::errors::SimulationError;
 
 use sim_derive::SerializableModel;
@@ -47,9 +50,13 @@ struct PortsOut {
 #[serde(rename_all = "camelCase")]
 struct State {
     phase: Phase,
+    #[serde(deserialize_with = "deserialize_infinity")]
     until_next_event: f64,
     jobs: Vec<String>,         // port, message, time
     records: Vec<ModelRecord>, // port, message, time
+    // Attributes of the passing jobs, in arrival order
+    #[serde(default)]
+    job_attributes: Vec<HashMap<String, f64>>,
 }
 
 impl Default for State {
@@ -58,6 +65,7 @@ impl Default for State {
             phase: Phase::Passive,
             until_next_event: f64::INFINITY,
             jobs: Vec::new(),
+            job_attributes: Vec::new(),
             records: Vec::new(),
         }
     }
@@ -95,7 +103,15 @@ impl ExclusiveGateway {
     fn pass_job(&mut self, incoming_message: &ModelMessage, services: &mut Services) {
         self.state.phase = Phase::Pass;
         self.state.until_next_event = 0.0;
+        // Job attributes are aligned lazily, to support deserialized
+        // configurations with passing jobs but without their attributes
+        self.state
+            .job_attributes
+            .resize(self.state.jobs.len(), HashMap::new());
         self.state.jobs.push(incoming_message.content.clone());
+        self.state
+            .job_attributes
+            .push(incoming_message.attributes.clone());
         self.record(
             services.global_time(),
             String::from("Arrival"),
@@ -125,9 +141,15 @@ impl ExclusiveGateway {
                         self.ports_out.flow_paths[departure_port_index].clone()
                     ],
                 );
+                let attributes = if self.state.job_attributes.is_empty() {
+                    HashMap::new()
+                } else {
+                    self.state.job_attributes.remove(0)
+                };
                 ModelMessage {
                     port_name: self.ports_out.flow_paths[departure_port_index].clone(),
                     content: self.state.jobs.remove(0),
+                    attributes,
                 }
             })
             .collect())
@@ -177,6 +199,14 @@ impl DevsModel for ExclusiveGateway {
     fn until_next_event(&self) -> f64 {
         self.state.until_next_event
     }
+
+    fn input_ports(&self) -> Option<Vec<String>> {
+        Some(self.ports_in.flow_paths.clone())
+    }
+
+    fn rngs_mut(&mut self) -> Vec<&mut DynRng> {
+        self.rng.iter_mut().collect()
+    }
 }
 
 impl Reportable for ExclusiveGateway {
@@ -190,6 +220,14 @@ impl Reportable for ExclusiveGateway {
     fn records(&self) -> &Vec<ModelRecord> {
         &self.state.records
     }
+
+    fn records_mut(&mut self) -> Option<&mut Vec<ModelRecord>> {
+        Some(&mut self.state.records)
+    }
+
+    fn is_stochastic(&self) -> bool {
+        true
+    }
 }
 
 impl ReportableModel for ExclusiveGateway {}
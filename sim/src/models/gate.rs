@@ -1,8 +1,11 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use super::model_trait::{DevsModel, Reportable, ReportableModel, SerializableModel};
 use super::{ModelMessage, ModelRecord};
 use crate::simulator::Services;
+use crate::utils::deserialize_infinity;
 use crate::utils::errors::SimulationError;
 
 use sim_derive::SerializableModel;
@@ -51,8 +54,12 @@ struct PortsOut {
 #[serde(rename_all = "camelCase")]
 struct State {
     phase: Phase,
+    #[serde(deserialize_with = "deserialize_infinity")]
     until_next_event: f64,
     jobs: Vec<String>,
+    // Attributes of the passing jobs, in arrival order
+    #[serde(default)]
+    job_attributes: Vec<HashMap<String, f64>>,
     records: Vec<ModelRecord>,
 }
 
@@ -62,6 +69,7 @@ impl Default for State {
             phase: Phase::Open,
             until_next_event: f64::INFINITY,
             jobs: Vec::new(),
+            job_attributes: Vec::new(),
             records: Vec::new(),
         }
     }
@@ -130,7 +138,15 @@ impl Gate {
     fn pass_job(&mut self, incoming_message: &ModelMessage, services: &mut Services) {
         self.state.phase = Phase::Pass;
         self.state.until_next_event = 0.0;
+        // Job attributes are aligned lazily, to support deserialized
+        // configurations with passing jobs but without their attributes
+        self.state
+            .job_attributes
+            .resize(self.state.jobs.len(), HashMap::new());
         self.state.jobs.push(incoming_message.content.clone());
+        self.state
+            .job_attributes
+            .push(incoming_message.attributes.clone());
         self.record(
             services.global_time(),
             String::from("Arrival"),
@@ -156,9 +172,15 @@ impl Gate {
                     String::from("Departure"),
                     self.state.jobs[0].clone(),
                 );
+                let attributes = if self.state.job_attributes.is_empty() {
+                    HashMap::new()
+                } else {
+                    self.state.job_attributes.remove(0)
+                };
                 ModelMessage {
                     port_name: self.ports_out.job.clone(),
                     content: self.state.jobs.remove(0),
+                    attributes,
                 }
             })
             .collect()
@@ -208,6 +230,14 @@ impl DevsModel for Gate {
     fn until_next_event(&self) -> f64 {
         self.state.until_next_event
     }
+
+    fn input_ports(&self) -> Option<Vec<String>> {
+        Some(vec![
+            self.ports_in.job.clone(),
+            self.ports_in.activation.clone(),
+            self.ports_in.deactivation.clone(),
+        ])
+    }
 }
 
 impl Reportable for Gate {
@@ -222,6 +252,10 @@ impl Reportable for Gate {
     fn records(&self) -> &Vec<ModelRecord> {
         &self.state.records
     }
+
+    fn records_mut(&mut self) -> Option<&mut Vec<ModelRecord>> {
+        Some(&mut self.state.records)
+    }
 }
 
 impl ReportableModel for Gate {}
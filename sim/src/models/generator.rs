@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, HashMap};
+
 use serde::{Deserialize, Serialize};
 
 use super::model_trait::{DevsModel, Reportable, ReportableModel, SerializableModel};
@@ -20,7 +22,11 @@ use simx::event_rules;
 /// case, the time between job generation is constant. This model will
 /// produce jobs through perpetuity, and the generator does not receive
 /// messages or otherwise change behavior throughout a simulation (except
-/// through the thinning function).
+/// through the thinning function).  Job content may be configured with a
+/// template, where `{seq}` is replaced with the zero-based job sequence
+/// number, and `{time}` with the global time of generation.  Jobs may also
+/// carry numeric attributes, such as a job size, sampled from configured
+/// distributions for each generated job.
 #[derive(Debug, Clone, Serialize, Deserialize, SerializableModel)]
 #[serde(rename_all = "camelCase")]
 pub struct Generator {
@@ -31,6 +37,12 @@ pub struct Generator {
     thinning: Option<Thinning>,
     ports_in: PortsIn,
     ports_out: PortsOut,
+    // Template for the content of generated jobs
+    #[serde(default)]
+    content_template: Option<String>,
+    // Distributions of the numeric attributes of generated jobs
+    #[serde(default)]
+    attributes: BTreeMap<String, ContinuousRandomVariable>,
     #[serde(default)]
     store_records: bool,
     #[serde(default)]
@@ -83,22 +95,47 @@ impl Generator {
         job_port: String,
         store_records: bool,
         rng: Option<DynRng>,
+        content_template: Option<String>,
     ) -> Self {
         Self {
             message_interdeparture_time,
             thinning,
             ports_in: PortsIn {},
             ports_out: PortsOut { job: job_port },
+            content_template,
+            attributes: BTreeMap::new(),
             store_records,
             state: State::default(),
             rng,
         }
     }
 
-    fn release_job(
+    /// Attach a numeric attribute to each generated job, sampled from the
+    /// specified distribution.
+    pub fn with_attribute(mut self, name: String, distribution: ContinuousRandomVariable) -> Self {
+        self.attributes.insert(name, distribution);
+        self
+    }
+
+    fn sample_attributes(
         &mut self,
         services: &mut Services,
-    ) -> Result<Vec<ModelMessage>, SimulationError> {
+    ) -> Result<HashMap<String, f64>, SimulationError> {
+        let rng = match &self.rng {
+            Some(rng) => rng.clone(),
+            None => services.global_rng(),
+        };
+        self.attributes
+            .iter_mut()
+            .map(|(name, distribution)| {
+                Ok((name.clone(), distribution.random_variate(rng.clone())?))
+            })
+            .collect()
+    }
+
+    /// Sample the time until the next job, recording the interarrival draw
+    /// and the resulting scheduled job time.
+    fn sample_interdeparture(&mut self, services: &mut Services) -> Result<f64, SimulationError> {
         let interdeparture = match &self.rng {
             Some(rng) => self
                 .message_interdeparture_time
@@ -107,33 +144,55 @@ impl Generator {
                 .message_interdeparture_time
                 .random_variate(services.global_rng())?,
         };
+        self.record(
+            services.global_time(),
+            String::from("Interarrival"),
+            format![
+                "{} until {}",
+                interdeparture,
+                services.global_time() + interdeparture
+            ],
+        );
+        Ok(interdeparture)
+    }
+
+    fn release_job(
+        &mut self,
+        services: &mut Services,
+    ) -> Result<Vec<ModelMessage>, SimulationError> {
+        let interdeparture = self.sample_interdeparture(services)?;
         self.state.phase = Phase::Generating;
         self.state.until_next_event = interdeparture;
         self.state.until_job = interdeparture;
         self.state.last_job += 1;
+        let content = self.job_content(services.global_time());
+        let attributes = self.sample_attributes(services)?;
         self.record(
             services.global_time(),
             String::from("Generation"),
-            format!["{} {}", self.ports_out.job, self.state.last_job],
+            content.clone(),
         );
         Ok(vec![ModelMessage {
             port_name: self.ports_out.job.clone(),
-            content: format!["{} {}", self.ports_out.job, self.state.last_job],
+            content,
+            attributes,
         }])
     }
 
+    fn job_content(&self, global_time: f64) -> String {
+        match &self.content_template {
+            Some(template) => template
+                .replace("{seq}", &(self.state.last_job - 1).to_string())
+                .replace("{time}", &global_time.to_string()),
+            None => format!["{} {}", self.ports_out.job, self.state.last_job],
+        }
+    }
+
     fn initialize_generation(
         &mut self,
         services: &mut Services,
     ) -> Result<Vec<ModelMessage>, SimulationError> {
-        let interdeparture = match &self.rng {
-            Some(rng) => self
-                .message_interdeparture_time
-                .random_variate(rng.clone())?,
-            None => self
-                .message_interdeparture_time
-                .random_variate(services.global_rng())?,
-        };
+        let interdeparture = self.sample_interdeparture(services)?;
         self.state.phase = Phase::Generating;
         self.state.until_next_event = interdeparture;
         self.state.until_job = interdeparture;
@@ -183,6 +242,22 @@ impl DevsModel for Generator {
     fn until_next_event(&self) -> f64 {
         self.state.until_next_event
     }
+
+    fn input_ports(&self) -> Option<Vec<String>> {
+        Some(Vec::new())
+    }
+
+    fn rngs_mut(&mut self) -> Vec<&mut DynRng> {
+        self.rng.iter_mut().collect()
+    }
+
+    fn message_blocked(&mut self, blocked_message: &ModelMessage, services: &mut Services) {
+        self.record(
+            services.global_time(),
+            String::from("Blocked"),
+            blocked_message.content.clone(),
+        );
+    }
 }
 
 impl Reportable for Generator {
@@ -193,6 +268,31 @@ impl Reportable for Generator {
     fn records(&self) -> &Vec<ModelRecord> {
         &self.state.records
     }
+
+    fn records_mut(&mut self) -> Option<&mut Vec<ModelRecord>> {
+        Some(&mut self.state.records)
+    }
+
+    fn status_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "status": self.status(),
+            "jobsGenerated": self.state.last_job,
+            "untilNextJob": self.state.until_next_event,
+        })
+    }
+
+    fn mean_delay(&self) -> Option<f64> {
+        // The mean interdeparture time, without regard to thinning
+        Some(self.message_interdeparture_time.mean())
+    }
+
+    fn is_stochastic(&self) -> bool {
+        true
+    }
+
+    fn validate_parameters(&self) -> Result<(), SimulationError> {
+        self.message_interdeparture_time.validate()
+    }
 }
 
 impl ReportableModel for Generator {}
@@ -1,8 +1,11 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use super::model_trait::{DevsModel, Reportable, ReportableModel, SerializableModel};
 use super::{ModelMessage, ModelRecord};
 use crate::simulator::Services;
+use crate::utils::deserialize_infinity;
 use crate::utils::errors::SimulationError;
 
 use sim_derive::SerializableModel;
@@ -38,9 +41,13 @@ struct PortsOut {
 #[serde(rename_all = "camelCase")]
 struct State {
     phase: Phase,
+    #[serde(deserialize_with = "deserialize_infinity")]
     until_next_event: f64,
     next_port_out: usize,
     jobs: Vec<String>,
+    // Attributes of the pending jobs, in arrival order
+    #[serde(default)]
+    job_attributes: Vec<HashMap<String, f64>>,
     records: Vec<ModelRecord>,
 }
 
@@ -51,6 +58,7 @@ impl Default for State {
             until_next_event: f64::INFINITY,
             next_port_out: 0,
             jobs: Vec::new(),
+            job_attributes: Vec::new(),
             records: Vec::new(),
         }
     }
@@ -78,7 +86,15 @@ impl LoadBalancer {
     fn pass_job(&mut self, incoming_message: &ModelMessage, services: &mut Services) {
         self.state.phase = Phase::LoadBalancing;
         self.state.until_next_event = 0.0;
+        // Job attributes are aligned lazily, to support deserialized
+        // configurations with pending jobs but without their attributes
+        self.state
+            .job_attributes
+            .resize(self.state.jobs.len(), HashMap::new());
         self.state.jobs.push(incoming_message.content.clone());
+        self.state
+            .job_attributes
+            .push(incoming_message.attributes.clone());
         self.record(
             services.global_time(),
             String::from("Arrival"),
@@ -104,9 +120,15 @@ impl LoadBalancer {
                 self.ports_out.flow_paths[self.state.next_port_out].clone()
             ],
         );
+        let attributes = if self.state.job_attributes.is_empty() {
+            HashMap::new()
+        } else {
+            self.state.job_attributes.remove(0)
+        };
         vec![ModelMessage {
             port_name: self.ports_out.flow_paths[self.state.next_port_out].clone(),
             content: self.state.jobs.remove(0),
+            attributes,
         }]
     }
 
@@ -148,6 +170,10 @@ impl DevsModel for LoadBalancer {
     fn until_next_event(&self) -> f64 {
         self.state.until_next_event
     }
+
+    fn input_ports(&self) -> Option<Vec<String>> {
+        Some(vec![self.ports_in.job.clone()])
+    }
 }
 
 impl Reportable for LoadBalancer {
@@ -158,6 +184,10 @@ impl Reportable for LoadBalancer {
     fn records(&self) -> &Vec<ModelRecord> {
         &self.state.records
     }
+
+    fn records_mut(&mut self) -> Option<&mut Vec<ModelRecord>> {
+        Some(&mut self.state.records)
+    }
 }
 
 impl ReportableModel for LoadBalancer {}
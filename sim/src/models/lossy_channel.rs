@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::model_trait::{DevsModel, Reportable, ReportableModel, SerializableModel};
+use super::{ModelMessage, ModelRecord, LOSS_PORT};
+use crate::input_modeling::dynamic_rng::DynRng;
+use crate::input_modeling::{BooleanRandomVariable, ContinuousRandomVariable};
+use crate::simulator::Services;
+use crate::utils::deserialize_infinity;
+use crate::utils::errors::SimulationError;
+
+use sim_derive::SerializableModel;
+
+#[cfg(feature = "simx")]
+use simx::event_rules;
+
+/// The lossy channel models a communication link with both latency and
+/// loss.  Each arriving message is dropped with a probability given by a
+/// Bernoulli distribution - a variate of 1 drops the message.  Surviving
+/// messages are delivered after a transit time, which is either fixed or
+/// sampled per message, so messages may overtake one another under a
+/// random transit time.  Dropped messages are emitted immediately on the
+/// loss port - `LOSS_PORT` by default - and are discarded if the loss port
+/// is not connected.
+#[derive(Debug, Clone, Serialize, Deserialize, SerializableModel)]
+#[serde(rename_all = "camelCase")]
+pub struct LossyChannel {
+    transit_time: TransitTime,
+    drop_distribution: BooleanRandomVariable,
+    ports_in: PortsIn,
+    ports_out: PortsOut,
+    #[serde(default)]
+    store_records: bool,
+    #[serde(default)]
+    state: State,
+    #[serde(skip)]
+    rng: Option<DynRng>,
+}
+
+/// The transit time of messages through a lossy channel - either a fixed
+/// time, or a time sampled from a distribution for each message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TransitTime {
+    Fixed(f64),
+    Random(ContinuousRandomVariable),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PortsIn {
+    job: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PortsOut {
+    job: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    loss: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct State {
+    #[serde(deserialize_with = "deserialize_infinity")]
+    until_next_event: f64,
+    // Messages in transit, in arrival order
+    jobs: Vec<TransitJob>,
+    records: Vec<ModelRecord>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            until_next_event: f64::INFINITY,
+            jobs: Vec::new(),
+            records: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransitJob {
+    content: String,
+    attributes: HashMap<String, f64>,
+    until_release: f64,
+    dropped: bool,
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl LossyChannel {
+    pub fn new(
+        transit_time: TransitTime,
+        drop_distribution: BooleanRandomVariable,
+        job_in_port: String,
+        job_out_port: String,
+        store_records: bool,
+        rng: Option<DynRng>,
+    ) -> Self {
+        Self {
+            transit_time,
+            drop_distribution,
+            ports_in: PortsIn { job: job_in_port },
+            ports_out: PortsOut {
+                job: job_out_port,
+                loss: None,
+            },
+            store_records,
+            state: State::default(),
+            rng,
+        }
+    }
+
+    /// Emit dropped messages on a custom port, rather than `LOSS_PORT`.
+    pub fn with_loss_port(mut self, loss_port: String) -> Self {
+        self.ports_out.loss = Some(loss_port);
+        self
+    }
+
+    fn rng(&self, services: &Services) -> DynRng {
+        match &self.rng {
+            Some(rng) => rng.clone(),
+            None => services.global_rng(),
+        }
+    }
+
+    fn admit_job(
+        &mut self,
+        incoming_message: &ModelMessage,
+        services: &mut Services,
+    ) -> Result<(), SimulationError> {
+        let rng = self.rng(services);
+        let dropped = self.drop_distribution.random_variate(rng.clone())?;
+        let until_release = match (dropped, &mut self.transit_time) {
+            (true, _) => 0.0,
+            (false, TransitTime::Fixed(transit_time)) => *transit_time,
+            (false, TransitTime::Random(transit_time)) => transit_time.random_variate(rng)?,
+        };
+        self.record(
+            services.global_time(),
+            String::from(if dropped { "Drop" } else { "Arrival" }),
+            incoming_message.content.clone(),
+        );
+        self.state.jobs.push(TransitJob {
+            content: incoming_message.content.clone(),
+            attributes: incoming_message.attributes.clone(),
+            until_release,
+            dropped,
+        });
+        self.state.until_next_event = self
+            .state
+            .jobs
+            .iter()
+            .fold(f64::INFINITY, |min, job| f64::min(min, job.until_release));
+        Ok(())
+    }
+
+    fn release_jobs(&mut self, services: &mut Services) -> Vec<ModelMessage> {
+        // Every message due at the current time is released, in arrival
+        // order
+        let release_time = self.state.until_next_event;
+        let (released_jobs, pending_jobs): (Vec<TransitJob>, Vec<TransitJob>) = self
+            .state
+            .jobs
+            .drain(..)
+            .partition(|job| job.until_release <= release_time);
+        self.state.jobs = pending_jobs;
+        self.state.until_next_event = self
+            .state
+            .jobs
+            .iter()
+            .fold(f64::INFINITY, |min, job| f64::min(min, job.until_release));
+        released_jobs
+            .into_iter()
+            .map(|job| {
+                let port_name = if job.dropped {
+                    self.loss_port().unwrap_or(LOSS_PORT).to_string()
+                } else {
+                    self.record(
+                        services.global_time(),
+                        String::from("Departure"),
+                        job.content.clone(),
+                    );
+                    self.ports_out.job.clone()
+                };
+                ModelMessage {
+                    port_name,
+                    content: job.content,
+                    attributes: job.attributes,
+                }
+            })
+            .collect()
+    }
+
+    fn record(&mut self, time: f64, action: String, subject: String) {
+        if self.store_records {
+            self.state.records.push(ModelRecord {
+                time,
+                action,
+                subject,
+            });
+        }
+    }
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl DevsModel for LossyChannel {
+    fn events_ext(
+        &mut self,
+        incoming_message: &ModelMessage,
+        services: &mut Services,
+    ) -> Result<(), SimulationError> {
+        if incoming_message.port_name == self.ports_in.job {
+            self.admit_job(incoming_message, services)
+        } else {
+            Err(SimulationError::InvalidMessage)
+        }
+    }
+
+    fn events_int(
+        &mut self,
+        services: &mut Services,
+    ) -> Result<Vec<ModelMessage>, SimulationError> {
+        Ok(self.release_jobs(services))
+    }
+
+    fn time_advance(&mut self, time_delta: f64) {
+        self.state.until_next_event -= time_delta;
+        self.state
+            .jobs
+            .iter_mut()
+            .for_each(|job| job.until_release -= time_delta);
+    }
+
+    fn until_next_event(&self) -> f64 {
+        self.state.until_next_event
+    }
+
+    fn input_ports(&self) -> Option<Vec<String>> {
+        Some(vec![self.ports_in.job.clone()])
+    }
+
+    fn rngs_mut(&mut self) -> Vec<&mut DynRng> {
+        self.rng.iter_mut().collect()
+    }
+
+    fn loss_port(&self) -> Option<&str> {
+        Some(self.ports_out.loss.as_deref().unwrap_or(LOSS_PORT))
+    }
+}
+
+impl Reportable for LossyChannel {
+    fn status(&self) -> String {
+        match self.state.jobs.len() {
+            0 => format!["Listening for {}s", self.ports_in.job],
+            in_transit => format!["Transmitting {} {}s", in_transit, self.ports_in.job],
+        }
+    }
+
+    fn records(&self) -> &Vec<ModelRecord> {
+        &self.state.records
+    }
+
+    fn records_mut(&mut self) -> Option<&mut Vec<ModelRecord>> {
+        Some(&mut self.state.records)
+    }
+
+    fn mean_delay(&self) -> Option<f64> {
+        match &self.transit_time {
+            TransitTime::Fixed(transit_time) => Some(*transit_time),
+            TransitTime::Random(transit_time) => Some(transit_time.mean()),
+        }
+    }
+
+    fn is_stochastic(&self) -> bool {
+        true
+    }
+
+    fn validate_parameters(&self) -> Result<(), SimulationError> {
+        match &self.transit_time {
+            TransitTime::Fixed(transit_time) if transit_time.is_nan() || *transit_time < 0.0 => {
+                Err(SimulationError::InvalidRandomVariable(format![
+                    "Fixed({}) requires a non-negative transit time",
+                    transit_time
+                ]))
+            }
+            TransitTime::Fixed(_) => Ok(()),
+            TransitTime::Random(transit_time) => transit_time.validate(),
+        }
+    }
+}
+
+impl ReportableModel for LossyChannel {}
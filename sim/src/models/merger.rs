@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::model_trait::{DevsModel, Reportable, ReportableModel, SerializableModel};
+use super::{ModelMessage, ModelRecord};
+use crate::simulator::Services;
+use crate::utils::deserialize_infinity;
+use crate::utils::errors::SimulationError;
+
+use sim_derive::SerializableModel;
+
+#[cfg(feature = "simx")]
+use simx::event_rules;
+
+/// The merger combines jobs from a set of process paths into a single
+/// process path. When jobs are pending on multiple input paths, the merger
+/// releases them using a round robin strategy across the input paths, so
+/// that a busy path cannot starve the others. There is no stochastic
+/// behavior in this model.
+#[derive(Debug, Clone, Serialize, Deserialize, SerializableModel)]
+#[serde(rename_all = "camelCase")]
+pub struct Merger {
+    ports_in: PortsIn,
+    ports_out: PortsOut,
+    #[serde(default)]
+    store_records: bool,
+    #[serde(default)]
+    state: State,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PortsIn {
+    flow_paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PortsOut {
+    job: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct State {
+    phase: Phase,
+    #[serde(deserialize_with = "deserialize_infinity")]
+    until_next_event: f64,
+    next_port_in: usize,
+    // Pending jobs, by input flow path
+    jobs: Vec<Vec<String>>,
+    // Attributes of the pending jobs, aligned with the pending jobs
+    #[serde(default)]
+    job_attributes: Vec<Vec<HashMap<String, f64>>>,
+    records: Vec<ModelRecord>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            phase: Phase::Passive,
+            until_next_event: f64::INFINITY,
+            next_port_in: 0,
+            jobs: Vec::new(),
+            job_attributes: Vec::new(),
+            records: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+enum Phase {
+    Passive,
+    Merging,
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl Merger {
+    pub fn new(flow_path_ports: Vec<String>, job_port: String, store_records: bool) -> Self {
+        Self {
+            ports_in: PortsIn {
+                flow_paths: flow_path_ports,
+            },
+            ports_out: PortsOut { job: job_port },
+            store_records,
+            state: State::default(),
+        }
+    }
+
+    fn arrival_port(&self, message_port: &str) -> Option<usize> {
+        self.ports_in
+            .flow_paths
+            .iter()
+            .position(|flow_path| flow_path == message_port)
+    }
+
+    fn pending_jobs(&self) -> usize {
+        self.state.jobs.iter().map(|jobs| jobs.len()).sum()
+    }
+
+    fn hold_job(
+        &mut self,
+        port_index: usize,
+        incoming_message: &ModelMessage,
+        services: &mut Services,
+    ) -> Result<(), SimulationError> {
+        // Pending job queues are sized lazily, to support deserialized
+        // configurations without an explicit state
+        self.state
+            .jobs
+            .resize(self.ports_in.flow_paths.len(), Vec::new());
+        self.state
+            .job_attributes
+            .resize(self.ports_in.flow_paths.len(), Vec::new());
+        let pending = self.state.jobs[port_index].len();
+        self.state.job_attributes[port_index].resize(pending, HashMap::new());
+        self.state.phase = Phase::Merging;
+        self.state.until_next_event = 0.0;
+        self.state.jobs[port_index].push(incoming_message.content.clone());
+        self.state.job_attributes[port_index].push(incoming_message.attributes.clone());
+        self.record(
+            services.global_time(),
+            String::from("Arrival"),
+            format![
+                "{} on {}",
+                incoming_message.content, self.ports_in.flow_paths[port_index]
+            ],
+        );
+        Ok(())
+    }
+
+    fn passivate(&mut self) -> Vec<ModelMessage> {
+        self.state.phase = Phase::Passive;
+        self.state.until_next_event = f64::INFINITY;
+        Vec::new()
+    }
+
+    fn send_job(&mut self, services: &mut Services) -> Vec<ModelMessage> {
+        let flow_paths_count = self.ports_in.flow_paths.len();
+        // Poll the input flow paths in round robin order, starting from the
+        // path following the last released job
+        let port_index = (0..flow_paths_count)
+            .map(|offset| (self.state.next_port_in + offset) % flow_paths_count)
+            .find(|port_index| !self.state.jobs[*port_index].is_empty())
+            .unwrap_or(self.state.next_port_in);
+        let job = self.state.jobs[port_index].remove(0);
+        let attributes = match self.state.job_attributes.get_mut(port_index) {
+            Some(job_attributes) if !job_attributes.is_empty() => job_attributes.remove(0),
+            _ => HashMap::new(),
+        };
+        self.state.until_next_event = 0.0;
+        self.state.next_port_in = (port_index + 1) % flow_paths_count;
+        self.record(
+            services.global_time(),
+            String::from("Departure"),
+            format!["{} from {}", job, self.ports_in.flow_paths[port_index]],
+        );
+        vec![ModelMessage {
+            port_name: self.ports_out.job.clone(),
+            content: job,
+            attributes,
+        }]
+    }
+
+    fn record(&mut self, time: f64, action: String, subject: String) {
+        if self.store_records {
+            self.state.records.push(ModelRecord {
+                time,
+                action,
+                subject,
+            });
+        }
+    }
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl DevsModel for Merger {
+    fn events_ext(
+        &mut self,
+        incoming_message: &ModelMessage,
+        services: &mut Services,
+    ) -> Result<(), SimulationError> {
+        match self.arrival_port(&incoming_message.port_name) {
+            Some(port_index) => self.hold_job(port_index, incoming_message, services),
+            None => Err(SimulationError::InvalidMessage),
+        }
+    }
+
+    fn events_int(
+        &mut self,
+        services: &mut Services,
+    ) -> Result<Vec<ModelMessage>, SimulationError> {
+        match self.pending_jobs() {
+            0 => Ok(self.passivate()),
+            _ => Ok(self.send_job(services)),
+        }
+    }
+
+    fn time_advance(&mut self, time_delta: f64) {
+        self.state.until_next_event -= time_delta;
+    }
+
+    fn until_next_event(&self) -> f64 {
+        self.state.until_next_event
+    }
+
+    fn input_ports(&self) -> Option<Vec<String>> {
+        Some(self.ports_in.flow_paths.clone())
+    }
+}
+
+impl Reportable for Merger {
+    fn status(&self) -> String {
+        match self.pending_jobs() {
+            0 => format!["Listening for {}s", self.ports_out.job],
+            pending => format!["Merging {} {}s", pending, self.ports_out.job],
+        }
+    }
+
+    fn records(&self) -> &Vec<ModelRecord> {
+        &self.state.records
+    }
+
+    fn records_mut(&mut self) -> Option<&mut Vec<ModelRecord>> {
+        Some(&mut self.state.records)
+    }
+}
+
+impl ReportableModel for Merger {}
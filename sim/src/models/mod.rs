@@ -3,6 +3,8 @@
 //! specifies the requirements of any additional custom models, via the
 //! `Model` trait.
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 pub mod batcher;
@@ -11,12 +13,18 @@ pub mod exclusive_gateway;
 pub mod gate;
 pub mod generator;
 pub mod load_balancer;
+pub mod lossy_channel;
+pub mod merger;
 pub mod model;
 pub mod parallel_gateway;
 pub mod processor;
+pub mod resource;
+pub mod responder;
+pub mod statistics;
 pub mod stochastic_gate;
 pub mod stopwatch;
 pub mod storage;
+pub mod transform;
 
 pub mod model_factory;
 pub mod model_repr;
@@ -28,20 +36,37 @@ pub use self::exclusive_gateway::ExclusiveGateway;
 pub use self::gate::Gate;
 pub use self::generator::Generator;
 pub use self::load_balancer::LoadBalancer;
+pub use self::lossy_channel::{LossyChannel, TransitTime};
+pub use self::merger::Merger;
 pub use self::model::Model;
+pub use self::model_factory::model_config_schema;
 pub use self::model_trait::{DevsModel, Reportable, ReportableModel};
 pub use self::parallel_gateway::ParallelGateway;
 pub use self::processor::Processor;
+pub use self::resource::Resource;
+pub use self::responder::Responder;
+pub use self::statistics::Statistics;
 pub use self::stochastic_gate::StochasticGate;
 pub use self::stopwatch::Stopwatch;
 pub use self::storage::Storage;
+pub use self::transform::Transform;
 
 pub use self::model_repr::ModelRepr;
 
+/// The conventional name of the output port on which models emit the jobs
+/// they reject - such as jobs arriving at a full processor - so that losses
+/// can be routed to a model that tallies them.  Unconnected loss ports drop
+/// the rejected jobs.
+pub const LOSS_PORT: &str = "loss";
+
+/// Model messages carry content on a model port, along with an optional
+/// bag of numeric attributes - such as a job size or class - for models
+/// that parameterize their behavior by the attributes of a job.
 #[derive(Debug, Clone)]
 pub struct ModelMessage {
     pub port_name: String,
     pub content: String,
+    pub attributes: HashMap<String, f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -3,26 +3,55 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use super::model_trait::{DevsModel, Reportable, ReportableModel, SerializableModel};
 use super::{ModelMessage, ModelRecord};
+use crate::input_modeling::dynamic_rng::DynRng;
 use crate::simulator::Services;
 use crate::utils::errors::SimulationError;
 
 /// `Model` wraps `model_type` and provides common ID functionality (a struct
 /// field and associated accessor method).  The simulator requires all models
-/// to have an ID.
+/// to have an ID.  Models may additionally have a priority, which orders
+/// simultaneous internal events - lower priorities fire first.
 #[derive(Clone)]
 pub struct Model {
     id: String,
+    priority: i32,
     inner: Box<dyn ReportableModel>,
 }
 
 impl Model {
     pub fn new(id: String, inner: Box<dyn ReportableModel>) -> Self {
-        Self { id, inner }
+        Self {
+            id,
+            priority: 0,
+            inner,
+        }
+    }
+
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    /// Set the tie-break priority of the model, for simultaneous internal
+    /// events.  The default priority is 0, and models with equal priorities
+    /// fire in simulation model order.
+    pub fn set_priority(&mut self, priority: i32) {
+        self.priority = priority;
     }
 
     pub fn id(&self) -> &str {
         self.id.as_str()
     }
+
+    /// Replace the inner model, keeping the model ID and priority.
+    pub fn set_inner(&mut self, inner: Box<dyn ReportableModel>) {
+        self.inner = inner;
+    }
+
+    /// The model type is the name under which the inner model is registered
+    /// with the model factory, such as "Generator" or "Processor".
+    pub fn model_type(&self) -> &str {
+        self.inner.get_type()
+    }
 }
 
 impl Serialize for Model {
@@ -31,6 +60,9 @@ impl Serialize for Model {
         let mut model = serializer.serialize_map(None)?;
         model.serialize_entry("id", &self.id)?;
         model.serialize_entry("type", self.inner.get_type())?;
+        if self.priority != 0 {
+            model.serialize_entry("priority", &self.priority)?;
+        }
         if let serde_yaml::Value::Mapping(map) = extra_fields {
             for (key, value) in map.iter() {
                 model.serialize_entry(&key, &value)?;
@@ -45,11 +77,17 @@ impl<'de> Deserialize<'de> for Model {
         let model_repr = super::ModelRepr::deserialize(deserializer)?;
         let concrete_model =
             super::model_factory::create::<D>(&model_repr.model_type[..], model_repr.extra)?;
-        Ok(Model::new(model_repr.id, concrete_model))
+        let mut model = Model::new(model_repr.id, concrete_model);
+        model.set_priority(model_repr.priority);
+        Ok(model)
     }
 }
 
-impl SerializableModel for Model {}
+impl SerializableModel for Model {
+    fn load_state(&mut self, state: serde_yaml::Value) -> Result<(), serde_yaml::Error> {
+        self.inner.load_state(state)
+    }
+}
 
 impl DevsModel for Model {
     fn events_ext(
@@ -67,6 +105,15 @@ impl DevsModel for Model {
         self.inner.events_int(services)
     }
 
+    fn events_ext_with_output(
+        &mut self,
+        incoming_message: &ModelMessage,
+        services: &mut Services,
+    ) -> Result<Vec<ModelMessage>, SimulationError> {
+        self.inner
+            .events_ext_with_output(incoming_message, services)
+    }
+
     fn time_advance(&mut self, time_delta: f64) {
         self.inner.time_advance(time_delta);
     }
@@ -75,6 +122,34 @@ impl DevsModel for Model {
         self.inner.until_next_event()
     }
 
+    fn can_accept(&self, port: &str) -> bool {
+        self.inner.can_accept(port)
+    }
+
+    fn message_blocked(&mut self, blocked_message: &ModelMessage, services: &mut Services) {
+        self.inner.message_blocked(blocked_message, services)
+    }
+
+    fn input_ports(&self) -> Option<Vec<String>> {
+        self.inner.input_ports()
+    }
+
+    fn metric(&self, name: &str) -> Option<f64> {
+        self.inner.metric(name)
+    }
+
+    fn loss_port(&self) -> Option<&str> {
+        self.inner.loss_port()
+    }
+
+    fn observation_target(&self) -> Option<(&str, &str)> {
+        self.inner.observation_target()
+    }
+
+    fn rngs_mut(&mut self) -> Vec<&mut DynRng> {
+        self.inner.rngs_mut()
+    }
+
     #[cfg(feature = "simx")]
     fn event_rules_scheduling(&self) -> &str {
         self.inner.event_rules_scheduling()
@@ -94,6 +169,30 @@ impl Reportable for Model {
     fn records(&self) -> &Vec<ModelRecord> {
         self.inner.records()
     }
+
+    fn records_mut(&mut self) -> Option<&mut Vec<ModelRecord>> {
+        self.inner.records_mut()
+    }
+
+    fn mean_delay(&self) -> Option<f64> {
+        self.inner.mean_delay()
+    }
+
+    fn is_stochastic(&self) -> bool {
+        self.inner.is_stochastic()
+    }
+
+    fn validate_parameters(&self) -> Result<(), SimulationError> {
+        self.inner.validate_parameters()
+    }
+
+    fn utilization(&self, end_time: f64) -> Option<f64> {
+        self.inner.utilization(end_time)
+    }
+
+    fn status_json(&self) -> serde_json::Value {
+        self.inner.status_json()
+    }
 }
 
 impl ReportableModel for Model {}
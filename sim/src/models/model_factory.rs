@@ -4,10 +4,17 @@ use serde::Deserializer;
 use std::collections::HashMap;
 
 use lazy_static::lazy_static;
+use serde_json::json;
 
 use std::sync::Mutex;
 
 pub type ModelConstructor = fn(serde_yaml::Value) -> Option<Box<dyn ReportableModel>>;
+
+/// The configuration fields of a model type, as (name, JSON Schema type,
+/// required) tuples - typically the `config_fields` of a model deriving
+/// `SerializableModel`.
+pub type ModelConfigFields = fn() -> &'static [(&'static str, Option<&'static str>, bool)];
+
 lazy_static! {
     static ref CONSTRUCTORS: Mutex<HashMap<&'static str, ModelConstructor>> = {
         let mut m = HashMap::new();
@@ -25,6 +32,11 @@ lazy_static! {
             "LoadBalancer",
             super::LoadBalancer::from_value as ModelConstructor,
         );
+        m.insert(
+            "LossyChannel",
+            super::LossyChannel::from_value as ModelConstructor,
+        );
+        m.insert("Merger", super::Merger::from_value as ModelConstructor);
         m.insert(
             "ParallelGateway",
             super::ParallelGateway::from_value as ModelConstructor,
@@ -33,6 +45,15 @@ lazy_static! {
             "Processor",
             super::Processor::from_value as ModelConstructor,
         );
+        m.insert("Resource", super::Resource::from_value as ModelConstructor);
+        m.insert(
+            "Responder",
+            super::Responder::from_value as ModelConstructor,
+        );
+        m.insert(
+            "Statistics",
+            super::Statistics::from_value as ModelConstructor,
+        );
         m.insert(
             "StochasticGate",
             super::StochasticGate::from_value as ModelConstructor,
@@ -42,6 +63,72 @@ lazy_static! {
             super::Stopwatch::from_value as ModelConstructor,
         );
         m.insert("Storage", super::Storage::from_value as ModelConstructor);
+        m.insert(
+            "Transform",
+            super::Transform::from_value as ModelConstructor,
+        );
+        Mutex::new(m)
+    };
+    static ref CONFIG_FIELDS: Mutex<HashMap<&'static str, ModelConfigFields>> = {
+        let mut m = HashMap::new();
+        m.insert(
+            "Batcher",
+            super::Batcher::config_fields as ModelConfigFields,
+        );
+        m.insert(
+            "ExclusiveGateway",
+            super::ExclusiveGateway::config_fields as ModelConfigFields,
+        );
+        m.insert("Gate", super::Gate::config_fields as ModelConfigFields);
+        m.insert(
+            "Generator",
+            super::Generator::config_fields as ModelConfigFields,
+        );
+        m.insert(
+            "LoadBalancer",
+            super::LoadBalancer::config_fields as ModelConfigFields,
+        );
+        m.insert(
+            "LossyChannel",
+            super::LossyChannel::config_fields as ModelConfigFields,
+        );
+        m.insert("Merger", super::Merger::config_fields as ModelConfigFields);
+        m.insert(
+            "ParallelGateway",
+            super::ParallelGateway::config_fields as ModelConfigFields,
+        );
+        m.insert(
+            "Processor",
+            super::Processor::config_fields as ModelConfigFields,
+        );
+        m.insert(
+            "Resource",
+            super::Resource::config_fields as ModelConfigFields,
+        );
+        m.insert(
+            "Responder",
+            super::Responder::config_fields as ModelConfigFields,
+        );
+        m.insert(
+            "Statistics",
+            super::Statistics::config_fields as ModelConfigFields,
+        );
+        m.insert(
+            "StochasticGate",
+            super::StochasticGate::config_fields as ModelConfigFields,
+        );
+        m.insert(
+            "Stopwatch",
+            super::Stopwatch::config_fields as ModelConfigFields,
+        );
+        m.insert(
+            "Storage",
+            super::Storage::config_fields as ModelConfigFields,
+        );
+        m.insert(
+            "Transform",
+            super::Transform::config_fields as ModelConfigFields,
+        );
         Mutex::new(m)
     };
     static ref VARIANTS: Vec<&'static str> = {
@@ -55,6 +142,12 @@ lazy_static! {
     };
 }
 
+/// Register a model type with the model factory, so that configurations
+/// using the type name deserialize through the standard `Model` path.  This
+/// allows downstream crates to plug in custom `ReportableModel`
+/// implementations - typically with the `register!` macro of `sim_derive`,
+/// for models deriving `SerializableModel`.  Registering an existing type
+/// name replaces its constructor.
 pub fn register(model_type: &'static str, model_constructor: ModelConstructor) {
     CONSTRUCTORS
         .lock()
@@ -62,6 +155,72 @@ pub fn register(model_type: &'static str, model_constructor: ModelConstructor) {
         .insert(model_type, model_constructor);
 }
 
+/// Register the configuration fields of a model type, for the model
+/// configuration schema.  The `register!` macro of `sim_derive` registers
+/// the fields along with the constructor.
+pub fn register_config_fields(model_type: &'static str, config_fields: ModelConfigFields) {
+    CONFIG_FIELDS
+        .lock()
+        .unwrap()
+        .insert(model_type, config_fields);
+}
+
+/// This function emits a JSON Schema describing model configurations - the
+/// registered model types, and the configuration fields of each, for
+/// editor validation and autocompletion of hand-authored YAML or JSON.
+/// Field types are constrained where they map directly to a JSON type.
+pub fn model_config_schema() -> serde_json::Value {
+    let config_fields = CONFIG_FIELDS.lock().unwrap();
+    let mut model_types: Vec<&'static str> = CONSTRUCTORS.lock().unwrap().keys().copied().collect();
+    model_types.sort();
+    let variants: Vec<serde_json::Value> = model_types
+        .iter()
+        .map(|model_type| {
+            let fields = config_fields
+                .get(model_type)
+                .map(|config_fields| config_fields())
+                .unwrap_or_default();
+            let mut properties = serde_json::Map::new();
+            properties.insert("type".to_string(), json!({ "const": model_type }));
+            fields.iter().for_each(|(name, json_type, _)| {
+                properties.insert(
+                    name.to_string(),
+                    match json_type {
+                        Some(json_type) => json!({ "type": json_type }),
+                        None => json!({}),
+                    },
+                );
+            });
+            let required: Vec<&str> = std::iter::once("type")
+                .chain(
+                    fields
+                        .iter()
+                        .filter(|(_, _, required)| *required)
+                        .map(|(name, _, _)| *name),
+                )
+                .collect();
+            json!({
+                "title": model_type,
+                "properties": properties,
+                "required": required,
+            })
+        })
+        .collect();
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "Model",
+        "type": "object",
+        "properties": {
+            "id": { "type": "string" },
+            "type": { "enum": model_types },
+            "priority": { "type": "integer" },
+        },
+        "required": ["id", "type"],
+        "oneOf": variants,
+    })
+}
+
+/// Create a model of a registered model type from its configuration fields.
 pub fn create<'de, D: Deserializer<'de>>(
     model_type: &str,
     extra_fields: serde_yaml::Value,
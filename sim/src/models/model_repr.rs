@@ -5,6 +5,8 @@ pub struct ModelRepr {
     pub id: String,
     #[serde(rename = "type")]
     pub model_type: String,
+    #[serde(default)]
+    pub priority: i32,
     #[serde(flatten)]
     pub extra: serde_yaml::Value,
 }
@@ -1,4 +1,5 @@
 use super::{ModelMessage, ModelRecord};
+use crate::input_modeling::dynamic_rng::DynRng;
 use crate::simulator::Services;
 use crate::utils::errors::SimulationError;
 
@@ -28,12 +29,23 @@ pub trait SerializableModel {
     fn serialize(&self) -> serde_yaml::Value {
         serde_yaml::Value::Null
     }
+    /// Load the internal state of the model from a (possibly partial)
+    /// representation of its `state` field, for warm starts from a known
+    /// operating point.  Fields of the state that are not provided retain
+    /// their current values.
+    fn load_state(&mut self, _state: serde_yaml::Value) -> Result<(), serde_yaml::Error> {
+        Err(serde::de::Error::custom(
+            "the model does not support state loading",
+        ))
+    }
 }
 
 /// The `DevsModel` trait defines everything required for a model to operate
 /// within the discrete event simulation.  The simulator formalism (Discrete
 /// Event System Specification) requires `events_ext`, `events_int`,
-/// `time_advance`, and `until_next_event`.
+/// `time_advance`, and `until_next_event`.  Messages emitted on the same port
+/// by one `events_int` (or `events_ext_with_output`) call are routed, and
+/// delivered to each target model, in emission order.
 pub trait DevsModel: ModelClone + SerializableModel {
     fn events_ext(
         &mut self,
@@ -44,6 +56,61 @@ pub trait DevsModel: ModelClone + SerializableModel {
         -> Result<Vec<ModelMessage>, SimulationError>;
     fn time_advance(&mut self, time_delta: f64);
     fn until_next_event(&self) -> f64;
+    /// Reactive models may emit messages immediately on an external event,
+    /// without an artificial zero-delay internal event.  The simulator
+    /// routes these messages within the same step.  By default, the
+    /// external event is handled by `events_ext`, and no messages are
+    /// emitted.
+    fn events_ext_with_output(
+        &mut self,
+        incoming_message: &ModelMessage,
+        services: &mut Services,
+    ) -> Result<Vec<ModelMessage>, SimulationError> {
+        self.events_ext(incoming_message, services)?;
+        Ok(Vec::new())
+    }
+    /// Models with finite capacity report whether a message on the given
+    /// port can currently be accepted.  With backpressure enabled, the
+    /// simulator holds messages that cannot be accepted, instead of
+    /// delivering them.
+    fn can_accept(&self, _port: &str) -> bool {
+        true
+    }
+    /// Source models are notified, on each step, of emitted messages held
+    /// by backpressure because the target model cannot accept them - so
+    /// that upstream models learn the downstream model is full.  The
+    /// blocked message port is the source model output port.  By default,
+    /// the notification is ignored.
+    fn message_blocked(&mut self, _blocked_message: &ModelMessage, _services: &mut Services) {}
+    /// Models may report the names of their input ports, for validation of
+    /// message delivery.  Models that do not report input ports are not
+    /// validated.
+    fn input_ports(&self) -> Option<Vec<String>> {
+        None
+    }
+    /// Models may report named metrics, such as a queue length, for
+    /// sampling by a `Statistics` model.
+    fn metric(&self, _name: &str) -> Option<f64> {
+        None
+    }
+    /// Models that reject jobs report the output port on which the rejected
+    /// jobs are emitted - by convention, `LOSS_PORT`.
+    fn loss_port(&self) -> Option<&str> {
+        None
+    }
+    /// Models observing a metric of another model report the target model
+    /// ID and the metric name.  Before each internal event of the observing
+    /// model, the simulator provides the current metric value through
+    /// `Services::observation`.
+    fn observation_target(&self) -> Option<(&str, &str)> {
+        None
+    }
+    /// Models holding their own random number generators provide mutable
+    /// access to them, so that the simulator can save, restore, and copy
+    /// the generator states - such as when rolling back a failed step.
+    fn rngs_mut(&mut self) -> Vec<&mut DynRng> {
+        Vec::new()
+    }
     #[cfg(feature = "simx")]
     fn event_rules_scheduling(&self) -> &str;
     #[cfg(feature = "simx")]
@@ -56,6 +123,40 @@ pub trait DevsModel: ModelClone + SerializableModel {
 pub trait Reportable {
     fn status(&self) -> String;
     fn records(&self) -> &Vec<ModelRecord>;
+    /// Models with append-only records may provide mutable access to them,
+    /// so that simulation snapshots can exclude the records, and restore
+    /// them by truncation.  Models without this access are copied in full.
+    fn records_mut(&mut self) -> Option<&mut Vec<ModelRecord>> {
+        None
+    }
+    /// Models may report an estimate of the mean delay they introduce - such
+    /// as a mean service time - for static latency analysis of a simulation.
+    fn mean_delay(&self) -> Option<f64> {
+        None
+    }
+    /// Models report whether they consume random numbers, for
+    /// reproducibility auditing and variance reduction techniques.
+    fn is_stochastic(&self) -> bool {
+        false
+    }
+    /// Models validate their configured parameters, such as random variable
+    /// distribution parameters, prior to simulation.
+    fn validate_parameters(&self) -> Result<(), SimulationError> {
+        Ok(())
+    }
+    /// Models with a server may report the fraction of time the server was
+    /// busy, from the start of the simulation to `end_time`, as derived from
+    /// their records.  Models without a meaningful utilization, or without
+    /// stored records, report none.
+    fn utilization(&self, _end_time: f64) -> Option<f64> {
+        None
+    }
+    /// Models report a machine-readable status, for programmatic consumers
+    /// of the status.  By default, the status string is reported under a
+    /// `status` field.
+    fn status_json(&self) -> serde_json::Value {
+        serde_json::json!({ "status": self.status() })
+    }
 }
 
 /// A `ReportableModel` has the required Discrete Event System Specification
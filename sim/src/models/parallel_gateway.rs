@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use super::model_trait::{DevsModel, Reportable, ReportableModel, SerializableModel};
 use super::{ModelMessage, ModelRecord};
 use crate::simulator::Services;
+use crate::utils::deserialize_infinity;
 use crate::utils::errors::SimulationError;
 
 use sim_derive::SerializableModel;
@@ -48,8 +49,12 @@ struct PortsOut {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct State {
+    #[serde(deserialize_with = "deserialize_infinity")]
     until_next_event: f64,
     collections: HashMap<String, usize>,
+    // Attributes of the collected jobs, merged across the arrivals of each job
+    #[serde(default)]
+    collection_attributes: HashMap<String, HashMap<String, f64>>,
     records: Vec<ModelRecord>,
 }
 
@@ -58,6 +63,7 @@ impl Default for State {
         Self {
             until_next_event: f64::INFINITY,
             collections: HashMap::new(),
+            collection_attributes: HashMap::new(),
             records: Vec::new(),
         }
     }
@@ -103,6 +109,11 @@ impl ParallelGateway {
             .collections
             .entry(incoming_message.content.clone())
             .or_insert(0) += 1;
+        self.state
+            .collection_attributes
+            .entry(incoming_message.content.clone())
+            .or_default()
+            .extend(incoming_message.attributes.clone());
         self.record(
             services.global_time(),
             String::from("Arrival"),
@@ -123,6 +134,11 @@ impl ParallelGateway {
             .0
             .to_string();
         self.state.collections.remove(&completed_collection);
+        let attributes = self
+            .state
+            .collection_attributes
+            .remove(&completed_collection)
+            .unwrap_or_default();
         Ok(self
             .ports_out
             .flow_paths
@@ -137,6 +153,7 @@ impl ParallelGateway {
                 messages.push(ModelMessage {
                     port_name: flow_path.clone(),
                     content: completed_collection.clone(),
+                    attributes: attributes.clone(),
                 });
                 messages
             }))
@@ -188,6 +205,10 @@ impl DevsModel for ParallelGateway {
     fn until_next_event(&self) -> f64 {
         self.state.until_next_event
     }
+
+    fn input_ports(&self) -> Option<Vec<String>> {
+        Some(self.ports_in.flow_paths.clone())
+    }
 }
 
 impl Reportable for ParallelGateway {
@@ -198,6 +219,10 @@ impl Reportable for ParallelGateway {
     fn records(&self) -> &Vec<ModelRecord> {
         &self.state.records
     }
+
+    fn records_mut(&mut self) -> Option<&mut Vec<ModelRecord>> {
+        Some(&mut self.state.records)
+    }
 }
 
 impl ReportableModel for ParallelGateway {}
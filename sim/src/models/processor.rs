@@ -1,10 +1,13 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use super::model_trait::{DevsModel, Reportable, ReportableModel, SerializableModel};
-use super::{ModelMessage, ModelRecord};
+use super::{ModelMessage, ModelRecord, LOSS_PORT};
 use crate::input_modeling::dynamic_rng::DynRng;
 use crate::input_modeling::ContinuousRandomVariable;
 use crate::simulator::Services;
+use crate::utils::deserialize_infinity;
 use crate::utils::errors::SimulationError;
 
 use sim_derive::SerializableModel;
@@ -20,13 +23,22 @@ use simx::event_rules;
 /// variable distribution dictates the amount of time required to process a
 /// job. For non-stochastic behavior, a random variable distribution with a
 /// single point can be used - in which case, every job takes exactly the
-/// specified amount of time to process.
+/// specified amount of time to process.  The service time may be scaled by
+/// a numeric attribute of each job, such as a job size, and job attributes
+/// are carried through to the processed jobs.  The queue capacity includes
+/// the job in process, so a capacity of 0 or 1 is a loss system (M/M/1/1) -
+/// a job is processed only if the processor is idle, and is otherwise lost.
+/// Lost jobs are emitted on the loss port - `LOSS_PORT` by default - and
+/// are dropped if the loss port is not connected.
 #[derive(Debug, Clone, Serialize, Deserialize, SerializableModel)]
 #[serde(rename_all = "camelCase")]
 pub struct Processor {
     service_time: ContinuousRandomVariable,
     #[serde(default = "max_usize")]
     queue_capacity: usize,
+    // Job attribute scaling the service time of each job
+    #[serde(default)]
+    service_time_scale: Option<String>,
     ports_in: PortsIn,
     ports_out: PortsOut,
     #[serde(default)]
@@ -57,14 +69,22 @@ enum ArrivalPort {
 #[serde(rename_all = "camelCase")]
 struct PortsOut {
     job: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    loss: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct State {
     phase: Phase,
+    #[serde(deserialize_with = "deserialize_infinity")]
     until_next_event: f64,
     queue: Vec<String>,
+    // Attributes of the queued jobs, in queue order
+    #[serde(default)]
+    queue_attributes: Vec<HashMap<String, f64>>,
+    #[serde(default)]
+    jobs_processed: usize,
     records: Vec<ModelRecord>,
 }
 
@@ -74,6 +94,8 @@ impl Default for State {
             phase: Phase::Passive,
             until_next_event: f64::INFINITY,
             queue: Vec::new(),
+            queue_attributes: Vec::new(),
+            jobs_processed: 0,
             records: Vec::new(),
         }
     }
@@ -98,9 +120,11 @@ impl Processor {
         Self {
             service_time,
             queue_capacity: queue_capacity.unwrap_or(usize::MAX),
+            service_time_scale: None,
             ports_in: PortsIn { job: job_port },
             ports_out: PortsOut {
                 job: processed_job_port,
+                loss: None,
             },
             store_records,
             state: State::default(),
@@ -108,6 +132,41 @@ impl Processor {
         }
     }
 
+    /// Scale the service time of each job by the specified job attribute.
+    /// Jobs without the attribute are processed with an unscaled service
+    /// time.
+    pub fn with_service_time_scale(mut self, attribute: String) -> Self {
+        self.service_time_scale = Some(attribute);
+        self
+    }
+
+    /// Emit jobs lost to a full queue on the specified port, instead of the
+    /// default loss port.
+    pub fn with_loss_port(mut self, loss_port: String) -> Self {
+        self.ports_out.loss = Some(loss_port);
+        self
+    }
+
+    // The queue capacity includes the job in process, so a capacity of 0
+    // behaves as a capacity of 1 - no queueing beyond the job in process
+    fn capacity(&self) -> usize {
+        self.queue_capacity.max(1)
+    }
+
+    fn service_time(&mut self, services: &mut Services) -> Result<f64, SimulationError> {
+        let service_time = match &self.rng {
+            Some(rng) => self.service_time.random_variate(rng.clone())?,
+            None => self.service_time.random_variate(services.global_rng())?,
+        };
+        let scale = self
+            .service_time_scale
+            .as_ref()
+            .and_then(|attribute| self.state.queue_attributes.first()?.get(attribute))
+            .copied()
+            .unwrap_or(1.0);
+        Ok(service_time * scale)
+    }
+
     fn arrival_port(&self, message_port: &str) -> ArrivalPort {
         if message_port == self.ports_in.job {
             ArrivalPort::Job
@@ -116,8 +175,20 @@ impl Processor {
         }
     }
 
-    fn add_job(&mut self, incoming_message: &ModelMessage, services: &mut Services) {
+    fn enqueue(&mut self, incoming_message: &ModelMessage) {
+        // Queue attributes are aligned lazily, to support deserialized
+        // configurations with a queue but without queue attributes
+        self.state
+            .queue_attributes
+            .resize(self.state.queue.len(), HashMap::new());
         self.state.queue.push(incoming_message.content.clone());
+        self.state
+            .queue_attributes
+            .push(incoming_message.attributes.clone());
+    }
+
+    fn add_job(&mut self, incoming_message: &ModelMessage, services: &mut Services) {
+        self.enqueue(incoming_message);
         self.record(
             services.global_time(),
             String::from("Arrival"),
@@ -130,12 +201,9 @@ impl Processor {
         incoming_message: &ModelMessage,
         services: &mut Services,
     ) -> Result<(), SimulationError> {
-        self.state.queue.push(incoming_message.content.clone());
+        self.enqueue(incoming_message);
         self.state.phase = Phase::Active;
-        self.state.until_next_event = match &self.rng {
-            Some(rng) => self.service_time.random_variate(rng.clone())?,
-            None => self.service_time.random_variate(services.global_rng())?,
-        };
+        self.state.until_next_event = self.service_time(services)?;
         self.record(
             services.global_time(),
             String::from("Arrival"),
@@ -157,15 +225,23 @@ impl Processor {
         );
     }
 
+    fn lost_jobs(&self, incoming_message: &ModelMessage, rejected: bool) -> Vec<ModelMessage> {
+        match (self.loss_port(), rejected) {
+            (Some(loss_port), true) => vec![ModelMessage {
+                content: incoming_message.content.clone(),
+                port_name: loss_port.to_string(),
+                attributes: incoming_message.attributes.clone(),
+            }],
+            _ => Vec::new(),
+        }
+    }
+
     fn process_next(
         &mut self,
         services: &mut Services,
     ) -> Result<Vec<ModelMessage>, SimulationError> {
         self.state.phase = Phase::Active;
-        self.state.until_next_event = match &self.rng {
-            Some(rng) => self.service_time.random_variate(rng.clone())?,
-            None => self.service_time.random_variate(services.global_rng())?,
-        };
+        self.state.until_next_event = self.service_time(services)?;
         self.record(
             services.global_time(),
             String::from("Processing Start"),
@@ -176,8 +252,14 @@ impl Processor {
 
     fn release_job(&mut self, services: &mut Services) -> Vec<ModelMessage> {
         let job = self.state.queue.remove(0);
+        let attributes = if self.state.queue_attributes.is_empty() {
+            HashMap::new()
+        } else {
+            self.state.queue_attributes.remove(0)
+        };
         self.state.phase = Phase::Passive;
         self.state.until_next_event = 0.0;
+        self.state.jobs_processed += 1;
         self.record(
             services.global_time(),
             String::from("Departure"),
@@ -186,6 +268,7 @@ impl Processor {
         vec![ModelMessage {
             content: job,
             port_name: self.ports_out.job.clone(),
+            attributes,
         }]
     }
 
@@ -216,7 +299,7 @@ impl DevsModel for Processor {
         match (
             self.arrival_port(&incoming_message.port_name),
             self.state.queue.is_empty(),
-            self.state.queue.len() == self.queue_capacity,
+            self.state.queue.len() >= self.capacity(),
         ) {
             (ArrivalPort::Job, true, true) => Err(SimulationError::InvalidModelState),
             (ArrivalPort::Job, false, true) => Ok(self.ignore_job(incoming_message, services)),
@@ -226,6 +309,16 @@ impl DevsModel for Processor {
         }
     }
 
+    fn events_ext_with_output(
+        &mut self,
+        incoming_message: &ModelMessage,
+        services: &mut Services,
+    ) -> Result<Vec<ModelMessage>, SimulationError> {
+        let rejected = !self.can_accept(&incoming_message.port_name);
+        self.events_ext(incoming_message, services)?;
+        Ok(self.lost_jobs(incoming_message, rejected))
+    }
+
     fn events_int(
         &mut self,
         services: &mut Services,
@@ -244,6 +337,32 @@ impl DevsModel for Processor {
     fn until_next_event(&self) -> f64 {
         self.state.until_next_event
     }
+
+    fn input_ports(&self) -> Option<Vec<String>> {
+        Some(vec![self.ports_in.job.clone()])
+    }
+
+    fn rngs_mut(&mut self) -> Vec<&mut DynRng> {
+        self.rng.iter_mut().collect()
+    }
+
+    fn can_accept(&self, port: &str) -> bool {
+        match self.arrival_port(port) {
+            ArrivalPort::Job => self.state.queue.len() < self.capacity(),
+            ArrivalPort::Unknown => true,
+        }
+    }
+
+    fn loss_port(&self) -> Option<&str> {
+        Some(self.ports_out.loss.as_deref().unwrap_or(LOSS_PORT))
+    }
+
+    fn metric(&self, name: &str) -> Option<f64> {
+        match name {
+            "queue_length" => Some(self.state.queue.len() as f64),
+            _ => None,
+        }
+    }
 }
 
 impl Reportable for Processor {
@@ -257,6 +376,54 @@ impl Reportable for Processor {
     fn records(&self) -> &Vec<ModelRecord> {
         &self.state.records
     }
+
+    fn records_mut(&mut self) -> Option<&mut Vec<ModelRecord>> {
+        Some(&mut self.state.records)
+    }
+
+    fn status_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "status": self.status(),
+            "processing": self.state.phase == Phase::Active,
+            "queueLength": self.state.queue.len(),
+            "jobsProcessed": self.state.jobs_processed,
+        })
+    }
+
+    fn mean_delay(&self) -> Option<f64> {
+        // The mean service time, without regard to attribute scaling
+        Some(self.service_time.mean())
+    }
+
+    fn is_stochastic(&self) -> bool {
+        true
+    }
+
+    fn validate_parameters(&self) -> Result<(), SimulationError> {
+        self.service_time.validate()
+    }
+
+    fn utilization(&self, end_time: f64) -> Option<f64> {
+        if !self.store_records || end_time <= 0.0 {
+            return None;
+        }
+        // The processor is busy from the start of processing a job until
+        // the job departs, or until the end time for the job in process
+        let (busy_time, processing_start) =
+            self.state
+                .records
+                .iter()
+                .fold((0.0, None), |(busy_time, processing_start), record| match (
+                    record.action.as_str(),
+                    processing_start,
+                ) {
+                    ("Processing Start", _) => (busy_time, Some(record.time)),
+                    ("Departure", Some(start)) => (busy_time + record.time - start, None),
+                    _ => (busy_time, processing_start),
+                });
+        let busy_time = busy_time + processing_start.map_or(0.0, |start| end_time - start);
+        Some(busy_time / end_time)
+    }
 }
 
 impl ReportableModel for Processor {}
@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::model_trait::{DevsModel, Reportable, ReportableModel, SerializableModel};
+use super::{ModelMessage, ModelRecord};
+use crate::simulator::Services;
+use crate::utils::deserialize_infinity;
+use crate::utils::errors::SimulationError;
+
+use sim_derive::SerializableModel;
+
+#[cfg(feature = "simx")]
+use simx::event_rules;
+
+/// The resource model holds a fixed number of units of a shared, limited
+/// resource - such as machines or staff. A job seizes a unit by arriving on
+/// the seize port, and is granted the unit on the granted port as soon as a
+/// unit is available. Jobs waiting for a unit are granted units in FIFO
+/// order, as units are returned on the release port. There is no stochastic
+/// behavior in this model.
+#[derive(Debug, Clone, Serialize, Deserialize, SerializableModel)]
+#[serde(rename_all = "camelCase")]
+pub struct Resource {
+    units: usize,
+    ports_in: PortsIn,
+    ports_out: PortsOut,
+    #[serde(default)]
+    store_records: bool,
+    #[serde(default)]
+    state: State,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PortsIn {
+    seize: String,
+    release: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum ArrivalPort {
+    Seize,
+    Release,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PortsOut {
+    granted: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct State {
+    #[serde(deserialize_with = "deserialize_infinity")]
+    until_next_event: f64,
+    units_in_use: usize,
+    // Jobs waiting for a unit, in arrival order
+    waiting_jobs: Vec<String>,
+    // Attributes of the waiting jobs, in arrival order
+    #[serde(default)]
+    waiting_attributes: Vec<HashMap<String, f64>>,
+    // Jobs granted a unit, awaiting departure
+    granted_jobs: Vec<String>,
+    // Attributes of the granted jobs, in grant order
+    #[serde(default)]
+    granted_attributes: Vec<HashMap<String, f64>>,
+    records: Vec<ModelRecord>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            until_next_event: f64::INFINITY,
+            units_in_use: 0,
+            waiting_jobs: Vec::new(),
+            waiting_attributes: Vec::new(),
+            granted_jobs: Vec::new(),
+            granted_attributes: Vec::new(),
+            records: Vec::new(),
+        }
+    }
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl Resource {
+    pub fn new(
+        units: usize,
+        seize_port: String,
+        release_port: String,
+        granted_port: String,
+        store_records: bool,
+    ) -> Self {
+        Self {
+            units,
+            ports_in: PortsIn {
+                seize: seize_port,
+                release: release_port,
+            },
+            ports_out: PortsOut {
+                granted: granted_port,
+            },
+            store_records,
+            state: State::default(),
+        }
+    }
+
+    fn arrival_port(&self, message_port: &str) -> ArrivalPort {
+        if message_port == self.ports_in.seize {
+            ArrivalPort::Seize
+        } else if message_port == self.ports_in.release {
+            ArrivalPort::Release
+        } else {
+            ArrivalPort::Unknown
+        }
+    }
+
+    fn available_units(&self) -> usize {
+        // A loaded state may have more units in use than the resource has
+        self.units.saturating_sub(self.state.units_in_use)
+    }
+
+    fn seize_unit(&mut self, incoming_message: &ModelMessage, services: &mut Services) {
+        self.record(
+            services.global_time(),
+            String::from("Arrival"),
+            incoming_message.content.clone(),
+        );
+        // Job attributes are aligned lazily, to support deserialized
+        // configurations with waiting jobs but without their attributes
+        self.state
+            .waiting_attributes
+            .resize(self.state.waiting_jobs.len(), HashMap::new());
+        self.state
+            .waiting_jobs
+            .push(incoming_message.content.clone());
+        self.state
+            .waiting_attributes
+            .push(incoming_message.attributes.clone());
+        self.grant_units(services);
+    }
+
+    fn release_unit(
+        &mut self,
+        incoming_message: &ModelMessage,
+        services: &mut Services,
+    ) -> Result<(), SimulationError> {
+        if self.state.units_in_use == 0 {
+            return Err(SimulationError::InvalidModelState);
+        }
+        self.state.units_in_use -= 1;
+        self.record(
+            services.global_time(),
+            String::from("Release"),
+            incoming_message.content.clone(),
+        );
+        self.grant_units(services);
+        Ok(())
+    }
+
+    fn grant_units(&mut self, services: &mut Services) {
+        while self.available_units() > 0 && !self.state.waiting_jobs.is_empty() {
+            let job = self.state.waiting_jobs.remove(0);
+            let attributes = if self.state.waiting_attributes.is_empty() {
+                HashMap::new()
+            } else {
+                self.state.waiting_attributes.remove(0)
+            };
+            self.state.units_in_use += 1;
+            self.record(services.global_time(), String::from("Seize"), job.clone());
+            self.state
+                .granted_attributes
+                .resize(self.state.granted_jobs.len(), HashMap::new());
+            self.state.granted_jobs.push(job);
+            self.state.granted_attributes.push(attributes);
+        }
+        if !self.state.granted_jobs.is_empty() {
+            self.state.until_next_event = 0.0;
+        }
+    }
+
+    fn send_jobs(&mut self, services: &mut Services) -> Vec<ModelMessage> {
+        self.state.until_next_event = f64::INFINITY;
+        (0..self.state.granted_jobs.len())
+            .map(|_| {
+                let job = self.state.granted_jobs.remove(0);
+                let attributes = if self.state.granted_attributes.is_empty() {
+                    HashMap::new()
+                } else {
+                    self.state.granted_attributes.remove(0)
+                };
+                self.record(
+                    services.global_time(),
+                    String::from("Departure"),
+                    job.clone(),
+                );
+                ModelMessage {
+                    port_name: self.ports_out.granted.clone(),
+                    content: job,
+                    attributes,
+                }
+            })
+            .collect()
+    }
+
+    fn record(&mut self, time: f64, action: String, subject: String) {
+        if self.store_records {
+            self.state.records.push(ModelRecord {
+                time,
+                action,
+                subject,
+            });
+        }
+    }
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl DevsModel for Resource {
+    fn events_ext(
+        &mut self,
+        incoming_message: &ModelMessage,
+        services: &mut Services,
+    ) -> Result<(), SimulationError> {
+        match self.arrival_port(&incoming_message.port_name) {
+            ArrivalPort::Seize => {
+                self.seize_unit(incoming_message, services);
+                Ok(())
+            }
+            ArrivalPort::Release => self.release_unit(incoming_message, services),
+            ArrivalPort::Unknown => Err(SimulationError::InvalidMessage),
+        }
+    }
+
+    fn events_int(
+        &mut self,
+        services: &mut Services,
+    ) -> Result<Vec<ModelMessage>, SimulationError> {
+        Ok(self.send_jobs(services))
+    }
+
+    fn time_advance(&mut self, time_delta: f64) {
+        self.state.until_next_event -= time_delta;
+    }
+
+    fn until_next_event(&self) -> f64 {
+        self.state.until_next_event
+    }
+
+    fn input_ports(&self) -> Option<Vec<String>> {
+        Some(vec![
+            self.ports_in.seize.clone(),
+            self.ports_in.release.clone(),
+        ])
+    }
+
+    fn metric(&self, name: &str) -> Option<f64> {
+        match name {
+            "available_units" => Some(self.available_units() as f64),
+            "queue_length" => Some(self.state.waiting_jobs.len() as f64),
+            _ => None,
+        }
+    }
+}
+
+impl Reportable for Resource {
+    fn status(&self) -> String {
+        format![
+            "{} of {} units available, with {} waiting",
+            self.available_units(),
+            self.units,
+            self.state.waiting_jobs.len()
+        ]
+    }
+
+    fn records(&self) -> &Vec<ModelRecord> {
+        &self.state.records
+    }
+
+    fn records_mut(&mut self) -> Option<&mut Vec<ModelRecord>> {
+        Some(&mut self.state.records)
+    }
+
+    fn utilization(&self, end_time: f64) -> Option<f64> {
+        if !self.store_records || end_time <= 0.0 || self.units == 0 {
+            return None;
+        }
+        // Units in use, weighted by the time until the next seize or release
+        let (busy_unit_time, units_in_use, last_time) = self
+            .state
+            .records
+            .iter()
+            .filter(|record| record.action == "Seize" || record.action == "Release")
+            .fold(
+                (0.0, 0.0, 0.0),
+                |(busy_unit_time, units_in_use, last_time), record| {
+                    let busy_unit_time = busy_unit_time + units_in_use * (record.time - last_time);
+                    match record.action.as_str() {
+                        "Seize" => (busy_unit_time, units_in_use + 1.0, record.time),
+                        _ => (busy_unit_time, units_in_use - 1.0, record.time),
+                    }
+                },
+            );
+        let busy_unit_time = busy_unit_time + units_in_use * (end_time - last_time);
+        Some(busy_unit_time / (end_time * self.units as f64))
+    }
+}
+
+impl ReportableModel for Resource {}
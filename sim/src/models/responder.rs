@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::model_trait::{DevsModel, Reportable, ReportableModel, SerializableModel};
+use super::{ModelMessage, ModelRecord};
+use crate::input_modeling::dynamic_rng::DynRng;
+use crate::input_modeling::ContinuousRandomVariable;
+use crate::simulator::Services;
+use crate::utils::deserialize_infinity;
+use crate::utils::errors::SimulationError;
+
+use sim_derive::SerializableModel;
+
+#[cfg(feature = "simx")]
+use simx::event_rules;
+
+/// The responder models the server side of a request/response exchange.
+/// Each arriving request carries a correlation ID in its content - the text
+/// following the last correlation delimiter, or the whole content if the
+/// delimiter is absent.  After a service time, sampled for each request,
+/// the responder emits a response with content of the form
+/// `{response port}{delimiter}{correlation ID}`, so that a requester can
+/// match responses to requests.  Requests are served concurrently, so
+/// responses may be emitted in a different order than their requests
+/// arrived.
+#[derive(Debug, Clone, Serialize, Deserialize, SerializableModel)]
+#[serde(rename_all = "camelCase")]
+pub struct Responder {
+    service_time: ContinuousRandomVariable,
+    #[serde(default = "default_correlation_delimiter")]
+    correlation_delimiter: String,
+    ports_in: PortsIn,
+    ports_out: PortsOut,
+    #[serde(default)]
+    store_records: bool,
+    #[serde(default)]
+    state: State,
+    #[serde(skip)]
+    rng: Option<DynRng>,
+}
+
+fn default_correlation_delimiter() -> String {
+    String::from(":")
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PortsIn {
+    request: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PortsOut {
+    response: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct State {
+    #[serde(deserialize_with = "deserialize_infinity")]
+    until_next_event: f64,
+    // Requests in service, in arrival order
+    requests: Vec<PendingRequest>,
+    records: Vec<ModelRecord>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            until_next_event: f64::INFINITY,
+            requests: Vec::new(),
+            records: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PendingRequest {
+    correlation_id: String,
+    attributes: HashMap<String, f64>,
+    until_response: f64,
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl Responder {
+    pub fn new(
+        service_time: ContinuousRandomVariable,
+        request_port: String,
+        response_port: String,
+        store_records: bool,
+        rng: Option<DynRng>,
+    ) -> Self {
+        Self {
+            service_time,
+            correlation_delimiter: default_correlation_delimiter(),
+            ports_in: PortsIn {
+                request: request_port,
+            },
+            ports_out: PortsOut {
+                response: response_port,
+            },
+            store_records,
+            state: State::default(),
+            rng,
+        }
+    }
+
+    /// Extract correlation IDs after a custom delimiter, rather than `:`.
+    pub fn with_correlation_delimiter(mut self, correlation_delimiter: String) -> Self {
+        self.correlation_delimiter = correlation_delimiter;
+        self
+    }
+
+    fn correlation_id<'a>(&self, content: &'a str) -> &'a str {
+        match content.rfind(&self.correlation_delimiter) {
+            Some(index) if !self.correlation_delimiter.is_empty() => {
+                &content[index + self.correlation_delimiter.len()..]
+            }
+            _ => content,
+        }
+    }
+
+    fn accept_request(
+        &mut self,
+        incoming_message: &ModelMessage,
+        services: &mut Services,
+    ) -> Result<(), SimulationError> {
+        let until_response = match &self.rng {
+            Some(rng) => self.service_time.random_variate(rng.clone())?,
+            None => self.service_time.random_variate(services.global_rng())?,
+        };
+        let correlation_id = self.correlation_id(&incoming_message.content).to_string();
+        self.record(
+            services.global_time(),
+            String::from("Request"),
+            correlation_id.clone(),
+        );
+        self.state.requests.push(PendingRequest {
+            correlation_id,
+            attributes: incoming_message.attributes.clone(),
+            until_response,
+        });
+        self.state.until_next_event = self
+            .state
+            .requests
+            .iter()
+            .fold(f64::INFINITY, |min, request| {
+                f64::min(min, request.until_response)
+            });
+        Ok(())
+    }
+
+    fn respond(&mut self, services: &mut Services) -> Vec<ModelMessage> {
+        // Every request due at the current time is answered, in arrival
+        // order
+        let response_time = self.state.until_next_event;
+        let (answered_requests, pending_requests): (Vec<PendingRequest>, Vec<PendingRequest>) =
+            self.state
+                .requests
+                .drain(..)
+                .partition(|request| request.until_response <= response_time);
+        self.state.requests = pending_requests;
+        self.state.until_next_event = self
+            .state
+            .requests
+            .iter()
+            .fold(f64::INFINITY, |min, request| {
+                f64::min(min, request.until_response)
+            });
+        answered_requests
+            .into_iter()
+            .map(|request| {
+                self.record(
+                    services.global_time(),
+                    String::from("Response"),
+                    request.correlation_id.clone(),
+                );
+                ModelMessage {
+                    port_name: self.ports_out.response.clone(),
+                    content: format![
+                        "{}{}{}",
+                        self.ports_out.response, self.correlation_delimiter, request.correlation_id
+                    ],
+                    attributes: request.attributes,
+                }
+            })
+            .collect()
+    }
+
+    fn record(&mut self, time: f64, action: String, subject: String) {
+        if self.store_records {
+            self.state.records.push(ModelRecord {
+                time,
+                action,
+                subject,
+            });
+        }
+    }
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl DevsModel for Responder {
+    fn events_ext(
+        &mut self,
+        incoming_message: &ModelMessage,
+        services: &mut Services,
+    ) -> Result<(), SimulationError> {
+        if incoming_message.port_name == self.ports_in.request {
+            self.accept_request(incoming_message, services)
+        } else {
+            Err(SimulationError::InvalidMessage)
+        }
+    }
+
+    fn events_int(
+        &mut self,
+        services: &mut Services,
+    ) -> Result<Vec<ModelMessage>, SimulationError> {
+        Ok(self.respond(services))
+    }
+
+    fn time_advance(&mut self, time_delta: f64) {
+        self.state.until_next_event -= time_delta;
+        self.state
+            .requests
+            .iter_mut()
+            .for_each(|request| request.until_response -= time_delta);
+    }
+
+    fn until_next_event(&self) -> f64 {
+        self.state.until_next_event
+    }
+
+    fn input_ports(&self) -> Option<Vec<String>> {
+        Some(vec![self.ports_in.request.clone()])
+    }
+
+    fn rngs_mut(&mut self) -> Vec<&mut DynRng> {
+        self.rng.iter_mut().collect()
+    }
+}
+
+impl Reportable for Responder {
+    fn status(&self) -> String {
+        match self.state.requests.len() {
+            0 => format!["Listening for {}s", self.ports_in.request],
+            pending => format!["Serving {} {}s", pending, self.ports_in.request],
+        }
+    }
+
+    fn records(&self) -> &Vec<ModelRecord> {
+        &self.state.records
+    }
+
+    fn records_mut(&mut self) -> Option<&mut Vec<ModelRecord>> {
+        Some(&mut self.state.records)
+    }
+
+    fn mean_delay(&self) -> Option<f64> {
+        Some(self.service_time.mean())
+    }
+
+    fn is_stochastic(&self) -> bool {
+        true
+    }
+
+    fn validate_parameters(&self) -> Result<(), SimulationError> {
+        self.service_time.validate()
+    }
+}
+
+impl ReportableModel for Responder {}
@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+
+use super::model_trait::{DevsModel, Reportable, ReportableModel, SerializableModel};
+use super::{ModelMessage, ModelRecord};
+use crate::simulator::Services;
+use crate::utils::errors::SimulationError;
+
+use sim_derive::SerializableModel;
+
+#[cfg(feature = "simx")]
+use simx::event_rules;
+
+/// The statistics model periodically samples a metric of a target model,
+/// such as the queue length of a processor, and accumulates a time-weighted
+/// running mean and variance of the metric. Each sampled value is held
+/// until the next sample, so the time-weighted mean estimates the long-run
+/// average of the metric - for example, the mean number in system of a
+/// queueing process. There is no stochastic behavior in this model.
+#[derive(Debug, Clone, Serialize, Deserialize, SerializableModel)]
+#[serde(rename_all = "camelCase")]
+pub struct Statistics {
+    sampling_interval: f64,
+    #[serde(rename = "targetID")]
+    target_id: String,
+    metric: String,
+    #[serde(default)]
+    store_records: bool,
+    #[serde(default)]
+    state: State,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct State {
+    until_next_event: f64,
+    last_sample: Option<(f64, f64)>,
+    total_time: f64,
+    weighted_sum: f64,
+    weighted_squares_sum: f64,
+    records: Vec<ModelRecord>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            until_next_event: 0.0,
+            last_sample: None,
+            total_time: 0.0,
+            weighted_sum: 0.0,
+            weighted_squares_sum: 0.0,
+            records: Vec::new(),
+        }
+    }
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl Statistics {
+    pub fn new(
+        sampling_interval: f64,
+        target_id: String,
+        metric: String,
+        store_records: bool,
+    ) -> Self {
+        Self {
+            sampling_interval,
+            target_id,
+            metric,
+            store_records,
+            state: State::default(),
+        }
+    }
+
+    fn mean(&self) -> f64 {
+        self.state.weighted_sum / self.state.total_time
+    }
+
+    fn variance(&self) -> f64 {
+        self.state.weighted_squares_sum / self.state.total_time - self.mean().powi(2)
+    }
+
+    fn sample(&mut self, services: &mut Services) -> Result<Vec<ModelMessage>, SimulationError> {
+        let value = services
+            .observation()
+            .ok_or(SimulationError::InvalidModelConfiguration)?;
+        // The prior sample is held over the interval since it was taken
+        if let Some((time, prior_value)) = self.state.last_sample {
+            let elapsed = services.global_time() - time;
+            self.state.total_time += elapsed;
+            self.state.weighted_sum += prior_value * elapsed;
+            self.state.weighted_squares_sum += prior_value.powi(2) * elapsed;
+        }
+        self.state.last_sample = Some((services.global_time(), value));
+        self.state.until_next_event = self.sampling_interval;
+        self.record(
+            services.global_time(),
+            String::from("Sample"),
+            value.to_string(),
+        );
+        Ok(Vec::new())
+    }
+
+    fn record(&mut self, time: f64, action: String, subject: String) {
+        if self.store_records {
+            self.state.records.push(ModelRecord {
+                time,
+                action,
+                subject,
+            });
+        }
+    }
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl DevsModel for Statistics {
+    fn events_ext(
+        &mut self,
+        _incoming_message: &ModelMessage,
+        _services: &mut Services,
+    ) -> Result<(), SimulationError> {
+        Ok(())
+    }
+
+    fn events_int(
+        &mut self,
+        services: &mut Services,
+    ) -> Result<Vec<ModelMessage>, SimulationError> {
+        self.sample(services)
+    }
+
+    fn time_advance(&mut self, time_delta: f64) {
+        self.state.until_next_event -= time_delta;
+    }
+
+    fn until_next_event(&self) -> f64 {
+        self.state.until_next_event
+    }
+
+    fn input_ports(&self) -> Option<Vec<String>> {
+        Some(Vec::new())
+    }
+
+    fn observation_target(&self) -> Option<(&str, &str)> {
+        Some((&self.target_id, &self.metric))
+    }
+}
+
+impl Reportable for Statistics {
+    fn status(&self) -> String {
+        if self.state.total_time > 0.0 {
+            format![
+                "Mean {} and variance {} for {} of {}",
+                self.mean(),
+                self.variance(),
+                self.metric,
+                self.target_id
+            ]
+        } else {
+            format!["Sampling {} of {}", self.metric, self.target_id]
+        }
+    }
+
+    fn records(&self) -> &Vec<ModelRecord> {
+        &self.state.records
+    }
+
+    fn records_mut(&mut self) -> Option<&mut Vec<ModelRecord>> {
+        Some(&mut self.state.records)
+    }
+}
+
+impl ReportableModel for Statistics {}
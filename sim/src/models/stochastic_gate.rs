@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use super::model_trait::{DevsModel, Reportable, ReportableModel, SerializableModel};
@@ -5,6 +7,7 @@ use super::{ModelMessage, ModelRecord};
 use crate::input_modeling::dynamic_rng::DynRng;
 use crate::input_modeling::BooleanRandomVariable;
 use crate::simulator::Services;
+use crate::utils::deserialize_infinity;
 use crate::utils::errors::SimulationError;
 
 use sim_derive::SerializableModel;
@@ -49,6 +52,7 @@ struct PortsOut {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct State {
+    #[serde(deserialize_with = "deserialize_infinity")]
     until_next_event: f64,
     jobs: Vec<Job>,
     records: Vec<ModelRecord>,
@@ -68,6 +72,8 @@ impl Default for State {
 #[serde(rename_all = "camelCase")]
 pub struct Job {
     pub content: String,
+    #[serde(default)]
+    pub attributes: HashMap<String, f64>,
     pub pass: bool,
 }
 
@@ -106,6 +112,7 @@ impl StochasticGate {
         self.state.until_next_event = 0.0;
         self.state.jobs.push(Job {
             content: incoming_message.content.clone(),
+            attributes: incoming_message.attributes.clone(),
             pass: match &self.rng {
                 Some(rng) => self.pass_distribution.random_variate(rng.clone())?,
                 None => self
@@ -137,6 +144,7 @@ impl StochasticGate {
         vec![ModelMessage {
             content: job.content,
             port_name: self.ports_out.job.clone(),
+            attributes: job.attributes,
         }]
     }
 
@@ -189,6 +197,14 @@ impl DevsModel for StochasticGate {
     fn until_next_event(&self) -> f64 {
         self.state.until_next_event
     }
+
+    fn input_ports(&self) -> Option<Vec<String>> {
+        Some(vec![self.ports_in.job.clone()])
+    }
+
+    fn rngs_mut(&mut self) -> Vec<&mut DynRng> {
+        self.rng.iter_mut().collect()
+    }
 }
 
 impl Reportable for StochasticGate {
@@ -199,6 +215,14 @@ impl Reportable for StochasticGate {
     fn records(&self) -> &Vec<ModelRecord> {
         &self.state.records
     }
+
+    fn records_mut(&mut self) -> Option<&mut Vec<ModelRecord>> {
+        Some(&mut self.state.records)
+    }
+
+    fn is_stochastic(&self) -> bool {
+        true
+    }
 }
 
 impl ReportableModel for StochasticGate {}
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::iter::once;
 
 use serde::{Deserialize, Serialize};
@@ -5,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use super::model_trait::{DevsModel, Reportable, ReportableModel, SerializableModel};
 use super::{ModelMessage, ModelRecord};
 use crate::simulator::Services;
+use crate::utils::deserialize_infinity;
 use crate::utils::errors::SimulationError;
 
 use sim_derive::SerializableModel;
@@ -62,6 +64,7 @@ pub enum Metric {
 #[serde(rename_all = "camelCase")]
 struct State {
     phase: Phase,
+    #[serde(deserialize_with = "deserialize_infinity")]
     until_next_event: f64,
     jobs: Vec<Job>,
     records: Vec<ModelRecord>,
@@ -90,6 +93,8 @@ pub struct Job {
     name: String,
     start: Option<f64>,
     stop: Option<f64>,
+    #[serde(default)]
+    attributes: HashMap<String, f64>,
 }
 
 fn some_duration(job: &Job) -> Option<(String, f64)> {
@@ -145,13 +150,26 @@ impl Stopwatch {
                 name: incoming_message.content.clone(),
                 start: None,
                 stop: None,
+                attributes: HashMap::new(),
             });
         }
-        self.state
+        let job = self
+            .state
             .jobs
             .iter_mut()
             .find(|job| job.name == incoming_message.content)
-            .unwrap()
+            .unwrap();
+        job.attributes.extend(incoming_message.attributes.clone());
+        job
+    }
+
+    fn job_attributes(&self, job_name: &str) -> HashMap<String, f64> {
+        self.state
+            .jobs
+            .iter()
+            .find(|job| job.name == job_name)
+            .map(|job| job.attributes.clone())
+            .unwrap_or_default()
     }
 
     fn minimum_duration_job(&self) -> Option<String> {
@@ -225,6 +243,7 @@ impl Stopwatch {
         once(self.minimum_duration_job())
             .flatten()
             .map(|job| ModelMessage {
+                attributes: self.job_attributes(&job),
                 content: job,
                 port_name: self.ports_out.job.clone(),
             })
@@ -243,6 +262,7 @@ impl Stopwatch {
         once(self.maximum_duration_job())
             .flatten()
             .map(|job| ModelMessage {
+                attributes: self.job_attributes(&job),
                 content: job,
                 port_name: self.ports_out.job.clone(),
             })
@@ -299,6 +319,14 @@ impl DevsModel for Stopwatch {
     fn until_next_event(&self) -> f64 {
         self.state.until_next_event
     }
+
+    fn input_ports(&self) -> Option<Vec<String>> {
+        Some(vec![
+            self.ports_in.start.clone(),
+            self.ports_in.stop.clone(),
+            self.ports_in.metric.clone(),
+        ])
+    }
 }
 
 impl Reportable for Stopwatch {
@@ -322,6 +350,10 @@ impl Reportable for Stopwatch {
     fn records(&self) -> &Vec<ModelRecord> {
         &self.state.records
     }
+
+    fn records_mut(&mut self) -> Option<&mut Vec<ModelRecord>> {
+        Some(&mut self.state.records)
+    }
 }
 
 impl ReportableModel for Stopwatch {}
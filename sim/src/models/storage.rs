@@ -1,8 +1,11 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use super::model_trait::{DevsModel, Reportable, ReportableModel, SerializableModel};
 use super::{ModelMessage, ModelRecord};
 use crate::simulator::Services;
+use crate::utils::deserialize_infinity;
 use crate::utils::errors::SimulationError;
 
 use sim_derive::SerializableModel;
@@ -45,8 +48,12 @@ struct PortsOut {
 #[serde(rename_all = "camelCase")]
 struct State {
     phase: Phase,
+    #[serde(deserialize_with = "deserialize_infinity")]
     until_next_event: f64,
     job: Option<String>,
+    // Attributes of the stored job
+    #[serde(default)]
+    job_attributes: HashMap<String, f64>,
     records: Vec<ModelRecord>,
 }
 
@@ -56,6 +63,7 @@ impl Default for State {
             phase: Phase::Passive,
             until_next_event: f64::INFINITY,
             job: None,
+            job_attributes: HashMap::new(),
             records: Vec::new(),
         }
     }
@@ -105,6 +113,7 @@ impl Storage {
 
     fn hold_job(&mut self, incoming_message: &ModelMessage, services: &mut Services) {
         self.state.job = Some(incoming_message.content.clone());
+        self.state.job_attributes = incoming_message.attributes.clone();
         self.record(
             services.global_time(),
             String::from("Arrival"),
@@ -124,6 +133,7 @@ impl Storage {
             Some(job) => vec![ModelMessage {
                 port_name: self.ports_out.stored.clone(),
                 content: job.clone(),
+                attributes: self.state.job_attributes.clone(),
             }],
             None => Vec::new(),
         }
@@ -177,6 +187,10 @@ impl DevsModel for Storage {
     fn until_next_event(&self) -> f64 {
         self.state.until_next_event
     }
+
+    fn input_ports(&self) -> Option<Vec<String>> {
+        Some(vec![self.ports_in.put.clone(), self.ports_in.get.clone()])
+    }
 }
 
 impl Reportable for Storage {
@@ -190,6 +204,17 @@ impl Reportable for Storage {
     fn records(&self) -> &Vec<ModelRecord> {
         &self.state.records
     }
+
+    fn records_mut(&mut self) -> Option<&mut Vec<ModelRecord>> {
+        Some(&mut self.state.records)
+    }
+
+    fn status_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "status": self.status(),
+            "stored": self.state.job,
+        })
+    }
 }
 
 impl ReportableModel for Storage {}
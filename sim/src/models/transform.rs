@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::model_trait::{DevsModel, Reportable, ReportableModel, SerializableModel};
+use super::{ModelMessage, ModelRecord};
+use crate::simulator::{ContentTransform, Services};
+use crate::utils::deserialize_infinity;
+use crate::utils::errors::SimulationError;
+
+use sim_derive::SerializableModel;
+
+#[cfg(feature = "simx")]
+use simx::event_rules;
+
+/// The transform model rewrites the content of jobs - for example, adding
+/// a prefix or replacing a substring - and releases the transformed jobs
+/// after a fixed delay, which is zero by default.  Unlike a connector
+/// content transform, the transform model keeps records of the jobs it
+/// rewrites.  Job attributes are carried through to the transformed jobs.
+/// There is no stochastic behavior in this model.
+#[derive(Debug, Clone, Serialize, Deserialize, SerializableModel)]
+#[serde(rename_all = "camelCase")]
+pub struct Transform {
+    transform: ContentTransform,
+    #[serde(default)]
+    delay: f64,
+    ports_in: PortsIn,
+    ports_out: PortsOut,
+    #[serde(default)]
+    store_records: bool,
+    #[serde(default)]
+    state: State,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PortsIn {
+    job: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PortsOut {
+    job: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct State {
+    #[serde(deserialize_with = "deserialize_infinity")]
+    until_next_event: f64,
+    // Transformed jobs awaiting release, in arrival order
+    jobs: Vec<PendingJob>,
+    records: Vec<ModelRecord>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            until_next_event: f64::INFINITY,
+            jobs: Vec::new(),
+            records: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PendingJob {
+    content: String,
+    attributes: HashMap<String, f64>,
+    until_release: f64,
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl Transform {
+    pub fn new(
+        transform: ContentTransform,
+        delay: f64,
+        job_in_port: String,
+        job_out_port: String,
+        store_records: bool,
+    ) -> Self {
+        Self {
+            transform,
+            delay,
+            ports_in: PortsIn { job: job_in_port },
+            ports_out: PortsOut { job: job_out_port },
+            store_records,
+            state: State::default(),
+        }
+    }
+
+    fn hold_job(&mut self, incoming_message: &ModelMessage, services: &mut Services) {
+        let content = self.transform.apply(&incoming_message.content);
+        self.record(
+            services.global_time(),
+            String::from("Arrival"),
+            incoming_message.content.clone(),
+        );
+        self.state.jobs.push(PendingJob {
+            content,
+            attributes: incoming_message.attributes.clone(),
+            until_release: self.delay,
+        });
+        self.state.until_next_event = self.state.jobs[0].until_release;
+    }
+
+    fn release_jobs(&mut self, services: &mut Services) -> Vec<ModelMessage> {
+        // Jobs share a fixed delay, so jobs are released in arrival order,
+        // along with any jobs due at the same time
+        let release_time = self.state.until_next_event;
+        let released_count = self
+            .state
+            .jobs
+            .iter()
+            .take_while(|job| job.until_release <= release_time)
+            .count();
+        let released_jobs: Vec<PendingJob> = self.state.jobs.drain(..released_count).collect();
+        self.state.until_next_event = self
+            .state
+            .jobs
+            .first()
+            .map_or(f64::INFINITY, |job| job.until_release);
+        released_jobs
+            .into_iter()
+            .map(|job| {
+                self.record(
+                    services.global_time(),
+                    String::from("Departure"),
+                    job.content.clone(),
+                );
+                ModelMessage {
+                    port_name: self.ports_out.job.clone(),
+                    content: job.content,
+                    attributes: job.attributes,
+                }
+            })
+            .collect()
+    }
+
+    fn record(&mut self, time: f64, action: String, subject: String) {
+        if self.store_records {
+            self.state.records.push(ModelRecord {
+                time,
+                action,
+                subject,
+            });
+        }
+    }
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl DevsModel for Transform {
+    fn events_ext(
+        &mut self,
+        incoming_message: &ModelMessage,
+        services: &mut Services,
+    ) -> Result<(), SimulationError> {
+        if incoming_message.port_name == self.ports_in.job {
+            self.hold_job(incoming_message, services);
+            Ok(())
+        } else {
+            Err(SimulationError::InvalidMessage)
+        }
+    }
+
+    fn events_int(
+        &mut self,
+        services: &mut Services,
+    ) -> Result<Vec<ModelMessage>, SimulationError> {
+        Ok(self.release_jobs(services))
+    }
+
+    fn time_advance(&mut self, time_delta: f64) {
+        self.state.until_next_event -= time_delta;
+        self.state
+            .jobs
+            .iter_mut()
+            .for_each(|job| job.until_release -= time_delta);
+    }
+
+    fn until_next_event(&self) -> f64 {
+        self.state.until_next_event
+    }
+
+    fn input_ports(&self) -> Option<Vec<String>> {
+        Some(vec![self.ports_in.job.clone()])
+    }
+}
+
+impl Reportable for Transform {
+    fn status(&self) -> String {
+        match self.state.jobs.len() {
+            0 => format!["Listening for {}s", self.ports_in.job],
+            pending => format!["Transforming {} {}s", pending, self.ports_in.job],
+        }
+    }
+
+    fn records(&self) -> &Vec<ModelRecord> {
+        &self.state.records
+    }
+
+    fn records_mut(&mut self) -> Option<&mut Vec<ModelRecord>> {
+        Some(&mut self.state.records)
+    }
+
+    fn mean_delay(&self) -> Option<f64> {
+        Some(self.delay)
+    }
+}
+
+impl ReportableModel for Transform {}
@@ -3,11 +3,17 @@
 //! (IID) samples are analyzed with the `IndependentSample`.  Time series
 //! (including those with initialization bias and autocorrelation) can be
 //! analyzed with `TerminatingSimulationOutput` or `SteadyStateOutput`.
+//! Theoretical queueing metrics, for validation of simulated results, are
+//! available with `mm1_theoretical`.
+
+use std::collections::{HashMap, VecDeque};
 
 use num_traits::{Float, NumAssign};
 use serde::{Deserialize, Serialize};
 
 pub mod t_scores;
+use crate::models::ModelRecord;
+use crate::simulator::Message;
 use crate::utils::errors::SimulationError;
 use crate::utils::usize_sqrt;
 
@@ -345,6 +351,114 @@ where
     }
 }
 
+/// The theoretical steady-state metrics of an M/M/1 queue - Poisson
+/// arrivals, exponential service times, and a single server with an
+/// unbounded queue - as a reference for simulated results.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Mm1Metrics {
+    /// The server utilization, ρ = λ/μ
+    pub utilization: f64,
+    /// The mean number of jobs in the system, L = ρ/(1-ρ)
+    pub mean_number_in_system: f64,
+    /// The mean number of jobs waiting in the queue, Lq = ρ²/(1-ρ)
+    pub mean_number_in_queue: f64,
+    /// The mean time a job spends in the system, W = 1/(μ-λ)
+    pub mean_time_in_system: f64,
+    /// The mean time a job spends waiting in the queue, Wq = ρ/(μ-λ)
+    pub mean_time_in_queue: f64,
+}
+
+/// This function calculates the theoretical steady-state metrics of an
+/// M/M/1 queue, such as a `Generator` with exponential interarrival times
+/// (rate `lambda`) feeding a `Processor` with exponential service times
+/// (rate `mu`).  The rates must be positive, and the queue must be stable -
+/// a utilization below 1.
+pub fn mm1_theoretical(lambda: f64, mu: f64) -> Result<Mm1Metrics, SimulationError> {
+    if !(lambda > 0.0 && mu > 0.0) {
+        return Err(SimulationError::InvalidDistributionParameter);
+    }
+    let utilization = lambda / mu;
+    if utilization >= 1.0 {
+        return Err(SimulationError::UnstableQueue(utilization));
+    }
+    Ok(Mm1Metrics {
+        utilization,
+        mean_number_in_system: utilization / (1.0 - utilization),
+        mean_number_in_queue: utilization.powi(2) / (1.0 - utilization),
+        mean_time_in_system: 1.0 / (mu - lambda),
+        mean_time_in_queue: utilization / (mu - lambda),
+    })
+}
+
+/// This function calculates the time-weighted mean of a value extracted
+/// from model records - such as a queue length - where each record holds
+/// its value until the next record, and the last record holds its value
+/// until `end_time`.  The records must be in time order.  The mean is NaN
+/// when there are no records, or the records span no time.
+pub fn time_weighted_mean(
+    records: &[ModelRecord],
+    field_extractor: impl Fn(&ModelRecord) -> f64,
+    end_time: f64,
+) -> f64 {
+    let start_time = match records.first() {
+        Some(record) => record.time,
+        None => return f64::NAN,
+    };
+    let weighted_sum: f64 = records
+        .iter()
+        .enumerate()
+        .map(|(index, record)| {
+            let interval_end = records
+                .get(index + 1)
+                .map_or(end_time, |next_record| next_record.time);
+            field_extractor(record) * (interval_end - record.time)
+        })
+        .sum();
+    weighted_sum / (end_time - start_time)
+}
+
+/// This function calculates the latencies of each connector hop, from
+/// messages such as those from `step_until`.  The latency of a hop is the
+/// time from a message arriving at a target model, until the target model
+/// emits the same content onward - the delay accumulated after that hop.
+/// Each arrival is matched with the earliest unmatched onward emission of
+/// its content, and arrivals without an onward emission, such as arrivals
+/// at a sink, are omitted.  Hops are keyed by their endpoints, as
+/// `source_id:source_port -> target_id:target_port`.
+pub fn connector_latencies(messages: &[Message]) -> HashMap<String, Vec<f64>> {
+    let mut ordered_messages: Vec<&Message> = messages.iter().collect();
+    ordered_messages.sort_by(|a, b| a.time().total_cmp(b.time()));
+    let mut emissions: HashMap<(&str, &str), VecDeque<f64>> = HashMap::new();
+    ordered_messages.iter().for_each(|message| {
+        emissions
+            .entry((message.source_id(), message.content()))
+            .or_default()
+            .push_back(*message.time());
+    });
+    let mut latencies: HashMap<String, Vec<f64>> = HashMap::new();
+    ordered_messages.iter().for_each(|message| {
+        if let Some(emission_times) = emissions.get_mut(&(message.target_id(), message.content())) {
+            while let Some(emission_time) = emission_times.pop_front() {
+                if emission_time >= *message.time() {
+                    latencies
+                        .entry(format![
+                            "{}:{} -> {}:{}",
+                            message.source_id(),
+                            message.source_port(),
+                            message.target_id(),
+                            message.target_port()
+                        ])
+                        .or_default()
+                        .push(emission_time - message.time());
+                    break;
+                }
+            }
+        }
+    });
+    latencies
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -362,4 +476,52 @@ mod tests {
         assert!((confidence_interval.lower - 0.7492630635369267).abs() < epsilon());
         assert!((confidence_interval.upper - 1.534736936463073).abs() < epsilon());
     }
+
+    #[test]
+    fn mm1_theoretical_metrics() {
+        let metrics = mm1_theoretical(0.5, 0.8).unwrap();
+        let utilization = 0.5 / 0.8;
+        assert!((metrics.utilization - utilization).abs() < epsilon());
+        assert!(
+            (metrics.mean_number_in_system - utilization / (1.0 - utilization)).abs() < epsilon()
+        );
+        // Little's law relates the number in system to the time in system
+        assert!(
+            (metrics.mean_number_in_system - 0.5 * metrics.mean_time_in_system).abs() < epsilon()
+        );
+        assert!(
+            (metrics.mean_number_in_queue - 0.5 * metrics.mean_time_in_queue).abs() < epsilon()
+        );
+    }
+
+    #[test]
+    fn mm1_theoretical_rejects_unstable_queues() {
+        assert!(matches!(
+            mm1_theoretical(1.0, 1.0),
+            Err(SimulationError::UnstableQueue(_))
+        ));
+        assert!(matches!(
+            mm1_theoretical(0.0, 1.0),
+            Err(SimulationError::InvalidDistributionParameter)
+        ));
+    }
+
+    #[test]
+    fn time_weighted_mean_weights_by_duration() {
+        let records: Vec<ModelRecord> = [(0.0, "2"), (1.0, "5"), (4.0, "1")]
+            .iter()
+            .map(|(time, queue_length)| ModelRecord {
+                time: *time,
+                action: String::from("Queue Length"),
+                subject: queue_length.to_string(),
+            })
+            .collect();
+        let queue_length = |record: &ModelRecord| record.subject.parse::<f64>().unwrap();
+        // (2 * 1 + 5 * 3 + 1 * 6) / 10
+        let weighted_mean = time_weighted_mean(&records, queue_length, 10.0);
+        assert!((weighted_mean - 2.3).abs() < epsilon());
+        let naive_mean = records.iter().map(queue_length).sum::<f64>() / records.len() as f64;
+        assert!((weighted_mean - naive_mean).abs() > 0.1);
+        assert!(time_weighted_mean(&[], queue_length, 10.0).is_nan());
+    }
 }
@@ -0,0 +1,66 @@
+use super::{Checker, Connector, Simulation};
+use crate::input_modeling::dyn_rng;
+use crate::input_modeling::dynamic_rng::{DynRng, SimulationRng};
+use crate::models::Model;
+use crate::utils::errors::SimulationError;
+
+/// The `SimulationBuilder` provides incremental construction of a
+/// `Simulation` - adding models and connecting their ports one at a time,
+/// instead of assembling the full model and connector lists up front.  The
+/// configuration is validated with the `Checker` when the simulation is
+/// built.
+#[derive(Default)]
+pub struct SimulationBuilder {
+    models: Vec<Model>,
+    connectors: Vec<Connector>,
+    rng: Option<DynRng>,
+}
+
+impl SimulationBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a model to the simulation.
+    pub fn add_model(mut self, model: Model) -> Self {
+        self.models.push(model);
+        self
+    }
+
+    /// Connect a source model port to a target model port.  Connector IDs
+    /// are generated sequentially, as `connector-01`, `connector-02`, etc.
+    pub fn connect(
+        mut self,
+        source_id: &str,
+        source_port: &str,
+        target_id: &str,
+        target_port: &str,
+    ) -> Self {
+        self.connectors.push(Connector::new(
+            format!["connector-{:02}", self.connectors.len() + 1],
+            source_id.to_string(),
+            target_id.to_string(),
+            source_port.to_string(),
+            target_port.to_string(),
+        ));
+        self
+    }
+
+    /// Use the provided random number generator as the simulation global
+    /// random number generator.
+    pub fn with_rng(mut self, rng: impl SimulationRng + 'static) -> Self {
+        self.rng = Some(dyn_rng(rng));
+        self
+    }
+
+    /// Build the simulation, returning an error if the configuration fails
+    /// the `Checker` validation.
+    pub fn build(self) -> Result<Simulation, SimulationError> {
+        let mut simulation = Simulation::post(self.models, self.connectors);
+        if let Some(rng) = self.rng {
+            simulation.services.global_rng = rng;
+        }
+        simulation.check()?;
+        Ok(simulation)
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use super::Simulation;
+use crate::models::Reportable;
+use crate::utils::errors::SimulationError;
+
+/// The `Checker` validates the configuration of a simulation - the models
+/// and the connectors between them - prior to execution.  Each rule is
+/// available individually, and `check` runs the default bundle of rules.
+pub trait Checker {
+    /// Every connector source must be a model in the simulation.
+    fn connectors_source_to_model(&self) -> Result<(), SimulationError>;
+    /// Every connector target must be a model in the simulation.
+    fn connectors_target_to_model(&self) -> Result<(), SimulationError>;
+    /// Model IDs must be unique within the simulation.
+    fn valid_model_ids(&self) -> Result<(), SimulationError>;
+    /// Model parameters, such as random variable distribution parameters,
+    /// must be within their domains.
+    fn valid_model_parameters(&self) -> Result<(), SimulationError>;
+
+    /// Every model must be reachable, via connectors, from a source model -
+    /// a model without inbound connectors, such as a generator.  This check
+    /// is not included in the default bundle, since not every valid
+    /// topology has an explicit source model.
+    fn reachable_from_sources(&self) -> Result<(), SimulationError>;
+
+    /// The connectors must not form a cycle, such that the models can be
+    /// ordered topologically - as required by static analyses like
+    /// `expected_latency_bounds`.  This check is not included in the
+    /// default bundle, since feedback loops are valid in simulation.
+    fn acyclic_connectors(&self) -> Result<(), SimulationError>;
+
+    /// Run the default bundle of checks, returning the first failure.
+    fn check(&self) -> Result<(), SimulationError> {
+        self.connectors_source_to_model()?;
+        self.connectors_target_to_model()?;
+        self.valid_model_ids()?;
+        self.valid_model_parameters()
+    }
+
+    /// Run every rule, collecting all issues - with the offending model or
+    /// connector IDs - into a human-readable report.  Rules outside the
+    /// default bundle are reported as warnings.
+    fn check_report(&self) -> CheckReport;
+}
+
+/// The severity of a `CheckIssue`.  Errors are failures of the default
+/// bundle of checks, while warnings are failures of the optional rules.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CheckSeverity {
+    Error,
+    Warning,
+}
+
+/// A single issue found by the `Checker`, naming the rule that failed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckIssue {
+    pub severity: CheckSeverity,
+    pub rule: String,
+    pub message: String,
+}
+
+/// A `CheckReport` enumerates every issue found by the `Checker`, and
+/// displays as a bullet list.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CheckReport {
+    pub issues: Vec<CheckIssue>,
+}
+
+impl CheckReport {
+    /// A report has errors if any issue has error severity.
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity == CheckSeverity::Error)
+    }
+
+    fn push(&mut self, severity: CheckSeverity, rule: &str, message: String) {
+        self.issues.push(CheckIssue {
+            severity,
+            rule: rule.to_string(),
+            message,
+        });
+    }
+}
+
+impl fmt::Display for CheckSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckSeverity::Error => write!(f, "error"),
+            CheckSeverity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+impl fmt::Display for CheckIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] {}: {}", self.severity, self.rule, self.message)
+    }
+}
+
+impl fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.issues.is_empty() {
+            return writeln!(f, "No issues found");
+        }
+        self.issues
+            .iter()
+            .try_for_each(|issue| writeln!(f, "- {}", issue))
+    }
+}
+
+impl Checker for Simulation {
+    fn connectors_source_to_model(&self) -> Result<(), SimulationError> {
+        self.connectors.iter().try_for_each(|connector| {
+            match self
+                .models
+                .iter()
+                .find(|model| model.id() == connector.source_id())
+            {
+                Some(_) => Ok(()),
+                None => Err(SimulationError::ModelNotFound),
+            }
+        })
+    }
+
+    fn connectors_target_to_model(&self) -> Result<(), SimulationError> {
+        self.connectors.iter().try_for_each(|connector| {
+            match self
+                .models
+                .iter()
+                .find(|model| model.id() == connector.target_id())
+            {
+                Some(_) => Ok(()),
+                None => Err(SimulationError::ModelNotFound),
+            }
+        })
+    }
+
+    fn valid_model_ids(&self) -> Result<(), SimulationError> {
+        let mut model_ids = HashSet::new();
+        self.models.iter().try_for_each(|model| {
+            if model_ids.insert(model.id()) {
+                Ok(())
+            } else {
+                Err(SimulationError::DuplicateModelId)
+            }
+        })
+    }
+
+    fn valid_model_parameters(&self) -> Result<(), SimulationError> {
+        self.models
+            .iter()
+            .try_for_each(|model| model.validate_parameters())
+    }
+
+    fn reachable_from_sources(&self) -> Result<(), SimulationError> {
+        let mut frontier: Vec<&str> = self
+            .models
+            .iter()
+            .map(|model| model.id())
+            .filter(|model_id| {
+                !self
+                    .connectors
+                    .iter()
+                    .any(|connector| connector.target_id() == *model_id)
+            })
+            .collect();
+        let mut reached: HashSet<&str> = frontier.iter().copied().collect();
+        while let Some(model_id) = frontier.pop() {
+            self.connectors
+                .iter()
+                .filter(|connector| connector.source_id() == model_id)
+                .for_each(|connector| {
+                    if reached.insert(connector.target_id()) {
+                        frontier.push(connector.target_id());
+                    }
+                });
+        }
+        match self
+            .models
+            .iter()
+            .find(|model| !reached.contains(model.id()))
+        {
+            Some(model) => Err(SimulationError::UnreachableModel(model.id().to_string())),
+            None => Ok(()),
+        }
+    }
+
+    fn acyclic_connectors(&self) -> Result<(), SimulationError> {
+        self.topological_order().map(|_| ())
+    }
+
+    fn check_report(&self) -> CheckReport {
+        let mut report = CheckReport::default();
+        let model_exists = |model_id: &str| self.models.iter().any(|model| model.id() == model_id);
+        self.connectors.iter().for_each(|connector| {
+            if !model_exists(connector.source_id()) {
+                report.push(
+                    CheckSeverity::Error,
+                    "connectors_source_to_model",
+                    format!(
+                        "connector {} has source {}, which is not a model",
+                        connector.id(),
+                        connector.source_id()
+                    ),
+                );
+            }
+            if !model_exists(connector.target_id()) {
+                report.push(
+                    CheckSeverity::Error,
+                    "connectors_target_to_model",
+                    format!(
+                        "connector {} has target {}, which is not a model",
+                        connector.id(),
+                        connector.target_id()
+                    ),
+                );
+            }
+        });
+        let mut model_ids = HashSet::new();
+        self.models.iter().for_each(|model| {
+            if !model_ids.insert(model.id()) {
+                report.push(
+                    CheckSeverity::Error,
+                    "valid_model_ids",
+                    format!("model ID {} is not unique", model.id()),
+                );
+            }
+        });
+        self.models.iter().for_each(|model| {
+            if let Err(error) = model.validate_parameters() {
+                report.push(
+                    CheckSeverity::Error,
+                    "valid_model_parameters",
+                    format!("model {} has invalid parameters - {}", model.id(), error),
+                );
+            }
+        });
+        if let Err(SimulationError::UnreachableModel(model_id)) = self.reachable_from_sources() {
+            report.push(
+                CheckSeverity::Warning,
+                "reachable_from_sources",
+                format!("model {} is not reachable from a source model", model_id),
+            );
+        }
+        if let Err(SimulationError::CyclicConnectors(model_id)) = self.acyclic_connectors() {
+            report.push(
+                CheckSeverity::Warning,
+                "acyclic_connectors",
+                format!("model {} is part of a connector cycle", model_id),
+            );
+        }
+        report
+    }
+}
@@ -1,10 +1,15 @@
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
+use crate::utils::errors::SimulationError;
+
 /// Connectors are configured to connect models through their ports.  During
 /// simulation, models exchange messages (as per the Discrete Event System
 /// Specification) via these connectors.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Connector {
     id: String,
@@ -14,6 +19,38 @@ pub struct Connector {
     target_id: String,
     source_port: String,
     target_port: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    transform: Option<ContentTransform>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<i32>,
+}
+
+/// A content transform rewrites the content of messages in transit along a
+/// connector, or within a `Transform` model, for targets expecting a
+/// different content encoding than the source emits.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ContentTransform {
+    /// Convert the content to uppercase
+    Uppercase,
+    /// Prepend a prefix to the content
+    Prefix { prefix: String },
+    /// Append a suffix to the content
+    Suffix { suffix: String },
+    /// Replace every occurrence of `from` in the content with `to`
+    Replace { from: String, to: String },
+}
+
+impl ContentTransform {
+    /// Apply the transform to message content.
+    pub fn apply(&self, content: &str) -> String {
+        match self {
+            ContentTransform::Uppercase => content.to_uppercase(),
+            ContentTransform::Prefix { prefix } => format!["{}{}", prefix, content],
+            ContentTransform::Suffix { suffix } => format!["{}{}", content, suffix],
+            ContentTransform::Replace { from, to } => content.replace(from.as_str(), to),
+        }
+    }
 }
 
 impl Connector {
@@ -30,9 +67,33 @@ impl Connector {
             target_id,
             source_port,
             target_port,
+            transform: None,
+            priority: None,
         }
     }
 
+    /// Set a transform of the content of messages routed along the
+    /// connector.  By default, message content is unchanged.
+    pub fn with_transform(mut self, transform: ContentTransform) -> Self {
+        self.transform = Some(transform);
+        self
+    }
+
+    /// Set the priority of the connector, for the delivery order of
+    /// messages fanned out from one source port over several connectors.
+    /// Lower priorities are delivered first, and connectors without a
+    /// priority have priority 0.  Equal priorities are ordered by
+    /// connector ID.
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// This accessor method returns the ID of the connector.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
     /// This accessor method returns the model ID of the connector source model.
     pub fn source_id(&self) -> &str {
         &self.source_id
@@ -52,6 +113,97 @@ impl Connector {
     pub fn target_port(&self) -> &str {
         &self.target_port
     }
+
+    /// This accessor method returns the content transform of the connector.
+    pub fn transform(&self) -> Option<&ContentTransform> {
+        self.transform.as_ref()
+    }
+
+    /// This accessor method returns the fan-out delivery priority of the
+    /// connector, where set.
+    pub fn priority(&self) -> Option<i32> {
+        self.priority
+    }
+}
+
+/// This function parses numeric message content, such as a job size or
+/// priority, for models that interpret content as a number.  Surrounding
+/// whitespace is ignored.  Content that is not a number, including "NaN",
+/// is an `InvalidMessage`.
+pub fn parse_numeric_content(content: &str) -> Result<f64, SimulationError> {
+    match content.trim().parse::<f64>() {
+        Ok(value) if !value.is_nan() => Ok(value),
+        _ => Err(SimulationError::InvalidMessage),
+    }
+}
+
+/// This function merges message collections, such as the messages from
+/// several `step_until` calls or replications, into a single collection in
+/// time order.  The sort is stable, so messages with equal times retain
+/// their collection order, and their order within each collection.
+pub fn merge_message_collections(collections: &[&[Message]]) -> Vec<Message> {
+    let mut messages: Vec<Message> = collections
+        .iter()
+        .flat_map(|collection| collection.iter().cloned())
+        .collect();
+    messages.sort_by(|a, b| a.time().total_cmp(b.time()));
+    messages
+}
+
+/// This function imports connectors from CSV, with one connector per row,
+/// in the column order `id,source_id,source_port,target_id,target_port`.
+/// A header row with these column names is optional, and blank lines are
+/// ignored.  Fields may be quoted, as per RFC 4180.
+pub fn connectors_from_csv(csv: &str) -> Result<Vec<Connector>, SimulationError> {
+    const HEADER: [&str; 5] = ["id", "source_id", "source_port", "target_id", "target_port"];
+    csv.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| (index + 1, parse_csv_row(line)))
+        .filter(|(_, fields)| fields.iter().map(String::as_str).ne(HEADER.iter().copied()))
+        .map(|(row, fields)| {
+            if fields.len() != HEADER.len() {
+                return Err(SimulationError::MalformedCsvRow(
+                    row,
+                    format!("expected 5 columns, found {}", fields.len()),
+                ));
+            }
+            if let Some(column) = [0, 1, 3].iter().find(|column| fields[**column].is_empty()) {
+                return Err(SimulationError::MalformedCsvRow(
+                    row,
+                    format!("empty {}", HEADER[*column]),
+                ));
+            }
+            Ok(Connector::new(
+                fields[0].clone(),
+                fields[1].clone(),
+                fields[3].clone(),
+                fields[2].clone(),
+                fields[4].clone(),
+            ))
+        })
+        .collect()
+}
+
+/// Split a CSV row into trimmed fields, unquoting quoted fields.
+fn parse_csv_row(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
 }
 
 /// Messages are the mechanism of information exchange for models in a
@@ -59,7 +211,7 @@ impl Connector {
 /// ID and source model port), destination information (target model ID and
 /// target model port), and the text/content of the message.
 #[wasm_bindgen]
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Message {
     source_id: String,
@@ -68,6 +220,77 @@ pub struct Message {
     target_port: String,
     time: f64,
     content: String,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    attributes: HashMap<String, f64>,
+}
+
+/// The `MessageBuilder` constructs a `Message` with named fields, to avoid
+/// transposing the source and target, or the ports, of the positional
+/// `Message::new` arguments.  Every field is required, and must not be
+/// empty.
+#[derive(Debug, Clone, Default)]
+pub struct MessageBuilder {
+    source: Option<(String, String)>,
+    target: Option<(String, String)>,
+    time: Option<f64>,
+    content: Option<String>,
+}
+
+impl MessageBuilder {
+    /// Set the source model ID and port of the message.
+    pub fn from(mut self, source_id: &str, source_port: &str) -> Self {
+        self.source = Some((source_id.to_string(), source_port.to_string()));
+        self
+    }
+
+    /// Set the target model ID and port of the message.
+    pub fn to(mut self, target_id: &str, target_port: &str) -> Self {
+        self.target = Some((target_id.to_string(), target_port.to_string()));
+        self
+    }
+
+    /// Set the global time of the message.
+    pub fn at(mut self, time: f64) -> Self {
+        self.time = Some(time);
+        self
+    }
+
+    /// Set the content of the message.
+    pub fn content(mut self, content: &str) -> Self {
+        self.content = Some(content.to_string());
+        self
+    }
+
+    /// Build the message, returning an error naming the first field that
+    /// is missing or empty.
+    pub fn build(self) -> Result<Message, SimulationError> {
+        let missing = |field: &str| SimulationError::IncompleteMessage(field.to_string());
+        let (source_id, source_port) = self.source.ok_or_else(|| missing("source"))?;
+        let (target_id, target_port) = self.target.ok_or_else(|| missing("target"))?;
+        let time = self.time.ok_or_else(|| missing("time"))?;
+        let content = self.content.ok_or_else(|| missing("content"))?;
+        let fields = [
+            ("source ID", &source_id),
+            ("source port", &source_port),
+            ("target ID", &target_id),
+            ("target port", &target_port),
+            ("content", &content),
+        ];
+        if let Some((field, _)) = fields.iter().find(|(_, value)| value.is_empty()) {
+            return Err(missing(field));
+        }
+        if time.is_nan() {
+            return Err(missing("time"));
+        }
+        Ok(Message::new(
+            source_id,
+            source_port,
+            target_id,
+            target_port,
+            time,
+            content,
+        ))
+    }
 }
 
 impl Message {
@@ -88,9 +311,52 @@ impl Message {
             target_port,
             time,
             content,
+            attributes: HashMap::new(),
         }
     }
 
+    /// This method starts a `MessageBuilder`, for constructing a message
+    /// with named fields instead of positional arguments.
+    pub fn builder() -> MessageBuilder {
+        MessageBuilder::default()
+    }
+
+    /// This method sets the numeric attributes of a message, such as a job
+    /// size or class, for models that parameterize their behavior by the
+    /// attributes of a job.
+    pub fn with_attributes(mut self, attributes: HashMap<String, f64>) -> Self {
+        self.attributes = attributes;
+        self
+    }
+
+    /// This method restamps a message with a new transmission time, such as
+    /// the delivery time of a scheduled input.
+    pub(crate) fn with_time(mut self, time: f64) -> Self {
+        self.time = time;
+        self
+    }
+
+    /// This constructor method builds a `Message` with a structured payload,
+    /// by storing the JSON serialization of the payload as the message
+    /// content.  The payload can be retrieved with `content_as`.
+    pub fn new_json<T: Serialize>(
+        source_id: String,
+        source_port: String,
+        target_id: String,
+        target_port: String,
+        time: f64,
+        content: &T,
+    ) -> Result<Self, SimulationError> {
+        Ok(Self::new(
+            source_id,
+            source_port,
+            target_id,
+            target_port,
+            time,
+            serde_json::to_string(content)?,
+        ))
+    }
+
     /// This accessor method returns the model ID of a message source.
     pub fn source_id(&self) -> &str {
         &self.source_id
@@ -120,4 +386,15 @@ impl Message {
     pub fn content(&self) -> &str {
         &self.content
     }
+
+    /// This accessor method returns the numeric attributes of a message.
+    pub fn attributes(&self) -> &HashMap<String, f64> {
+        &self.attributes
+    }
+
+    /// This accessor method deserializes the JSON content of a message, as
+    /// built with `new_json`, into a structured payload.
+    pub fn content_as<T: DeserializeOwned>(&self) -> Result<T, SimulationError> {
+        Ok(serde_json::from_str(&self.content)?)
+    }
 }
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use rand_pcg::Pcg64Mcg;
+use serde::{Deserialize, Serialize};
+
+use super::{Connector, Simulation};
+use crate::input_modeling::dynamic_rng::SimulationRng;
+use crate::utils::errors::SimulationError;
+
+/// A `SimulationDiff` reports the structural differences between two
+/// simulations - models and connectors present in only one of the
+/// simulations, and the difference in global time.  Additions and removals
+/// are from the perspective of the original simulation, compared against
+/// the other simulation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationDiff {
+    pub added_models: Vec<String>,
+    pub removed_models: Vec<String>,
+    pub added_connectors: Vec<Connector>,
+    pub removed_connectors: Vec<Connector>,
+    pub global_time_delta: f64,
+}
+
+impl SimulationDiff {
+    /// A diff is empty when the simulations have the same models,
+    /// connectors, and global time.
+    pub fn is_empty(&self) -> bool {
+        self.added_models.is_empty()
+            && self.removed_models.is_empty()
+            && self.added_connectors.is_empty()
+            && self.removed_connectors.is_empty()
+            && self.global_time_delta == 0.0
+    }
+}
+
+/// A `VariantComparison` reports the outputs of a base and a variant
+/// simulation, run under common random numbers - the throughput of each
+/// model, and the total count of messages, as (base, variant) pairs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariantComparison {
+    pub throughput: HashMap<String, (f64, f64)>,
+    pub message_counts: (usize, usize),
+}
+
+impl VariantComparison {
+    /// The difference in throughput of a model, from the base to the
+    /// variant, or `None` if the model is in neither simulation.
+    pub fn throughput_delta(&self, model_id: &str) -> Option<f64> {
+        self.throughput
+            .get(model_id)
+            .map(|(base, variant)| variant - base)
+    }
+
+    /// The difference in the count of messages, from the base to the
+    /// variant.
+    pub fn message_count_delta(&self) -> i64 {
+        self.message_counts.1 as i64 - self.message_counts.0 as i64
+    }
+}
+
+/// This function compares two model variants under common random numbers.
+/// Both simulations are given an identical copy of the default random
+/// number generator, seeded with the seed of the base simulation, and run
+/// until a global time.  Model-specific random number generators are not
+/// replaced.
+pub fn compare_variants(
+    base: Simulation,
+    variant: Simulation,
+    until: f64,
+) -> Result<VariantComparison, SimulationError> {
+    let rng = Pcg64Mcg::new(u128::from(base.get_seed()));
+    compare_variants_with_rng(base, variant, until, rng)
+}
+
+/// This function compares two model variants under common random numbers,
+/// giving each simulation an identical copy of the provided random number
+/// generator.
+pub fn compare_variants_with_rng<Rng: SimulationRng + Clone + 'static>(
+    mut base: Simulation,
+    mut variant: Simulation,
+    until: f64,
+    rng: Rng,
+) -> Result<VariantComparison, SimulationError> {
+    base.set_rng(rng.clone());
+    variant.set_rng(rng);
+    let run = |simulation: &mut Simulation| -> Result<_, SimulationError> {
+        let start_time = simulation.get_global_time();
+        let messages = simulation.step_until(until)?;
+        let duration = until - start_time;
+        let throughput: HashMap<String, f64> = simulation
+            .models
+            .iter()
+            .map(|model| {
+                let departures = messages
+                    .iter()
+                    .filter(|message| message.source_id() == model.id())
+                    .count();
+                (model.id().to_string(), departures as f64 / duration)
+            })
+            .collect();
+        Ok((throughput, messages.len()))
+    };
+    let (base_throughput, base_count) = run(&mut base)?;
+    let (variant_throughput, variant_count) = run(&mut variant)?;
+    let mut model_ids: Vec<&String> = base_throughput
+        .keys()
+        .chain(variant_throughput.keys())
+        .collect();
+    model_ids.sort();
+    model_ids.dedup();
+    Ok(VariantComparison {
+        throughput: model_ids
+            .into_iter()
+            .map(|model_id| {
+                (
+                    model_id.clone(),
+                    (
+                        base_throughput.get(model_id).copied().unwrap_or(0.0),
+                        variant_throughput.get(model_id).copied().unwrap_or(0.0),
+                    ),
+                )
+            })
+            .collect(),
+        message_counts: (base_count, variant_count),
+    })
+}
+
+impl Simulation {
+    /// This method compares two simulations structurally - models (including
+    /// model state), connectors, active messages, and global time.  The
+    /// random number generators are not compared.
+    pub fn structural_eq(&self, other: &Simulation) -> bool {
+        match (serde_json::to_value(self), serde_json::to_value(other)) {
+            (Ok(simulation), Ok(other_simulation)) => simulation == other_simulation,
+            _ => false,
+        }
+    }
+
+    /// This method reports the models and connectors added or removed in
+    /// another simulation, along with the difference in global time.
+    pub fn diff(&self, other: &Simulation) -> SimulationDiff {
+        let model_ids = |simulation: &Simulation| -> Vec<String> {
+            simulation
+                .models
+                .iter()
+                .map(|model| model.id().to_string())
+                .collect()
+        };
+        let (ids, other_ids) = (model_ids(self), model_ids(other));
+        SimulationDiff {
+            added_models: other_ids
+                .iter()
+                .filter(|id| !ids.contains(id))
+                .cloned()
+                .collect(),
+            removed_models: ids
+                .iter()
+                .filter(|id| !other_ids.contains(id))
+                .cloned()
+                .collect(),
+            added_connectors: other
+                .connectors
+                .iter()
+                .filter(|connector| !self.connectors.contains(connector))
+                .cloned()
+                .collect(),
+            removed_connectors: self
+                .connectors
+                .iter()
+                .filter(|connector| !other.connectors.contains(connector))
+                .cloned()
+                .collect(),
+            global_time_delta: other.get_global_time() - self.get_global_time(),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+use super::Message;
+
+/// `StepTiming` reports the wall-clock time spent in each phase of a
+/// simulation step, in nanoseconds, for performance tuning.  Timing is not
+/// supported for WASM targets, where all phases are reported as zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StepTiming {
+    /// Time spent delivering messages to models, as external events
+    pub external_events_ns: u128,
+    /// Time spent computing the next event and advancing the model clocks
+    pub next_event_ns: u128,
+    /// Time spent executing internal events and routing the output messages
+    pub internal_events_ns: u128,
+}
+
+impl StepTiming {
+    /// The total time spent across all the step phases.
+    pub fn total_ns(&self) -> u128 {
+        self.external_events_ns + self.next_event_ns + self.internal_events_ns
+    }
+}
+
+/// A `Lap` measures the wall-clock time between successive calls to `lap`.
+pub(crate) struct Lap {
+    #[cfg(not(target_arch = "wasm32"))]
+    start: Instant,
+}
+
+impl Lap {
+    pub(crate) fn start() -> Self {
+        Self {
+            #[cfg(not(target_arch = "wasm32"))]
+            start: Instant::now(),
+        }
+    }
+
+    /// The nanoseconds elapsed since the last lap, or since the start.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn lap(&mut self) -> u128 {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.start).as_nanos();
+        self.start = now;
+        elapsed
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn lap(&mut self) -> u128 {
+        0
+    }
+}
+
+/// `ProgressInfo` reports the progress of a long simulation run, for
+/// periodic progress updates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressInfo {
+    /// The number of steps executed since the progress callback was set
+    pub steps: u64,
+    /// The simulation global time
+    pub global_time: f64,
+    /// The number of active messages, including messages held by
+    /// backpressure
+    pub active_messages: usize,
+}
+
+/// A `ProgressReporter` counts simulation steps, and invokes a progress
+/// callback every `every_steps` steps.
+#[derive(Clone)]
+pub(crate) struct ProgressReporter {
+    every_steps: usize,
+    steps: u64,
+    callback: Rc<RefCell<dyn FnMut(ProgressInfo)>>,
+}
+
+impl ProgressReporter {
+    pub(crate) fn new(every_steps: usize, callback: impl FnMut(ProgressInfo) + 'static) -> Self {
+        Self {
+            every_steps,
+            steps: 0,
+            callback: Rc::new(RefCell::new(callback)),
+        }
+    }
+
+    /// Count a step, invoking the callback if the step is a reporting step.
+    pub(crate) fn step(&mut self, global_time: f64, active_messages: usize) {
+        self.steps += 1;
+        if self.every_steps != 0 && self.steps.is_multiple_of(self.every_steps as u64) {
+            (self.callback.borrow_mut())(ProgressInfo {
+                steps: self.steps,
+                global_time,
+                active_messages,
+            });
+        }
+    }
+}
+
+type OverflowCallback = Rc<RefCell<dyn FnMut(&str, &Message)>>;
+
+/// An `OverflowNotifier` invokes an overflow callback with the model ID and
+/// the message, whenever a model at capacity discards a delivered message.
+#[derive(Clone)]
+pub(crate) struct OverflowNotifier {
+    callback: OverflowCallback,
+}
+
+impl OverflowNotifier {
+    pub(crate) fn new(callback: impl FnMut(&str, &Message) + 'static) -> Self {
+        Self {
+            callback: Rc::new(RefCell::new(callback)),
+        }
+    }
+
+    /// Notify the callback of a message discarded by the specified model.
+    pub(crate) fn notify(&self, model_id: &str, message: &Message) {
+        (self.callback.borrow_mut())(model_id, message)
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+
+use super::{Message, Simulation};
+use crate::models::Reportable;
+use crate::utils::errors::SimulationError;
+
+/// A `Metric` summarizes a simulation run as a single named value, such as
+/// throughput or mean sojourn time.  Metrics are evaluated from the
+/// messages of the run and, optionally, from a sample of the simulation
+/// taken after each step - for metrics of model state, like queue lengths.
+pub trait Metric {
+    /// The name of the metric, as the key of the collected results.
+    fn name(&self) -> String;
+
+    /// Sample the simulation after a step.  Metrics of model state return
+    /// the sampled value, and other metrics do not sample.
+    fn sample(&self, _simulation: &Simulation) -> Result<Option<f64>, SimulationError> {
+        Ok(None)
+    }
+
+    /// Evaluate the metric for the run, from the run messages, the
+    /// (global time, value) samples, and the run duration.
+    fn evaluate(&self, messages: &[Message], samples: &[(f64, f64)], duration: f64) -> f64;
+}
+
+/// A `MetricSet` collects the observations required by a set of metrics
+/// over a simulation run - the messages and per-step samples - and then
+/// evaluates each metric by name.
+pub struct MetricSet<'a> {
+    metrics: &'a [Box<dyn Metric>],
+    start_time: f64,
+    end_time: f64,
+    messages: Vec<Message>,
+    samples: Vec<Vec<(f64, f64)>>,
+}
+
+impl<'a> MetricSet<'a> {
+    /// Begin collection for the metrics, from the current simulation time.
+    pub fn new(metrics: &'a [Box<dyn Metric>], simulation: &Simulation) -> Self {
+        Self {
+            metrics,
+            start_time: simulation.get_global_time(),
+            end_time: simulation.get_global_time(),
+            messages: Vec::new(),
+            samples: vec![Vec::new(); metrics.len()],
+        }
+    }
+
+    /// Observe a simulation step, with the messages produced by the step.
+    pub fn observe(
+        &mut self,
+        simulation: &Simulation,
+        messages: &[Message],
+    ) -> Result<(), SimulationError> {
+        self.end_time = simulation.get_global_time();
+        self.messages.extend_from_slice(messages);
+        self.metrics
+            .iter()
+            .zip(self.samples.iter_mut())
+            .try_for_each(|(metric, samples)| {
+                if let Some(value) = metric.sample(simulation)? {
+                    samples.push((simulation.get_global_time(), value));
+                }
+                Ok(())
+            })
+    }
+
+    /// Evaluate every metric over the observed steps, by metric name.
+    pub fn collect(&self) -> HashMap<String, f64> {
+        let duration = self.end_time - self.start_time;
+        self.metrics
+            .iter()
+            .zip(self.samples.iter())
+            .map(|(metric, samples)| {
+                (
+                    metric.name(),
+                    metric.evaluate(&self.messages, samples, duration),
+                )
+            })
+            .collect()
+    }
+}
+
+/// The throughput of a model - the messages it emits per unit of time.
+pub struct ThroughputMetric {
+    pub model_id: String,
+}
+
+impl Metric for ThroughputMetric {
+    fn name(&self) -> String {
+        format!["throughput of {}", self.model_id]
+    }
+
+    fn evaluate(&self, messages: &[Message], _samples: &[(f64, f64)], duration: f64) -> f64 {
+        let departures = messages
+            .iter()
+            .filter(|message| message.source_id() == self.model_id)
+            .count();
+        departures as f64 / duration
+    }
+}
+
+/// The mean sojourn time of jobs in a model - from the arrival of a job to
+/// the departure of a job with the same content.  Jobs still in the model
+/// at the end of the run are excluded, and the metric is NaN when no jobs
+/// depart.
+pub struct MeanSojournMetric {
+    pub model_id: String,
+}
+
+impl Metric for MeanSojournMetric {
+    fn name(&self) -> String {
+        format!["mean sojourn time in {}", self.model_id]
+    }
+
+    fn evaluate(&self, messages: &[Message], _samples: &[(f64, f64)], _duration: f64) -> f64 {
+        let mut arrivals: HashMap<&str, f64> = HashMap::new();
+        let mut sojourn_times: Vec<f64> = Vec::new();
+        messages.iter().for_each(|message| {
+            if message.target_id() == self.model_id {
+                arrivals.entry(message.content()).or_insert(*message.time());
+            } else if message.source_id() == self.model_id {
+                if let Some(arrival) = arrivals.remove(message.content()) {
+                    sojourn_times.push(message.time() - arrival);
+                }
+            }
+        });
+        sojourn_times.iter().sum::<f64>() / sojourn_times.len() as f64
+    }
+}
+
+/// The maximum queue length of a model, as reported by the model
+/// `queue_length` metric after each step.
+pub struct MaxQueueMetric {
+    pub model_id: String,
+}
+
+impl Metric for MaxQueueMetric {
+    fn name(&self) -> String {
+        format!["max queue length of {}", self.model_id]
+    }
+
+    fn sample(&self, simulation: &Simulation) -> Result<Option<f64>, SimulationError> {
+        simulation.get_metric(&self.model_id, "queue_length")
+    }
+
+    fn evaluate(&self, _messages: &[Message], samples: &[(f64, f64)], _duration: f64) -> f64 {
+        samples
+            .iter()
+            .fold(0.0, |max, (_, queue_length)| f64::max(max, *queue_length))
+    }
+}
+
+impl Simulation {
+    /// This method identifies the bottleneck of the simulation - the model
+    /// with the highest utilization through the current global time, along
+    /// with the utilization.  Models without a meaningful utilization are
+    /// skipped, and the result is `None` if no model reports a utilization.
+    pub fn bottleneck(&self) -> Result<Option<(String, f64)>, SimulationError> {
+        let end_time = self.get_global_time();
+        Ok(self
+            .models
+            .iter()
+            .filter_map(|model| Some((model.id().to_string(), model.utilization(end_time)?)))
+            .fold(
+                None,
+                |bottleneck: Option<(String, f64)>, (model_id, utilization)| match bottleneck {
+                    Some((_, max_utilization)) if max_utilization >= utilization => bottleneck,
+                    _ => Some((model_id, utilization)),
+                },
+            ))
+    }
+}
@@ -14,33 +14,242 @@
 //! return the messages generated during the execution of the simulation
 //! step(s), for use in message analysis.
 
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use rand_pcg::Pcg64Mcg;
 use serde::{Deserialize, Serialize};
 
 use crate::input_modeling::dyn_rng;
-use crate::input_modeling::dynamic_rng::SimulationRng;
-use crate::models::{DevsModel, Model, ModelMessage, ModelRecord, Reportable};
+use crate::input_modeling::dynamic_rng::{DynRng, SimulationRng, SubstreamScheme, DEFAULT_SEED};
+use crate::models::model_trait::SerializableModel;
+use crate::models::{DevsModel, Model, ModelMessage, ModelRecord, Reportable, ReportableModel};
 use crate::utils::errors::SimulationError;
 use crate::utils::set_panic_hook;
 
+use self::instrumentation::{Lap, OverflowNotifier, ProgressReporter};
+
+pub mod builder;
+pub mod checker;
 pub mod coupling;
+pub mod diff;
+pub mod instrumentation;
+pub mod metrics;
+pub mod replication;
+pub mod report;
 pub mod services;
+pub mod sink;
+pub mod time;
 pub mod web;
 
-pub use self::coupling::{Connector, Message};
+pub use self::builder::SimulationBuilder;
+pub use self::checker::{CheckIssue, CheckReport, CheckSeverity, Checker};
+pub use self::coupling::{
+    connectors_from_csv, merge_message_collections, parse_numeric_content, Connector,
+    ContentTransform, Message, MessageBuilder,
+};
+pub use self::diff::{
+    compare_variants, compare_variants_with_rng, SimulationDiff, VariantComparison,
+};
+pub use self::instrumentation::{ProgressInfo, StepTiming};
+pub use self::metrics::{MaxQueueMetric, MeanSojournMetric, Metric, MetricSet, ThroughputMetric};
+pub use self::replication::SequentialResult;
+pub use self::report::{
+    content_histogram, export_xes, flow_summary, run_fingerprint, Report, TopologyEdge,
+    TopologyGraph, TopologyNode,
+};
 pub use self::services::Services;
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::sink::CsvFileSink;
+pub use self::sink::MessageSink;
+pub use self::time::{Clock, FixedTime, SimTime, TimeRepresentation};
 pub use self::web::Simulation as WebSimulation;
 
+/// The default time within which a model internal event is treated as
+/// imminent, to absorb floating-point rounding drift in event times.
+const DEFAULT_EVENT_TOLERANCE: f64 = 1e-9;
+
 /// The `Simulation` struct is the core of sim, and includes everything
 /// needed to run a simulation - models, connectors, and a random number
 /// generator.  State information, specifically global time and active
 /// messages are additionally retained in the struct.
-#[derive(Clone, Default, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Simulation {
     models: Vec<Model>,
     connectors: Vec<Connector>,
     messages: Vec<Message>,
     services: Services,
+    #[serde(skip)]
+    model_rngs: HashMap<String, DynRng>,
+    #[serde(default)]
+    backpressure: bool,
+    #[serde(default)]
+    dedup_messages: bool,
+    #[serde(default)]
+    fifo_delivery: bool,
+    #[serde(default)]
+    strict_ports: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_active_messages: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event_tolerance: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event_budget: Option<u64>,
+    #[serde(default)]
+    disabled_models: Vec<String>,
+    #[serde(default)]
+    drop_disabled_messages: bool,
+    #[serde(default)]
+    record_active_messages: bool,
+    #[serde(default)]
+    error_on_deadlock: bool,
+    #[serde(default = "default_rollback_on_error")]
+    rollback_on_error: bool,
+    #[serde(default)]
+    paused: bool,
+    #[serde(default)]
+    substream_scheme: SubstreamScheme,
+    #[serde(skip)]
+    seed: Option<u64>,
+    #[serde(skip)]
+    active_message_series: Vec<(f64, usize)>,
+    #[serde(skip)]
+    progress_reporter: Option<ProgressReporter>,
+    #[serde(skip)]
+    overflow_notifier: Option<OverflowNotifier>,
+    #[serde(default)]
+    blocked_messages: Vec<Message>,
+    #[serde(default)]
+    scheduled_messages: Vec<(f64, Message)>,
+    #[serde(skip)]
+    total_messages_routed: u64,
+    #[serde(skip)]
+    total_events: u64,
+    #[serde(skip)]
+    connector_traffic: HashMap<String, u64>,
+    #[serde(skip)]
+    snapshot_depth: usize,
+    #[serde(skip)]
+    snapshots: VecDeque<Snapshot>,
+}
+
+fn default_rollback_on_error() -> bool {
+    true
+}
+
+impl Default for Simulation {
+    fn default() -> Self {
+        Self {
+            models: Default::default(),
+            connectors: Default::default(),
+            messages: Default::default(),
+            services: Default::default(),
+            model_rngs: Default::default(),
+            backpressure: Default::default(),
+            dedup_messages: Default::default(),
+            fifo_delivery: Default::default(),
+            strict_ports: Default::default(),
+            max_active_messages: Default::default(),
+            event_tolerance: Default::default(),
+            event_budget: Default::default(),
+            disabled_models: Default::default(),
+            drop_disabled_messages: Default::default(),
+            record_active_messages: Default::default(),
+            error_on_deadlock: Default::default(),
+            rollback_on_error: default_rollback_on_error(),
+            paused: Default::default(),
+            substream_scheme: Default::default(),
+            seed: Default::default(),
+            active_message_series: Default::default(),
+            progress_reporter: Default::default(),
+            overflow_notifier: Default::default(),
+            blocked_messages: Default::default(),
+            scheduled_messages: Default::default(),
+            total_messages_routed: Default::default(),
+            total_events: Default::default(),
+            connector_traffic: Default::default(),
+            snapshot_depth: Default::default(),
+            snapshots: Default::default(),
+        }
+    }
+}
+
+/// A `Snapshot` captures the simulation state prior to a step - the models,
+/// the active messages, and the global time - so that the step can later be
+/// undone with `step_back`.
+#[derive(Clone)]
+struct Snapshot {
+    models: Vec<Model>,
+    record_counts: Vec<Option<usize>>,
+    rng_states: Vec<(DynRng, Rc<dyn Any>)>,
+    messages: Vec<Message>,
+    blocked_messages: Vec<Message>,
+    scheduled_messages: Vec<(f64, Message)>,
+    global_time: Clock,
+    total_messages_routed: u64,
+    total_events: u64,
+    connector_traffic: HashMap<String, u64>,
+    active_message_series_len: usize,
+}
+
+/// A `Topology` is the reusable design of a simulation - the models and
+/// connectors, without run state.
+#[derive(Deserialize)]
+struct Topology {
+    models: Vec<Model>,
+    connectors: Vec<Connector>,
+}
+
+/// Remove the state of a serialized model, and of any component models, so
+/// that the model deserializes to its initial state.
+fn strip_model_state(model: &mut serde_yaml::Value) {
+    if let serde_yaml::Value::Mapping(model) = model {
+        model.remove(&serde_yaml::Value::from("state"));
+        if let Some(serde_yaml::Value::Sequence(components)) =
+            model.get_mut(&serde_yaml::Value::from("components"))
+        {
+            components.iter_mut().for_each(strip_model_state);
+        }
+    }
+}
+
+/// A `DeadlockReport` describes a stuck simulation - messages held awaiting
+/// delivery to models that cannot accept them, with no pending model events
+/// or scheduled inputs to make progress.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeadlockReport {
+    /// The messages held awaiting delivery
+    pub blocked_messages: Vec<Message>,
+    /// The IDs of the models involved, as sources or targets of the held
+    /// messages, in simulation model order
+    pub model_ids: Vec<String>,
+}
+
+/// A `BreakResult` describes a run of `step_until_break` - whether the
+/// breakpoint was hit, and the messages from the steps up to that point.
+#[derive(Debug, Clone)]
+pub struct BreakResult {
+    /// Whether the breakpoint predicate held, before the maximum time
+    pub broke: bool,
+    /// The messages from the steps up to the breakpoint, or up to the
+    /// maximum time
+    pub messages: Vec<Message>,
+}
+
+/// A `PartialSteps` describes a run of `step_n_partial` - the steps that
+/// completed, their messages, and the error of the failed step, if any.
+#[derive(Debug)]
+pub struct PartialSteps {
+    /// The number of steps completed successfully
+    pub steps: usize,
+    /// The messages from the completed steps
+    pub messages: Vec<Message>,
+    /// The error of the step that failed, if stepping stopped early
+    pub error: Option<SimulationError>,
 }
 
 impl Simulation {
@@ -68,14 +277,130 @@ impl Simulation {
             connectors,
             services: Services {
                 global_rng: dyn_rng(global_rng),
-                global_time: 0.0,
+                ..Services::default()
             },
             ..Self::default()
         }
     }
 
+    /// This constructor method creates a simulation from a JSON
+    /// representation of a full `Simulation` - models, connectors, and
+    /// active messages.
+    pub fn from_json(json: &str) -> Result<Self, SimulationError> {
+        set_panic_hook();
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Get a JSON representation of the full `Simulation` - models,
+    /// connectors, and active messages.
+    pub fn to_json(&self) -> Result<String, SimulationError> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Get a YAML representation of the simulation topology - the models,
+    /// in their configured form without state, and the connectors.  Active
+    /// messages, global time, and random number generators are omitted, so
+    /// that the topology can be reused as a template.
+    pub fn export_topology(&self) -> Result<String, SimulationError> {
+        let mut models = serde_yaml::to_value(&self.models)?;
+        if let serde_yaml::Value::Sequence(models) = &mut models {
+            models.iter_mut().for_each(strip_model_state);
+        }
+        let mut topology = serde_yaml::Mapping::new();
+        topology.insert(serde_yaml::Value::from("models"), models);
+        topology.insert(
+            serde_yaml::Value::from("connectors"),
+            serde_yaml::to_value(&self.connectors)?,
+        );
+        Ok(serde_yaml::to_string(&topology)?)
+    }
+
+    /// This constructor method creates a fresh simulation from a YAML
+    /// topology, as produced by `export_topology`.
+    pub fn import_topology(yaml: &str) -> Result<Self, SimulationError> {
+        let topology: Topology = serde_yaml::from_str(yaml)?;
+        Ok(Self::post(topology.models, topology.connectors))
+    }
+
+    /// This constructor method creates a simulation from a supplied
+    /// configuration, as with `post`, and then runs the default bundle of
+    /// configuration checks, failing fast with a report of every error
+    /// found - rather than on the first step of a misconfigured simulation.
+    pub fn post_checked(
+        models: Vec<Model>,
+        connectors: Vec<Connector>,
+    ) -> Result<Self, SimulationError> {
+        let simulation = Self::post(models, connectors);
+        let report = simulation.check_report();
+        if report.has_errors() {
+            let errors = CheckReport {
+                issues: report
+                    .issues
+                    .into_iter()
+                    .filter(|issue| issue.severity == CheckSeverity::Error)
+                    .collect(),
+            };
+            return Err(SimulationError::ConfigurationCheckFailed(
+                errors.to_string(),
+            ));
+        }
+        Ok(simulation)
+    }
+
+    /// This method provides a `SimulationBuilder`, for incremental
+    /// construction of a simulation.
+    pub fn builder() -> SimulationBuilder {
+        SimulationBuilder::new()
+    }
+
+    /// This method replaces the global random number generator.  Any seed
+    /// set with `set_seed` no longer describes the generator, and is
+    /// cleared.
     pub fn set_rng(&mut self, rng: impl SimulationRng + 'static) {
-        self.services.global_rng = dyn_rng(rng)
+        self.services.global_rng = dyn_rng(rng);
+        self.seed = None;
+    }
+
+    /// This method seeds the global random number generator, replacing it
+    /// with the default generator constructed from the seed.  Variant
+    /// comparisons run under common random numbers from the seed, and
+    /// replications derive their substreams from the seed.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.services.global_rng = dyn_rng(Pcg64Mcg::new(u128::from(seed)));
+        self.seed = Some(seed);
+    }
+
+    /// This accessor method returns the seed of the global random number
+    /// generator, or the default seed if none has been set.
+    pub fn get_seed(&self) -> u64 {
+        self.seed.unwrap_or(DEFAULT_SEED)
+    }
+
+    /// This method sets a model-specific random number generator, which the
+    /// model uses in preference to the global random number generator.
+    /// This enables partially deterministic simulations - for example, a
+    /// single model with a fixed random number stream, independent of the
+    /// rest of the simulation.
+    pub fn set_model_rng(
+        &mut self,
+        model_id: &str,
+        rng: impl SimulationRng + 'static,
+    ) -> Result<(), SimulationError> {
+        self.models
+            .iter()
+            .find(|model| model.id() == model_id)
+            .ok_or(SimulationError::ModelNotFound)?;
+        self.model_rngs.insert(model_id.to_string(), dyn_rng(rng));
+        Ok(())
+    }
+
+    /// This method reseeds the random number stream of a single model,
+    /// replacing any model-specific stream with a fresh default generator
+    /// from the given seed.  The streams of the other models are
+    /// undisturbed, which isolates the randomness of one model for
+    /// controlled studies.
+    pub fn reseed_model(&mut self, model_id: &str, seed: u64) -> Result<(), SimulationError> {
+        self.set_model_rng(model_id, Pcg64Mcg::new(u128::from(seed)))
     }
 
     /// This method sets the models and connectors of an existing simulation.
@@ -84,6 +409,147 @@ impl Simulation {
         self.connectors = connectors;
     }
 
+    /// This method swaps the implementation of a model in place, keeping
+    /// the model ID, priority, and connectors - so a model can be retuned
+    /// mid-run without rebuilding the simulation.  Messages in flight to the
+    /// model are delivered to the new implementation, but the state of the
+    /// prior implementation, such as queued jobs, is discarded.
+    pub fn replace_model(
+        &mut self,
+        model_id: &str,
+        new_inner: Box<dyn ReportableModel>,
+    ) -> Result<(), SimulationError> {
+        self.models
+            .iter_mut()
+            .find(|model| model.id() == model_id)
+            .ok_or(SimulationError::ModelNotFound)?
+            .set_inner(new_inner);
+        Ok(())
+    }
+
+    /// This method seeds the internal state of a model, such as a queue of
+    /// jobs already awaiting processing, to start a simulation from a known
+    /// operating point without a warmup period.  The state is a (possibly
+    /// partial) representation of the model `state` field, as serialized.
+    pub fn seed_state(
+        &mut self,
+        model_id: &str,
+        state: serde_yaml::Value,
+    ) -> Result<(), SimulationError> {
+        self.models
+            .iter_mut()
+            .find(|model| model.id() == model_id)
+            .ok_or(SimulationError::ModelNotFound)?
+            .load_state(state)
+            .map_err(|_| SimulationError::InvalidModelState)
+    }
+
+    /// A subgraph is a new simulation containing only the specified models,
+    /// the connectors between them, and the messages targeting them.  This
+    /// supports focused analysis of part of a larger simulation.  Global
+    /// time, the random number generator states, and the simulation
+    /// configuration are carried over, as are the held and scheduled
+    /// messages targeting the selected models.  The generators are copied,
+    /// so running the subgraph does not advance the random number streams
+    /// of the original simulation.  Run statistics, snapshots, and callbacks
+    /// are not carried over.
+    pub fn subgraph(&self, model_ids: &[&str]) -> Result<Simulation, SimulationError> {
+        // Generators shared by several owners are copied once, and remain
+        // shared among the copies
+        let mut copies: Vec<(DynRng, DynRng)> = Vec::new();
+        let mut copy_rng = |rng: &DynRng| -> DynRng {
+            match copies
+                .iter()
+                .find(|(original, _)| Rc::ptr_eq(original, rng))
+            {
+                Some((_, copy)) => copy.clone(),
+                None => {
+                    let copy = rng.borrow().duplicate();
+                    copies.push((rng.clone(), copy.clone()));
+                    copy
+                }
+            }
+        };
+        let mut services = self.services.clone();
+        services.global_rng = copy_rng(&self.services.global_rng);
+        let model_rngs = self
+            .model_rngs
+            .iter()
+            .filter(|(model_id, _)| model_ids.contains(&model_id.as_str()))
+            .map(|(model_id, rng)| (model_id.clone(), copy_rng(rng)))
+            .collect();
+        let mut models = model_ids
+            .iter()
+            .map(|model_id| {
+                self.models
+                    .iter()
+                    .find(|model| model.id() == *model_id)
+                    .cloned()
+                    .ok_or(SimulationError::ModelNotFound)
+            })
+            .collect::<Result<Vec<Model>, SimulationError>>()?;
+        models.iter_mut().for_each(|model| {
+            model
+                .rngs_mut()
+                .into_iter()
+                .for_each(|rng| *rng = copy_rng(rng))
+        });
+        let included = |model_id: &str| model_ids.contains(&model_id);
+        Ok(Self {
+            models,
+            connectors: self
+                .connectors
+                .iter()
+                .filter(|connector| {
+                    included(connector.source_id()) && included(connector.target_id())
+                })
+                .cloned()
+                .collect(),
+            messages: self
+                .messages
+                .iter()
+                .filter(|message| included(message.target_id()))
+                .cloned()
+                .collect(),
+            services,
+            model_rngs,
+            backpressure: self.backpressure,
+            dedup_messages: self.dedup_messages,
+            fifo_delivery: self.fifo_delivery,
+            strict_ports: self.strict_ports,
+            max_active_messages: self.max_active_messages,
+            event_tolerance: self.event_tolerance,
+            event_budget: self.event_budget,
+            disabled_models: self
+                .disabled_models
+                .iter()
+                .filter(|model_id| included(model_id))
+                .cloned()
+                .collect(),
+            drop_disabled_messages: self.drop_disabled_messages,
+            record_active_messages: self.record_active_messages,
+            error_on_deadlock: self.error_on_deadlock,
+            rollback_on_error: self.rollback_on_error,
+            paused: self.paused,
+            substream_scheme: self.substream_scheme,
+            seed: self.seed,
+            blocked_messages: self
+                .blocked_messages
+                .iter()
+                .filter(|message| included(message.target_id()))
+                .cloned()
+                .collect(),
+            scheduled_messages: self
+                .scheduled_messages
+                .iter()
+                .filter(|(_, message)| included(message.target_id()))
+                .cloned()
+                .collect(),
+            snapshot_depth: self.snapshot_depth,
+            ..Self::default()
+        })
+    }
+
     /// Simulation steps generate messages, which are then consumed on
     /// subsequent simulation steps.  These messages between models in a
     /// simulation drive much of the discovery, analysis, and design.  This
@@ -95,11 +561,420 @@ impl Simulation {
         &self.messages
     }
 
+    /// Backpressure enables blocking behavior in the simulation.  When
+    /// enabled, messages are only delivered to models that can accept them
+    /// on the target port, as reported by `DevsModel::can_accept`.  Messages
+    /// that cannot be accepted are held, and delivery is retried on each
+    /// subsequent step.  Backpressure is disabled by default.
+    pub fn set_backpressure(&mut self, enabled: bool) {
+        self.backpressure = enabled;
+    }
+
+    /// Message deduplication coalesces identical messages - the same source,
+    /// target, target port, content, and time - into a single delivery, for
+    /// fan-out topologies where a model may otherwise receive duplicates in
+    /// a single step.  Deduplication is disabled by default.
+    pub fn set_dedup_messages(&mut self, enabled: bool) {
+        self.dedup_messages = enabled;
+    }
+
+    /// FIFO delivery orders the messages delivered to each target model in
+    /// a step by message time, and then by source model ID, so that models
+    /// sensitive to arrival order handle their messages in timestamp order.
+    /// FIFO delivery is disabled by default, in which case messages are
+    /// delivered in active message order.
+    pub fn set_fifo_delivery(&mut self, enabled: bool) {
+        self.fifo_delivery = enabled;
+    }
+
+    /// Strict port checking rejects messages delivered to an input port that
+    /// the target model does not recognize, as reported by
+    /// `DevsModel::input_ports`, with a `SimulationError::UnrecognizedPort`.
+    /// Strict port checking is disabled by default, in which case messages
+    /// are delivered to the model regardless of the port.
+    pub fn set_strict_ports(&mut self, enabled: bool) {
+        self.strict_ports = enabled;
+    }
+
+    /// The active message limit bounds the messages awaiting delivery -
+    /// including messages held by backpressure - so that a model emitting
+    /// faster than its downstream models consume fails the step with a
+    /// `SimulationError::MessageLimitExceeded`, instead of exhausting memory.
+    /// The limit is `None` (unlimited) by default.
+    pub fn set_max_active_messages(&mut self, limit: Option<usize>) {
+        self.max_active_messages = limit;
+    }
+
+    /// The substream scheme governs how the random number generators of
+    /// replications are derived from the simulation seed, as set with
+    /// `set_seed`, or the default seed otherwise.  The default scheme
+    /// seeds each substream with a well-mixed hash of the seed and the
+    /// substream index.
+    pub fn set_substream_scheme(&mut self, scheme: SubstreamScheme) {
+        self.substream_scheme = scheme;
+    }
+
+    /// An accessor method for the substream scheme.
+    pub fn substream_scheme(&self) -> SubstreamScheme {
+        self.substream_scheme
+    }
+
+    /// With rollback enabled, each `step` is transactional - if the step
+    /// fails, such as on an error in a model event, the models, messages,
+    /// global time, counters, and random number generator states are
+    /// restored to their state prior to the step, so that the caller can
+    /// inspect the simulation, correct it, and retry.  Rollback is enabled
+    /// by default, and requires a copy of the simulation state, excluding
+    /// model records, on every step.
+    pub fn set_rollback_on_error(&mut self, enabled: bool) {
+        self.rollback_on_error = enabled;
+    }
+
+    /// Pausing freezes the simulation without tearing down the loop driving
+    /// it, such as for a UI pause button.  While paused, `step` returns
+    /// immediately with no messages, without advancing time or processing
+    /// events, and runs to a global time - such as `step_until` - return
+    /// immediately with no messages.  Unpausing resumes the simulation where
+    /// it left off.  Simulations are not paused by default.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// An accessor method for whether the simulation is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// The event budget caps the total internal events processed across all
+    /// steps since the last reset, independent of simulation time or step
+    /// count.  Once the budget is exhausted, the next `step` fails with a
+    /// `SimulationError::EventBudgetExceeded`, leaving the simulation state
+    /// intact.  There is no budget by default.
+    pub fn set_event_budget(&mut self, budget: u64) {
+        self.event_budget = Some(budget);
+    }
+
+    /// The event tolerance is the time within which a model internal event
+    /// is treated as imminent, so that events scheduled for the current time
+    /// are not deferred to a separate step by floating-point rounding drift.
+    /// The default tolerance is 1e-9, and a tolerance of 0 requires exact
+    /// event times.
+    pub fn set_event_tolerance(&mut self, tolerance: f64) {
+        self.event_tolerance = Some(tolerance);
+    }
+
+    /// An accessor method for the event tolerance.
+    pub fn event_tolerance(&self) -> f64 {
+        self.event_tolerance.unwrap_or(DEFAULT_EVENT_TOLERANCE)
+    }
+
+    /// This method takes a model offline, or brings it back online, without
+    /// removing it from the simulation.  A disabled model retains its state
+    /// and connectors, but its clock is paused - it has no internal events
+    /// and receives no external events.  Messages to a disabled model are
+    /// held until it is re-enabled, or dropped, per
+    /// `set_drop_disabled_messages`.
+    pub fn set_model_enabled(
+        &mut self,
+        model_id: &str,
+        enabled: bool,
+    ) -> Result<(), SimulationError> {
+        if !self.models.iter().any(|model| model.id() == model_id) {
+            return Err(SimulationError::ModelNotFound);
+        }
+        self.disabled_models
+            .retain(|disabled_model| disabled_model != model_id);
+        if !enabled {
+            self.disabled_models.push(model_id.to_string());
+        }
+        Ok(())
+    }
+
+    /// Messages to a disabled model are held, awaiting delivery, by default.
+    /// When enabled, the messages are instead dropped.
+    pub fn set_drop_disabled_messages(&mut self, enabled: bool) {
+        self.drop_disabled_messages = enabled;
+    }
+
+    /// An accessor method for whether a model is enabled.
+    pub fn is_model_enabled(&self, model_id: &str) -> bool {
+        !self
+            .disabled_models
+            .iter()
+            .any(|disabled_model| disabled_model == model_id)
+    }
+
+    /// This accessor method provides the list of messages held due to a full
+    /// target model, when backpressure is enabled, or due to a disabled
+    /// target model, awaiting delivery.
+    pub fn get_blocked_messages(&self) -> &Vec<Message> {
+        &self.blocked_messages
+    }
+
+    /// An accessor method for the connectors of the simulation.
+    pub fn get_connectors(&self) -> &Vec<Connector> {
+        &self.connectors
+    }
+
+    /// This method provides the distinct ports referenced by the connectors,
+    /// by model ID, as (inbound target ports, outbound source ports).  The
+    /// ports are derived from the connectors alone, so models without
+    /// connectors are not included.
+    pub fn referenced_ports(&self) -> HashMap<String, (Vec<String>, Vec<String>)> {
+        let mut referenced_ports: HashMap<String, (Vec<String>, Vec<String>)> = HashMap::new();
+        self.connectors.iter().for_each(|connector| {
+            let (_, source_ports) = referenced_ports
+                .entry(connector.source_id().to_string())
+                .or_default();
+            if !source_ports
+                .iter()
+                .any(|port| port == connector.source_port())
+            {
+                source_ports.push(connector.source_port().to_string());
+            }
+            let (target_ports, _) = referenced_ports
+                .entry(connector.target_id().to_string())
+                .or_default();
+            if !target_ports
+                .iter()
+                .any(|port| port == connector.target_port())
+            {
+                target_ports.push(connector.target_port().to_string());
+            }
+        });
+        referenced_ports
+    }
+
+    /// The models, ordered such that every connector source precedes its
+    /// target.  Connectors with a source or target outside the simulation
+    /// are ignored.
+    pub(crate) fn topological_order(&self) -> Result<Vec<&str>, SimulationError> {
+        let mut in_degrees: HashMap<&str, usize> =
+            self.models.iter().map(|model| (model.id(), 0)).collect();
+        let connectors: Vec<&Connector> = self
+            .connectors
+            .iter()
+            .filter(|connector| {
+                in_degrees.contains_key(connector.source_id())
+                    && in_degrees.contains_key(connector.target_id())
+            })
+            .collect();
+        connectors.iter().for_each(|connector| {
+            *in_degrees.entry(connector.target_id()).or_default() += 1;
+        });
+        let mut frontier: VecDeque<&str> = self
+            .models
+            .iter()
+            .map(|model| model.id())
+            .filter(|model_id| in_degrees[model_id] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.models.len());
+        while let Some(model_id) = frontier.pop_front() {
+            order.push(model_id);
+            connectors
+                .iter()
+                .filter(|connector| connector.source_id() == model_id)
+                .for_each(|connector| {
+                    let in_degree = in_degrees.get_mut(connector.target_id()).unwrap();
+                    *in_degree -= 1;
+                    if *in_degree == 0 {
+                        frontier.push_back(connector.target_id());
+                    }
+                });
+        }
+        match self.models.iter().find(|model| in_degrees[model.id()] > 0) {
+            Some(model) => Err(SimulationError::CyclicConnectors(model.id().to_string())),
+            None => Ok(order),
+        }
+    }
+
+    /// This method returns the IDs of the models that consume random
+    /// numbers, in simulation order.
+    pub fn stochastic_models(&self) -> Vec<String> {
+        self.models
+            .iter()
+            .filter(|model| model.is_stochastic())
+            .map(|model| model.id().to_string())
+            .collect()
+    }
+
+    /// This method estimates the longest expected delay to reach each model,
+    /// by model ID, from the source models of the network.  The delay is
+    /// accumulated along the connectors from the mean delay of each model -
+    /// such as mean interarrival or service times - with models that do not
+    /// report a mean delay contributing none.  The connectors must not form
+    /// a cycle.
+    pub fn expected_latency_bounds(&self) -> Result<HashMap<String, f64>, SimulationError> {
+        let mean_delays: HashMap<&str, f64> = self
+            .models
+            .iter()
+            .map(|model| (model.id(), model.mean_delay().unwrap_or(0.0)))
+            .collect();
+        let mut latencies: HashMap<&str, f64> = HashMap::new();
+        for model_id in self.topological_order()? {
+            let latency = latencies.get(model_id).copied().unwrap_or(0.0);
+            let delay = mean_delays[model_id];
+            self.connectors
+                .iter()
+                .filter(|connector| connector.source_id() == model_id)
+                .for_each(|connector| {
+                    let target_latency = latencies.entry(connector.target_id()).or_insert(0.0);
+                    *target_latency = target_latency.max(latency + delay);
+                });
+            latencies.entry(model_id).or_insert(latency);
+        }
+        Ok(latencies
+            .into_iter()
+            .map(|(model_id, latency)| (model_id.to_string(), latency))
+            .collect())
+    }
+
+    /// This method detects a deadlocked simulation, where messages are held
+    /// awaiting delivery - by backpressure or a disabled model - but no held
+    /// message can be delivered, and no model event or scheduled input is
+    /// pending to make progress.
+    pub fn detect_deadlock(&self) -> Option<DeadlockReport> {
+        if self.blocked_messages.is_empty()
+            || !self.messages.is_empty()
+            || !self.scheduled_messages.is_empty()
+            || self.until_next_model_event().is_finite()
+        {
+            return None;
+        }
+        let deliverable = self.blocked_messages.iter().any(|message| {
+            self.is_model_enabled(message.target_id())
+                && self
+                    .models
+                    .iter()
+                    .find(|model| model.id() == message.target_id())
+                    .is_none_or(|model| {
+                        !self.backpressure || model.can_accept(message.target_port())
+                    })
+        });
+        if deliverable {
+            return None;
+        }
+        let model_ids = self
+            .models
+            .iter()
+            .map(|model| model.id())
+            .filter(|model_id| {
+                self.blocked_messages.iter().any(|message| {
+                    message.source_id() == *model_id || message.target_id() == *model_id
+                })
+            })
+            .map(|model_id| model_id.to_string())
+            .collect();
+        Some(DeadlockReport {
+            blocked_messages: self.blocked_messages.clone(),
+            model_ids,
+        })
+    }
+
+    /// When enabled, `step` returns a `SimulationError::Deadlock` for a
+    /// deadlocked simulation, as detected by `detect_deadlock`, instead of
+    /// stepping.  This is disabled by default.
+    pub fn set_error_on_deadlock(&mut self, enabled: bool) {
+        self.error_on_deadlock = enabled;
+    }
+
+    /// When enabled, each `step` records the number of active messages -
+    /// including messages held by backpressure - at the end of the step, as
+    /// a time series for diagnosing message buildup.  Recording is disabled
+    /// by default.
+    pub fn set_record_active_messages(&mut self, enabled: bool) {
+        self.record_active_messages = enabled;
+    }
+
+    /// The recorded (global time, active message count) series, with one
+    /// entry per step while recording is enabled.
+    pub fn active_message_series(&self) -> &[(f64, usize)] {
+        &self.active_message_series
+    }
+
+    /// This method sets a callback for periodic progress updates on long
+    /// runs, invoked every `every_steps` simulation steps - such as the steps
+    /// of `step_n` and `step_until`.  The callback replaces any prior
+    /// progress callback, and the step count restarts from zero.  An
+    /// `every_steps` of 0 never invokes the callback.
+    pub fn set_progress_callback(
+        &mut self,
+        every_steps: usize,
+        callback: impl FnMut(ProgressInfo) + 'static,
+    ) {
+        self.progress_reporter = Some(ProgressReporter::new(every_steps, callback));
+    }
+
+    /// This method removes the progress callback, if any.
+    pub fn clear_progress_callback(&mut self) {
+        self.progress_reporter = None;
+    }
+
+    /// This method sets a callback for observing capacity overflows as they
+    /// happen, invoked with the model ID and the message whenever a model
+    /// at capacity discards a delivered message during a step - such as a
+    /// processor with a full queue.  Messages held by backpressure are not
+    /// discarded, and do not invoke the callback.  The callback replaces any
+    /// prior overflow callback.
+    pub fn set_overflow_callback(&mut self, callback: impl FnMut(&str, &Message) + 'static) {
+        self.overflow_notifier = Some(OverflowNotifier::new(callback));
+    }
+
+    /// This method removes the overflow callback, if any.
+    pub fn clear_overflow_callback(&mut self) {
+        self.overflow_notifier = None;
+    }
+
     /// An accessor method for the simulation global time.
     pub fn get_global_time(&self) -> f64 {
         self.services.global_time()
     }
 
+    /// Select the representation of the simulation clock.  The default
+    /// floating-point clock accumulates rounding error over long runs,
+    /// while the fixed-point clock rounds each time advance to the nearest
+    /// tick, for exact discrete time.  The current global time is
+    /// converted to the new representation.
+    pub fn set_time_representation(&mut self, representation: TimeRepresentation) {
+        self.services.set_time_representation(representation);
+    }
+
+    /// An accessor method for the simulation clock representation.
+    pub fn time_representation(&self) -> TimeRepresentation {
+        self.services.time_representation()
+    }
+
+    /// An accessor method for the total number of messages routed between
+    /// models, across all simulation steps since the last reset.
+    pub fn total_messages_routed(&self) -> u64 {
+        self.total_messages_routed
+    }
+
+    /// An accessor method for the total number of internal events fired,
+    /// across all simulation steps since the last reset.
+    pub fn total_events(&self) -> u64 {
+        self.total_events
+    }
+
+    /// An accessor method for the number of messages routed through each
+    /// connector, keyed by connector ID, across all simulation steps since
+    /// the last reset.  Connectors without traffic are omitted.
+    pub fn connector_traffic(&self) -> &HashMap<String, u64> {
+        &self.connector_traffic
+    }
+
+    /// This method provides a mechanism for getting a named metric of any
+    /// model in a simulation, such as a processor queue length.  Models that
+    /// do not report the metric provide `None`.
+    pub fn get_metric(&self, model_id: &str, metric: &str) -> Result<Option<f64>, SimulationError> {
+        Ok(self
+            .models
+            .iter()
+            .find(|model| model.id() == model_id)
+            .ok_or(SimulationError::ModelNotFound)?
+            .metric(metric))
+    }
+
     /// This method provides a mechanism for getting the status of any model
     /// in a simulation.  The method takes the model ID as an argument, and
     /// returns the current status string for that model.
@@ -112,6 +987,18 @@ impl Simulation {
             .status())
     }
 
+    /// This method provides the machine-readable status of any model in a
+    /// simulation, such as the queue length of a processor, for
+    /// programmatic consumers of the status.
+    pub fn get_status_json(&self, model_id: &str) -> Result<serde_json::Value, SimulationError> {
+        Ok(self
+            .models
+            .iter()
+            .find(|model| model.id() == model_id)
+            .ok_or(SimulationError::ModelNotFound)?
+            .status_json())
+    }
+
     /// This method provides a mechanism for getting the records of any model
     /// in a simulation.  The method takes the model ID as an argument, and
     /// returns the records for that model.
@@ -124,6 +1011,22 @@ impl Simulation {
             .records())
     }
 
+    /// This method exports the records of any model in a simulation as CSV,
+    /// with a header row and one row per record.  The columns are the
+    /// `ModelRecord` fields - time, action, and subject.
+    pub fn records_to_csv(&self, model_id: &str) -> Result<String, SimulationError> {
+        let mut csv = String::from("time,action,subject\n");
+        self.get_records(model_id)?.iter().for_each(|record| {
+            csv.push_str(&format![
+                "{},{},{}\n",
+                record.time,
+                csv_field(&record.action),
+                csv_field(&record.subject)
+            ]);
+        });
+        Ok(csv)
+    }
+
     /// To enable simulation replications, the reset method resets the state
     /// of the simulation, except for the random number generator.
     /// Recreating a simulation from scratch for additional replications
@@ -131,11 +1034,148 @@ impl Simulation {
     pub fn reset(&mut self) {
         self.reset_messages();
         self.reset_global_time();
+        self.total_messages_routed = 0;
+        self.total_events = 0;
+        self.connector_traffic.clear();
+        self.active_message_series.clear();
+        self.snapshots.clear();
+    }
+
+    /// Snapshots enable stepping backward through a simulation, for
+    /// interactive debugging.  When the snapshot depth is non-zero, each
+    /// `step` retains the prior simulation state, up to `depth` steps back.
+    /// A depth of 0 (the default) disables snapshots, and discards any
+    /// snapshots already retained.  Lowering the depth discards the oldest
+    /// snapshots, so `step_back` still restores the most recent states.
+    pub fn set_snapshot_depth(&mut self, depth: usize) {
+        self.snapshot_depth = depth;
+        while self.snapshots.len() > depth {
+            self.snapshots.pop_front();
+        }
+    }
+
+    /// This method restores the simulation state from before the most
+    /// recent step, as captured by the snapshot ring, including the random
+    /// number generator states, so that repeating a step reproduces the
+    /// original step behavior.
+    pub fn step_back(&mut self) -> Result<(), SimulationError> {
+        let snapshot = self
+            .snapshots
+            .pop_back()
+            .ok_or(SimulationError::SnapshotNotFound)?;
+        self.restore(snapshot);
+        Ok(())
+    }
+
+    fn snapshot(&mut self) -> Snapshot {
+        // Records are append-only, so the models are copied without their
+        // records, which are instead truncated to their prior lengths on
+        // restore - the snapshot cost is independent of the run length
+        let mut record_counts = Vec::new();
+        let models = self
+            .models
+            .iter_mut()
+            .map(|model| match model.records_mut() {
+                Some(records) => {
+                    let records = std::mem::take(records);
+                    let copy = model.clone();
+                    record_counts.push(Some(records.len()));
+                    if let Some(slot) = model.records_mut() {
+                        *slot = records;
+                    }
+                    copy
+                }
+                None => {
+                    record_counts.push(None);
+                    model.clone()
+                }
+            })
+            .collect();
+        // Models share their random number generators with their copies,
+        // so the generator states are saved separately
+        let rng_states = std::iter::once(self.services.global_rng.clone())
+            .chain(self.model_rngs.values().cloned())
+            .chain(self.models.iter_mut().flat_map(|model| {
+                model
+                    .rngs_mut()
+                    .into_iter()
+                    .map(|rng| rng.clone())
+                    .collect::<Vec<DynRng>>()
+            }))
+            .map(|rng| {
+                let state = rng.borrow().save();
+                (rng, state)
+            })
+            .collect();
+        Snapshot {
+            models,
+            record_counts,
+            rng_states,
+            messages: self.messages.clone(),
+            blocked_messages: self.blocked_messages.clone(),
+            scheduled_messages: self.scheduled_messages.clone(),
+            global_time: self.services.global_time,
+            total_messages_routed: self.total_messages_routed,
+            total_events: self.total_events,
+            connector_traffic: self.connector_traffic.clone(),
+            active_message_series_len: self.active_message_series.len(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        let models: Vec<Model> = snapshot
+            .models
+            .into_iter()
+            .zip(snapshot.record_counts)
+            .enumerate()
+            .map(|(index, (mut model, record_count))| {
+                let current = self
+                    .models
+                    .get_mut(index)
+                    .filter(|current| current.id() == model.id());
+                if let (Some(record_count), Some(current)) = (record_count, current) {
+                    if let (Some(records), Some(slot)) =
+                        (current.records_mut(), model.records_mut())
+                    {
+                        let mut records = std::mem::take(records);
+                        records.truncate(record_count);
+                        *slot = records;
+                    }
+                }
+                model
+            })
+            .collect();
+        self.models = models;
+        snapshot
+            .rng_states
+            .iter()
+            .for_each(|(rng, state)| rng.borrow_mut().restore(&**state));
+        self.messages = snapshot.messages;
+        self.blocked_messages = snapshot.blocked_messages;
+        self.scheduled_messages = snapshot.scheduled_messages;
+        self.services.global_time = snapshot.global_time;
+        self.total_messages_routed = snapshot.total_messages_routed;
+        self.total_events = snapshot.total_events;
+        self.connector_traffic = snapshot.connector_traffic;
+        self.active_message_series
+            .truncate(snapshot.active_message_series_len);
+    }
+
+    fn retain_snapshot(&mut self, snapshot: Snapshot) {
+        if self.snapshot_depth == 0 {
+            return;
+        }
+        if self.snapshots.len() == self.snapshot_depth {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
     }
 
-    /// Clear the active messages in a simulation.
+    /// Clear the active, blocked, and scheduled messages in a simulation.
     pub fn reset_messages(&mut self) {
         self.messages = Vec::new();
+        self.blocked_messages = Vec::new();
+        self.scheduled_messages = Vec::new();
     }
 
     /// Reset the simulation global time to 0.0.
@@ -149,19 +1189,57 @@ impl Simulation {
         self.models.iter_mut().collect()
     }
 
+    /// This method notifies the source model of a message held by
+    /// backpressure, if the message was emitted by a model.
+    fn notify_blocked(&mut self, message: &Message) {
+        if let Some(source) = self
+            .models
+            .iter_mut()
+            .find(|model| model.id() == message.source_id())
+        {
+            source.message_blocked(
+                &ModelMessage {
+                    port_name: message.source_port().to_string(),
+                    content: message.content().to_string(),
+                    attributes: message.attributes().clone(),
+                },
+                &mut self.services,
+            );
+        }
+    }
+
+    /// This method constructs the list of connectors for a given source
+    /// model ID and port, in fan-out delivery order - ascending connector
+    /// priority, and then connector ID for equal priorities.
+    fn get_message_connectors(&self, source_id: &str, source_port: &str) -> Vec<&Connector> {
+        let mut connectors: Vec<&Connector> = self
+            .connectors
+            .iter()
+            .filter(|connector| {
+                connector.source_id() == source_id && connector.source_port() == source_port
+            })
+            .collect();
+        connectors.sort_by_key(|connector| (connector.priority().unwrap_or(0), connector.id()));
+        connectors
+    }
+
+    /// This method constructs a list of connector IDs for a given source
+    /// model ID and port.  This message routing information is derived from
+    /// the connectors configuration.
+    fn get_message_connector_ids(&self, source_id: &str, source_port: &str) -> Vec<String> {
+        self.get_message_connectors(source_id, source_port)
+            .iter()
+            .map(|connector| connector.id().to_string())
+            .collect()
+    }
+
     /// This method constructs a list of target IDs for a given source model
     /// ID and port.  This message target information is derived from the
     /// connectors configuration.
     fn get_message_target_ids(&self, source_id: &str, source_port: &str) -> Vec<String> {
-        self.connectors
+        self.get_message_connectors(source_id, source_port)
             .iter()
-            .filter_map(|connector| {
-                if connector.source_id() == source_id && connector.source_port() == source_port {
-                    Some(connector.target_id().to_string())
-                } else {
-                    None
-                }
-            })
+            .map(|connector| connector.target_id().to_string())
             .collect()
     }
 
@@ -169,15 +1247,23 @@ impl Simulation {
     /// ID and port.  This message target information is derived from the
     /// connectors configuration.
     fn get_message_target_ports(&self, source_id: &str, source_port: &str) -> Vec<String> {
-        self.connectors
+        self.get_message_connectors(source_id, source_port)
             .iter()
-            .filter_map(|connector| {
-                if connector.source_id() == source_id && connector.source_port() == source_port {
-                    Some(connector.target_port().to_string())
-                } else {
-                    None
-                }
-            })
+            .map(|connector| connector.target_port().to_string())
+            .collect()
+    }
+
+    /// This method constructs a list of content transforms for a given
+    /// source model ID and port.  This message transformation information
+    /// is derived from the connectors configuration.
+    fn get_message_transforms(
+        &self,
+        source_id: &str,
+        source_port: &str,
+    ) -> Vec<Option<ContentTransform>> {
+        self.get_message_connectors(source_id, source_port)
+            .iter()
+            .map(|connector| connector.transform().cloned())
             .collect()
     }
 
@@ -185,92 +1271,646 @@ impl Simulation {
     /// without needing to create that message through the standard
     /// simulation constructs.  This enables live simulation interaction,
     /// disruption, and manipulation - all through the standard simulation
-    /// message system.
+    /// message system.  The message time must not precede the global time
+    /// at which the message is handled, or the step fails with a
+    /// `SimulationError::CausalityViolation`.
     pub fn inject_input(&mut self, message: Message) {
         self.messages.push(message);
     }
 
+    /// Scheduled input injection creates a message for delivery at a future
+    /// global time, such as a planned disruption.  The simulation steps to
+    /// the scheduled time, if no other event occurs first, and the message
+    /// is then handled as an injected input.  The message is timestamped
+    /// with the scheduled time, whatever its original time.
+    pub fn schedule_input(&mut self, message: Message, at: f64) -> Result<(), SimulationError> {
+        if at.is_nan() || at < self.services.global_time() {
+            return Err(SimulationError::InvalidScheduleTime);
+        }
+        self.scheduled_messages.push((at, message.with_time(at)));
+        Ok(())
+    }
+
+    /// Replay re-drives the simulation with a recorded trace of external
+    /// inputs, scheduling each trace message for delivery at its recorded
+    /// time, to reproduce the behavior of a prior run.  The simulation is
+    /// stepped until every trace message has been delivered and no further
+    /// events are pending, so the simulation should be driven solely by the
+    /// trace - a perpetual source model, such as a generator, would prevent
+    /// the replay from completing.  The messages from all the replay steps
+    /// are returned.
+    pub fn replay(&mut self, trace: &[Message]) -> Result<Vec<Message>, SimulationError> {
+        trace
+            .iter()
+            .try_for_each(|message| self.schedule_input(message.clone(), *message.time()))?;
+        let mut message_records: Vec<Message> = Vec::new();
+        while !self.paused && self.until_next_event().is_finite() {
+            message_records.extend(self.step()?);
+        }
+        Ok(message_records)
+    }
+
+    /// The time until the earliest scheduled input, or infinity if no input
+    /// is scheduled.
+    fn until_next_scheduled_input(&self) -> f64 {
+        self.scheduled_messages
+            .iter()
+            .fold(f64::INFINITY, |min, (at, _)| {
+                f64::min(min, at - self.services.global_time())
+            })
+    }
+
+    /// This method moves due scheduled inputs into the active messages.
+    /// Where there are no active messages and the next scheduled input
+    /// precedes every model internal event, the simulation first advances
+    /// to the time of that scheduled input.
+    fn release_scheduled_inputs(&mut self) {
+        let next_scheduled = self
+            .scheduled_messages
+            .iter()
+            .fold(f64::INFINITY, |min, (at, _)| f64::min(min, *at));
+        if next_scheduled == f64::INFINITY {
+            return;
+        }
+        let until_scheduled = next_scheduled - self.services.global_time();
+        if self.messages.is_empty()
+            && self.blocked_messages.is_empty()
+            && until_scheduled <= self.until_next_model_event()
+        {
+            self.advance_model_clocks(until_scheduled);
+            self.services.set_global_time(next_scheduled);
+        }
+        // Inputs due within the event tolerance are released, so that rounding
+        // in the time advance does not defer them
+        let global_time = self.services.global_time();
+        let event_tolerance = self.event_tolerance();
+        let (due, pending): (Vec<_>, Vec<_>) = self
+            .scheduled_messages
+            .drain(..)
+            .partition(|(at, _)| *at <= global_time + event_tolerance);
+        self.scheduled_messages = pending;
+        self.messages
+            .extend(due.into_iter().map(|(_, message)| message));
+    }
+
     /// The simulation step is foundational for a discrete event simulation.
     /// This method executes a single discrete event simulation step,
     /// including internal state transitions, external state transitions,
     /// message orchestration, global time accounting, and step messages
     /// output.
     pub fn step(&mut self) -> Result<Vec<Message>, SimulationError> {
-        let messages = self.messages.clone();
-        let mut next_messages: Vec<Message> = Vec::new();
+        self.step_timed(&mut StepTiming::default())
+    }
+
+    /// This method executes a single simulation step, as with `step`, and
+    /// additionally reports the time spent in each phase of the step.
+    pub fn step_instrumented(&mut self) -> Result<(Vec<Message>, StepTiming), SimulationError> {
+        let mut timing = StepTiming::default();
+        let messages = self.step_timed(&mut timing)?;
+        Ok((messages, timing))
+    }
+
+    fn step_timed(&mut self, timing: &mut StepTiming) -> Result<Vec<Message>, SimulationError> {
+        if self.paused {
+            return Ok(Vec::new());
+        }
+        // The prior state is retained for `step_back` after a successful
+        // step, and restored after a failed step with rollback enabled
+        let snapshot = if self.rollback_on_error || self.snapshot_depth > 0 {
+            Some(self.snapshot())
+        } else {
+            None
+        };
+        match (self.step_untracked(timing), snapshot) {
+            (Ok(messages), Some(snapshot)) => {
+                self.retain_snapshot(snapshot);
+                Ok(messages)
+            }
+            (Err(error), Some(snapshot)) if self.rollback_on_error => {
+                self.restore(snapshot);
+                Err(error)
+            }
+            (result, _) => result,
+        }
+    }
+
+    fn step_untracked(&mut self, timing: &mut StepTiming) -> Result<Vec<Message>, SimulationError> {
+        if let Some(budget) = self.event_budget {
+            if self.total_events >= budget {
+                return Err(SimulationError::EventBudgetExceeded(budget));
+            }
+        }
+        if self.error_on_deadlock {
+            if let Some(report) = self.detect_deadlock() {
+                return Err(SimulationError::Deadlock(report.model_ids.join(", ")));
+            }
+        }
+        let mut lap = Lap::start();
+        self.release_scheduled_inputs();
+        self.check_causality()?;
+        // Blocked messages are retried ahead of the newly active messages
+        let mut messages = std::mem::take(&mut self.blocked_messages);
+        messages.extend(self.messages.clone());
+        if self.dedup_messages {
+            messages = dedup_messages(messages);
+        }
+        let mut next_messages: Vec<(String, Message)> = Vec::new();
         // Process external events
         if !messages.is_empty() {
-            (0..self.models.len()).try_for_each(|model_index| -> Result<(), SimulationError> {
-                let model_messages: Vec<ModelMessage> = messages
-                    .iter()
-                    .filter_map(|message| {
-                        if message.target_id() == self.models[model_index].id() {
-                            Some(ModelMessage {
-                                port_name: message.target_port().to_string(),
-                                content: message.content().to_string(),
-                            })
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
-                model_messages
-                    .iter()
-                    .try_for_each(|model_message| -> Result<(), SimulationError> {
-                        self.models[model_index].events_ext(model_message, &mut self.services)
-                    })
-            })?;
+            let external_events =
+                (0..self.models.len()).try_for_each(|model_index| -> Result<(), SimulationError> {
+                    let mut model_messages: Vec<&Message> = messages
+                        .iter()
+                        .filter(|message| message.target_id() == self.models[model_index].id())
+                        .collect();
+                    if self.fifo_delivery {
+                        model_messages.sort_by(|a, b| {
+                            a.time()
+                                .total_cmp(b.time())
+                                .then_with(|| a.source_id().cmp(b.source_id()))
+                        });
+                    }
+                    self.services.model_rng =
+                        self.model_rngs.get(self.models[model_index].id()).cloned();
+                    let enabled = self.is_model_enabled(self.models[model_index].id());
+                    model_messages
+                        .iter()
+                        .try_for_each(|message| -> Result<(), SimulationError> {
+                            if !enabled {
+                                if !self.drop_disabled_messages {
+                                    self.blocked_messages.push((*message).clone());
+                                }
+                                return Ok(());
+                            }
+                            if self.strict_ports {
+                                self.check_input_port(model_index, message.target_port())?;
+                            }
+                            let overflow =
+                                !self.models[model_index].can_accept(message.target_port());
+                            if self.backpressure && overflow {
+                                self.blocked_messages.push((*message).clone());
+                                self.notify_blocked(message);
+                                return Ok(());
+                            }
+                            // Reactive models may emit messages immediately
+                            let outgoing_messages = self.models[model_index]
+                                .events_ext_with_output(
+                                    &ModelMessage {
+                                        port_name: message.target_port().to_string(),
+                                        content: message.content().to_string(),
+                                        attributes: message.attributes().clone(),
+                                    },
+                                    &mut self.services,
+                                )?;
+                            if let (true, Some(overflow_notifier)) =
+                                (overflow, &self.overflow_notifier)
+                            {
+                                overflow_notifier.notify(self.models[model_index].id(), message);
+                            }
+                            let routed_messages =
+                                self.route_messages(model_index, &outgoing_messages);
+                            next_messages.extend(routed_messages);
+                            Ok(())
+                        })
+                });
+            self.services.model_rng = None;
+            external_events?;
         }
+        timing.external_events_ns = lap.lap();
         // Process internal events and gather associated messages
-        let until_next_event: f64 = if self.messages.is_empty() {
-            self.models().iter().fold(f64::INFINITY, |min, model| {
-                f64::min(min, model.until_next_event())
-            })
+        self.validate_until_next_events()?;
+        // With only held messages pending, the simulation advances to the
+        // next model event, without passing a scheduled input
+        let until_next_event: f64 = if messages.len() == self.blocked_messages.len() {
+            f64::min(
+                self.until_next_model_event(),
+                self.until_next_scheduled_input(),
+            )
         } else {
             0.0
         };
-        self.models().iter_mut().for_each(|model| {
-            model.time_advance(until_next_event);
-        });
-        self.services
-            .set_global_time(self.services.global_time() + until_next_event);
-        let errors: Result<Vec<()>, SimulationError> = (0..self.models.len())
+        self.services.advance_global_time(until_next_event)?;
+        // When every model is passive, the model clocks are left untouched,
+        // since an infinite time advance would produce NaN clocks
+        if until_next_event.is_finite() {
+            self.advance_model_clocks(until_next_event);
+        }
+        timing.next_event_ns = lap.lap();
+        // Imminent models fire in priority order, retaining the simulation
+        // model order for equal priorities
+        let event_tolerance = self.event_tolerance();
+        let mut model_indices: Vec<usize> = (0..self.models.len()).collect();
+        model_indices.sort_by_key(|model_index| self.models[*model_index].priority());
+        let errors: Result<Vec<()>, SimulationError> = model_indices
+            .into_iter()
             .map(|model_index| -> Result<(), SimulationError> {
-                if self.models[model_index].until_next_event() == 0.0 {
-                    self.models[model_index]
-                        .events_int(&mut self.services)?
-                        .iter()
-                        .for_each(|outgoing_message| {
-                            let target_ids = self.get_message_target_ids(
-                                self.models[model_index].id(), // Outgoing message source model ID
-                                &outgoing_message.port_name,   // Outgoing message source model port
-                            );
-                            let target_ports = self.get_message_target_ports(
-                                self.models[model_index].id(), // Outgoing message source model ID
-                                &outgoing_message.port_name,   // Outgoing message source model port
-                            );
-                            target_ids.iter().zip(target_ports.iter()).for_each(
-                                |(target_id, target_port)| {
-                                    next_messages.push(Message::new(
-                                        self.models[model_index].id().to_string(),
-                                        outgoing_message.port_name.clone(),
-                                        target_id.clone(),
-                                        target_port.clone(),
-                                        self.services.global_time(),
-                                        outgoing_message.content.clone(),
-                                    ));
-                                },
-                            );
-                        });
+                if self.models[model_index].until_next_event() <= event_tolerance
+                    && self.is_model_enabled(self.models[model_index].id())
+                {
+                    self.total_events += 1;
+                    self.services.model_rng =
+                        self.model_rngs.get(self.models[model_index].id()).cloned();
+                    self.services.observation = self.observe(model_index)?;
+                    let outgoing_messages =
+                        self.models[model_index].events_int(&mut self.services)?;
+                    let routed_messages = self.route_messages(model_index, &outgoing_messages);
+                    next_messages.extend(routed_messages);
                 }
                 Ok(())
             })
             .collect();
+        self.services.model_rng = None;
+        self.services.observation = None;
         errors?;
+        // Messages are ordered by source model priority, source model ID,
+        // source port, connector priority, and then connector ID, for
+        // reproducible step outputs that follow the model firing order.
+        // The sort is stable, so messages along the same connector retain
+        // their emission order - same-port messages from one model arrive at
+        // each target model in the order they were emitted
+        let model_priorities: HashMap<&str, i32> = self
+            .models
+            .iter()
+            .map(|model| (model.id(), model.priority()))
+            .collect();
+        let model_priority = |model_id: &str| model_priorities.get(model_id).copied().unwrap_or(0);
+        let connector_priorities: HashMap<&str, i32> = self
+            .connectors
+            .iter()
+            .map(|connector| (connector.id(), connector.priority().unwrap_or(0)))
+            .collect();
+        let connector_priority =
+            |connector_id: &str| connector_priorities.get(connector_id).copied().unwrap_or(0);
+        next_messages.sort_by(|(a_connector_id, a), (b_connector_id, b)| {
+            (
+                model_priority(a.source_id()),
+                a.source_id(),
+                a.source_port(),
+                connector_priority(a_connector_id),
+                a_connector_id,
+            )
+                .cmp(&(
+                    model_priority(b.source_id()),
+                    b.source_id(),
+                    b.source_port(),
+                    connector_priority(b_connector_id),
+                    b_connector_id,
+                ))
+        });
+        let next_messages: Vec<Message> = next_messages
+            .into_iter()
+            .map(|(_, message)| message)
+            .collect();
+        self.check_active_messages(&next_messages)?;
+        self.total_messages_routed += next_messages.len() as u64;
         self.messages = next_messages;
+        if self.record_active_messages {
+            self.active_message_series.push((
+                self.services.global_time(),
+                self.messages.len() + self.blocked_messages.len(),
+            ));
+        }
+        timing.internal_events_ns = lap.lap();
+        if let Some(progress_reporter) = &mut self.progress_reporter {
+            progress_reporter.step(
+                self.services.global_time(),
+                self.messages.len() + self.blocked_messages.len(),
+            );
+        }
         Ok(self.get_messages().clone())
     }
 
+    /// This method checks that no active message is timestamped before the
+    /// current global time, beyond the event tolerance - such as a message
+    /// injected with a past time - since handling the message would violate
+    /// causality.  Messages held by backpressure are exempt, since they are
+    /// delivered late by design.
+    fn check_causality(&self) -> Result<(), SimulationError> {
+        let earliest_time = self.services.global_time() - self.event_tolerance();
+        match self
+            .messages
+            .iter()
+            .find(|message| *message.time() < earliest_time)
+        {
+            Some(message) => Err(SimulationError::CausalityViolation(format![
+                "from {} ({}) to {} ({}) at {}",
+                message.source_id(),
+                message.source_port(),
+                message.target_id(),
+                message.target_port(),
+                message.time()
+            ])),
+            None => Ok(()),
+        }
+    }
+
+    /// This method checks that the active messages, along with the messages
+    /// held by backpressure, are within the active message limit.  The error
+    /// names the target model with the most active messages.
+    fn check_active_messages(&self, next_messages: &[Message]) -> Result<(), SimulationError> {
+        let limit = match self.max_active_messages {
+            Some(limit) => limit,
+            None => return Ok(()),
+        };
+        if next_messages.len() + self.blocked_messages.len() <= limit {
+            return Ok(());
+        }
+        let mut target_counts: HashMap<&str, usize> = HashMap::new();
+        next_messages
+            .iter()
+            .chain(self.blocked_messages.iter())
+            .for_each(|message| {
+                *target_counts.entry(message.target_id()).or_insert(0) += 1;
+            });
+        let flooded_target = target_counts
+            .into_iter()
+            .max_by_key(|(_, count)| *count)
+            .map(|(target_id, _)| target_id.to_string())
+            .unwrap_or_default();
+        Err(SimulationError::MessageLimitExceeded(flooded_target))
+    }
+
+    /// This method checks that a model recognizes an input port, where the
+    /// model reports its input ports.
+    fn check_input_port(&self, model_index: usize, port: &str) -> Result<(), SimulationError> {
+        match self.models[model_index].input_ports() {
+            Some(ports) if !ports.iter().any(|input_port| input_port == port) => {
+                Err(SimulationError::UnrecognizedPort(
+                    self.models[model_index].id().to_string(),
+                    port.to_string(),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// This method provides the current value of the metric observed by a
+    /// model, where the model reports an observation target.
+    fn observe(&self, model_index: usize) -> Result<Option<f64>, SimulationError> {
+        match self.models[model_index].observation_target() {
+            Some((target_id, metric)) => Ok(self
+                .models
+                .iter()
+                .find(|model| model.id() == target_id)
+                .ok_or(SimulationError::ModelNotFound)?
+                .metric(metric)),
+            None => Ok(None),
+        }
+    }
+
+    /// This method routes the outgoing messages of a model along the
+    /// connectors from the source model ports, producing a message for
+    /// each target model and port, and counting the connector traffic.
+    /// Each message is paired with the ID of its connector.
+    fn route_messages(
+        &mut self,
+        model_index: usize,
+        outgoing_messages: &[ModelMessage],
+    ) -> Vec<(String, Message)> {
+        let mut routed_messages: Vec<(String, Message)> = Vec::new();
+        outgoing_messages.iter().for_each(|outgoing_message| {
+            let connector_ids = self.get_message_connector_ids(
+                self.models[model_index].id(), // Outgoing message source model ID
+                &outgoing_message.port_name,   // Outgoing message source model port
+            );
+            let target_ids = self.get_message_target_ids(
+                self.models[model_index].id(), // Outgoing message source model ID
+                &outgoing_message.port_name,   // Outgoing message source model port
+            );
+            let target_ports = self.get_message_target_ports(
+                self.models[model_index].id(), // Outgoing message source model ID
+                &outgoing_message.port_name,   // Outgoing message source model port
+            );
+            let transforms = self.get_message_transforms(
+                self.models[model_index].id(), // Outgoing message source model ID
+                &outgoing_message.port_name,   // Outgoing message source model port
+            );
+            connector_ids
+                .iter()
+                .zip(target_ids.iter())
+                .zip(target_ports.iter())
+                .zip(transforms.iter())
+                .for_each(|(((connector_id, target_id), target_port), transform)| {
+                    *self
+                        .connector_traffic
+                        .entry(connector_id.clone())
+                        .or_insert(0) += 1;
+                    routed_messages.push((
+                        connector_id.clone(),
+                        Message::new(
+                            self.models[model_index].id().to_string(),
+                            outgoing_message.port_name.clone(),
+                            target_id.clone(),
+                            target_port.clone(),
+                            self.services.global_time(),
+                            match transform {
+                                Some(transform) => transform.apply(&outgoing_message.content),
+                                None => outgoing_message.content.clone(),
+                            },
+                        )
+                        .with_attributes(outgoing_message.attributes.clone()),
+                    ));
+                });
+        });
+        routed_messages
+    }
+
+    /// A model reporting a NaN time until its next event would silently
+    /// corrupt the model clocks on time advance, so such models are
+    /// rejected, by ID.
+    fn validate_until_next_events(&self) -> Result<(), SimulationError> {
+        match self
+            .models
+            .iter()
+            .find(|model| model.until_next_event().is_nan())
+        {
+            Some(model) => Err(SimulationError::InvalidTimeAdvance(model.id().to_string())),
+            None => Ok(()),
+        }
+    }
+
+    /// This method provides the time until the next simulation event - zero
+    /// when there are active messages to process, and otherwise the time
+    /// until the earliest model internal event or scheduled input.
+    pub fn until_next_event(&self) -> f64 {
+        if self.messages.is_empty() {
+            f64::min(
+                self.until_next_model_event(),
+                self.until_next_scheduled_input(),
+            )
+        } else {
+            0.0
+        }
+    }
+
+    /// A simulation is quiescent when nothing further will happen - there
+    /// are no active messages, including messages held by backpressure, and
+    /// no pending model events or scheduled inputs.
+    pub fn is_quiescent(&self) -> bool {
+        self.messages.is_empty()
+            && self.blocked_messages.is_empty()
+            && self.until_next_event().is_infinite()
+    }
+
+    /// Assert that the simulation is quiescent, for test suites that step
+    /// a simulation until nothing is happening before asserting on it.
+    #[cfg(feature = "test-util")]
+    #[track_caller]
+    pub fn assert_quiescent(&self) {
+        assert!(
+            self.is_quiescent(),
+            "simulation is not quiescent - {} active messages, {} blocked messages, and {} until the next event",
+            self.messages.len(),
+            self.blocked_messages.len(),
+            self.until_next_event()
+        );
+    }
+
+    /// This method provides the IDs of the imminent models - those with an
+    /// internal event at the time of the next simulation event - in the
+    /// order they will fire.  Active messages may change the imminent
+    /// models, since external events are processed first in a step.
+    pub fn imminent_models(&self) -> Vec<String> {
+        let until_next_event = self.until_next_event();
+        if !until_next_event.is_finite() {
+            return Vec::new();
+        }
+        let mut imminent_models: Vec<&Model> = self
+            .models
+            .iter()
+            .filter(|model| {
+                self.is_model_enabled(model.id())
+                    && (model.until_next_event() - until_next_event).abs() <= self.event_tolerance()
+            })
+            .collect();
+        imminent_models.sort_by_key(|model| model.priority());
+        imminent_models
+            .into_iter()
+            .map(|model| model.id().to_string())
+            .collect()
+    }
+
+    /// This method provides the time until the earliest internal event of
+    /// an enabled model.
+    fn until_next_model_event(&self) -> f64 {
+        self.models
+            .iter()
+            .filter(|model| self.is_model_enabled(model.id()))
+            .fold(f64::INFINITY, |min, model| {
+                f64::min(min, model.until_next_event())
+            })
+    }
+
+    /// This method advances the clocks of the enabled models, leaving the
+    /// clocks of disabled models paused.
+    fn advance_model_clocks(&mut self, time_delta: f64) {
+        let disabled_models = &self.disabled_models;
+        self.models
+            .iter_mut()
+            .filter(|model| {
+                !disabled_models
+                    .iter()
+                    .any(|disabled_model| disabled_model == model.id())
+            })
+            .for_each(|model| model.time_advance(time_delta));
+    }
+
+    /// This method executes a single simulation step, after waiting for the
+    /// simulated time until the next event to elapse in wall-clock time,
+    /// scaled by `scale` (simulated time units per second).  This enables
+    /// real-time playback of a simulation, for visualization and live
+    /// demonstrations.  Zero-delay events are executed without waiting.
+    /// Waiting is not supported for WASM targets, where the step is executed
+    /// immediately.
+    pub fn step_realtime(&mut self, scale: f64) -> Result<Vec<Message>, SimulationError> {
+        if !(scale.is_finite() && scale > 0.0) {
+            return Err(SimulationError::InvalidTimeScale);
+        }
+        let until_next_event = self.until_next_event();
+        #[cfg(not(target_arch = "wasm32"))]
+        if !self.paused && until_next_event.is_finite() && until_next_event > 0.0 {
+            std::thread::sleep(std::time::Duration::from_secs_f64(until_next_event / scale));
+        }
+        self.step()
+    }
+
+    /// This method executes simulation `step` calls, until a global time
+    /// has been exceeded, as with `step_until`, and evaluates each of the
+    /// metrics over the run.  The metric values are returned by metric name.
+    pub fn run_and_collect(
+        &mut self,
+        until: f64,
+        metrics: &[Box<dyn Metric>],
+    ) -> Result<HashMap<String, f64>, SimulationError> {
+        let mut metric_set = MetricSet::new(metrics, self);
+        loop {
+            let messages = self.step()?;
+            if !self.paused && self.services.global_time() < until {
+                metric_set.observe(self, &messages)?;
+            } else {
+                break;
+            }
+        }
+        Ok(metric_set.collect())
+    }
+
+    /// This method executes simulation `step` calls, until a global time
+    /// has been exceeded, as with `step_until`, or until the cancellation
+    /// flag is set - for example, by a UI thread.  The flag is checked
+    /// before each step, so a cancelled run stops between steps, in a
+    /// consistent state, and may be resumed.  The messages from the
+    /// completed steps are returned, along with whether the run was
+    /// cancelled.
+    pub fn step_until_cancellable(
+        &mut self,
+        until: f64,
+        cancel: Arc<AtomicBool>,
+    ) -> Result<(Vec<Message>, bool), SimulationError> {
+        let mut message_records: Vec<Message> = Vec::new();
+        loop {
+            if cancel.load(Ordering::Relaxed) {
+                return Ok((message_records, true));
+            }
+            self.step()?;
+            if !self.paused && self.services.global_time() < until {
+                message_records.extend(self.get_messages().clone());
+            } else {
+                return Ok((message_records, false));
+            }
+        }
+    }
+
+    /// This method executes simulation `step` calls until the breakpoint
+    /// predicate holds, or until a maximum global time has been exceeded,
+    /// as with `step_until`.  The predicate is evaluated after each step,
+    /// with the simulation and the messages of that step, so a broken run
+    /// is paused exactly at the breakpoint, and may be inspected, manipulated,
+    /// and resumed.  The messages from the steps up to the breakpoint are
+    /// returned, along with whether the breakpoint was hit.
+    pub fn step_until_break(
+        &mut self,
+        breakpoint: impl Fn(&Simulation, &[Message]) -> bool,
+        max: f64,
+    ) -> Result<BreakResult, SimulationError> {
+        let mut messages: Vec<Message> = Vec::new();
+        loop {
+            self.step()?;
+            if self.paused || self.services.global_time() >= max {
+                return Ok(BreakResult {
+                    broke: false,
+                    messages,
+                });
+            }
+            messages.extend(self.messages.iter().cloned());
+            if breakpoint(self, &self.messages) {
+                return Ok(BreakResult {
+                    broke: true,
+                    messages,
+                });
+            }
+        }
+    }
+
     /// This method executes simulation `step` calls, until a global time
     /// has been exceeded.  At which point, the messages from all the
     /// simulation steps are returned.
@@ -278,7 +1918,7 @@ impl Simulation {
         let mut message_records: Vec<Message> = Vec::new();
         loop {
             self.step()?;
-            if self.services.global_time() < until {
+            if !self.paused && self.services.global_time() < until {
                 message_records.extend(self.get_messages().clone());
             } else {
                 break;
@@ -289,16 +1929,100 @@ impl Simulation {
 
     /// This method executes the specified number of simulation steps, `n`.
     /// Upon execution of the n steps, the messages from all the steps are
-    /// returned.
+    /// returned.  Stepping stops at the first failed step, returning its
+    /// error - `step_n_partial` additionally retains the messages of the
+    /// steps before the failure.  An `n` of 0 executes no steps, and returns
+    /// no messages.
     pub fn step_n(&mut self, n: usize) -> Result<Vec<Message>, SimulationError> {
-        let mut message_records: Vec<Message> = Vec::new();
+        let partial_steps = self.step_n_partial(n);
+        match partial_steps.error {
+            Some(error) => Err(error),
+            None => Ok(partial_steps.messages),
+        }
+    }
+
+    /// This method executes up to `n` simulation steps, as with `step_n`,
+    /// stopping immediately at the first failed step.  The messages of the
+    /// steps completed before the failure are returned alongside the error,
+    /// rather than discarded.
+    pub fn step_n_partial(&mut self, n: usize) -> PartialSteps {
+        let mut messages: Vec<Message> = Vec::new();
+        for steps in 0..n {
+            match self.step() {
+                Ok(step_messages) => messages.extend(step_messages),
+                Err(error) => {
+                    return PartialSteps {
+                        steps,
+                        messages,
+                        error: Some(error),
+                    }
+                }
+            }
+        }
+        PartialSteps {
+            steps: n,
+            messages,
+            error: None,
+        }
+    }
+
+    /// This method executes the specified number of simulation steps, `n`,
+    /// as with `step_n`, but returns only the number of messages produced by
+    /// each step - the messages themselves are never accumulated, for
+    /// inexpensive monitoring of long runs.
+    pub fn step_counts(&mut self, n: usize) -> Result<Vec<usize>, SimulationError> {
         (0..n)
-            .map(|_| -> Result<Vec<Message>, SimulationError> {
+            .map(|_| -> Result<usize, SimulationError> {
                 self.step()?;
-                message_records.extend(self.messages.clone());
-                Ok(Vec::new())
+                Ok(self.messages.len())
             })
-            .find(Result::is_err)
-            .unwrap_or(Ok(message_records))
+            .collect()
+    }
+
+    /// This method executes simulation steps until at least `target_events`
+    /// messages have been produced, since many steps produce no messages.
+    /// Stepping stops early if the simulation has no further events.  Upon
+    /// completion, the messages from all the steps are returned.
+    pub fn step_until_events(
+        &mut self,
+        target_events: usize,
+    ) -> Result<Vec<Message>, SimulationError> {
+        let mut message_records: Vec<Message> = Vec::new();
+        while !self.paused
+            && message_records.len() < target_events
+            && self.until_next_event().is_finite()
+        {
+            self.step()?;
+            message_records.extend(self.messages.clone());
+        }
+        Ok(message_records)
+    }
+}
+
+/// Remove duplicate messages, retaining the first of each set of messages
+/// with the same source, target, target port, content, and time.
+fn dedup_messages(messages: Vec<Message>) -> Vec<Message> {
+    let mut unique_messages: Vec<Message> = Vec::new();
+    messages.into_iter().for_each(|message| {
+        let duplicate = unique_messages.iter().any(|unique_message| {
+            unique_message.source_id() == message.source_id()
+                && unique_message.target_id() == message.target_id()
+                && unique_message.target_port() == message.target_port()
+                && unique_message.content() == message.content()
+                && unique_message.time() == message.time()
+        });
+        if !duplicate {
+            unique_messages.push(message);
+        }
+    });
+    unique_messages
+}
+
+/// Quote a CSV field, where required, as per RFC 4180.
+fn csv_field(field: &str) -> String {
+    if field.contains(&[',', '"', '\n', '\r'][..]) {
+        format!["\"{}\"", field.replace('"', "\"\"")]
+    } else {
+        field.to_string()
     }
 }
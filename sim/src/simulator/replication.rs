@@ -0,0 +1,184 @@
+use super::{Message, Simulation};
+use crate::input_modeling::dynamic_rng::SubstreamScheme;
+use crate::output_analysis::IndependentSample;
+use crate::utils::errors::SimulationError;
+
+// The one-sided tail probabilities with tabulated t scores
+const SUPPORTED_ALPHAS: [f64; 7] = [0.1, 0.05, 0.025, 0.01, 0.005, 0.001, 0.0005];
+
+/// A `SequentialResult` describes a run of `sequential_stopping` - the
+/// batch means, and the confidence interval achieved on their mean.
+#[derive(Debug, Clone)]
+pub struct SequentialResult {
+    /// The metric of each batch, in batch order
+    pub batch_means: Vec<f64>,
+    /// The point estimate of the metric mean, across batches
+    pub mean: f64,
+    /// The achieved confidence interval half-width
+    pub half_width: f64,
+    /// Whether the target half-width was reached, before the batch cap
+    pub converged: bool,
+}
+
+impl Simulation {
+    /// This method runs `n` independent replications of the simulation until
+    /// a global time, and returns a metric of each replication, in
+    /// replication order.  Each replication is a fresh copy of the simulation
+    /// configuration and state, with a random number generator for its own
+    /// substream, derived from the simulation seed by the simulation
+    /// `SubstreamScheme`.  The copies are made through the
+    /// serialized simulation, so configuration that is not serialized - such
+    /// as model-specific random number generators - is not carried over.
+    pub fn run_replications<F>(
+        &self,
+        n: usize,
+        until: f64,
+        collector: F,
+    ) -> Result<Vec<f64>, SimulationError>
+    where
+        F: Fn(&Simulation, &[Message]) -> f64,
+    {
+        let json = serde_json::to_string(self)?;
+        let (seed, scheme) = (self.get_seed(), self.substream_scheme);
+        (0..n)
+            .map(|replication| run_replication(&json, seed, scheme, replication, until, &collector))
+            .collect()
+    }
+
+    /// This method runs replications as with `run_replications`, but across
+    /// threads, one per available core.  The results are identical to those
+    /// of `run_replications`.  Parallel replications are not supported for
+    /// WASM targets.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn run_replications_parallel<F>(
+        &self,
+        n: usize,
+        until: f64,
+        collector: F,
+    ) -> Result<Vec<f64>, SimulationError>
+    where
+        F: Fn(&Simulation, &[Message]) -> f64 + Sync,
+    {
+        let json = serde_json::to_string(self)?;
+        let (seed, scheme) = (self.get_seed(), self.substream_scheme);
+        let workers = std::thread::available_parallelism()
+            .map_or(1, |parallelism| parallelism.get())
+            .min(n.max(1));
+        // Replications are assigned to the workers round robin
+        let worker_results: Vec<Result<Vec<(usize, f64)>, SimulationError>> =
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = (0..workers)
+                    .map(|worker| {
+                        let (json, collector) = (&json, &collector);
+                        scope.spawn(move || {
+                            (worker..n)
+                                .step_by(workers)
+                                .map(|replication| {
+                                    run_replication(
+                                        json,
+                                        seed,
+                                        scheme,
+                                        replication,
+                                        until,
+                                        collector,
+                                    )
+                                    .map(|metric| (replication, metric))
+                                })
+                                .collect()
+                        })
+                    })
+                    .collect();
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().expect("a replication thread panicked"))
+                    .collect()
+            });
+        let mut results = vec![0.0; n];
+        for worker_result in worker_results {
+            for (replication, metric) in worker_result? {
+                results[replication] = metric;
+            }
+        }
+        Ok(results)
+    }
+
+    /// This method selects the run length for a target precision, by batch
+    /// means.  The simulation is run in batches of `batch_until` time units,
+    /// with the metric of each batch collected from the batch messages,
+    /// until the confidence interval half-width on the mean batch metric is
+    /// at most `target_halfwidth`, or `max_batches` batches have run.  At
+    /// least two batches are always run, as a confidence interval requires
+    /// two.  The confidence level must be one with a tabulated t score -
+    /// 0.8, 0.9, 0.95, 0.98, 0.99, 0.998, or 0.999.  Batches are assumed to
+    /// be long enough for their metrics to be approximately independent.
+    pub fn sequential_stopping<F>(
+        &mut self,
+        collector: F,
+        target_halfwidth: f64,
+        confidence: f64,
+        batch_until: f64,
+        max_batches: usize,
+    ) -> Result<SequentialResult, SimulationError>
+    where
+        F: Fn(&Simulation, &[Message]) -> f64,
+    {
+        let alpha = SUPPORTED_ALPHAS
+            .iter()
+            .copied()
+            .find(|alpha| ((1.0 - confidence) / 2.0 - alpha).abs() < 1e-9)
+            .ok_or_else(|| {
+                SimulationError::InvalidStoppingRule(format![
+                    "no t score is tabulated for confidence {}",
+                    confidence
+                ])
+            })?;
+        if target_halfwidth.is_nan()
+            || target_halfwidth <= 0.0
+            || batch_until.is_nan()
+            || batch_until <= 0.0
+            || max_batches < 2
+        {
+            return Err(SimulationError::InvalidStoppingRule(format![
+                "a positive target half-width {}, a positive batch length {}, and a cap of at least two batches {} are required",
+                target_halfwidth, batch_until, max_batches
+            ]));
+        }
+        let mut batch_means: Vec<f64> = Vec::new();
+        loop {
+            let until = self.get_global_time() + batch_until;
+            let messages = self.step_until(until)?;
+            batch_means.push(collector(self, &messages));
+            if batch_means.len() < 2 {
+                continue;
+            }
+            let sample = IndependentSample::post(batch_means.clone())?;
+            let half_width = sample.confidence_interval_mean(alpha)?.half_width();
+            let converged = half_width <= target_halfwidth;
+            if converged || batch_means.len() >= max_batches {
+                return Ok(SequentialResult {
+                    mean: sample.point_estimate_mean(),
+                    batch_means,
+                    half_width,
+                    converged,
+                });
+            }
+        }
+    }
+}
+
+fn run_replication<F>(
+    json: &str,
+    seed: u64,
+    scheme: SubstreamScheme,
+    replication: usize,
+    until: f64,
+    collector: &F,
+) -> Result<f64, SimulationError>
+where
+    F: Fn(&Simulation, &[Message]) -> f64,
+{
+    let mut simulation: Simulation = serde_json::from_str(json)?;
+    simulation.set_rng(scheme.rng(seed, replication as u64));
+    let messages = simulation.step_until(until)?;
+    Ok(collector(&simulation, &messages))
+}
@@ -0,0 +1,383 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Message, Simulation};
+
+/// A `TopologyGraph` describes the simulation topology - models as nodes,
+/// and connectors as edges - for rendering by external tools.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TopologyGraph {
+    pub nodes: Vec<TopologyNode>,
+    pub edges: Vec<TopologyEdge>,
+}
+
+/// A model in a `TopologyGraph`, identified by the model ID.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopologyNode {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub model_type: String,
+}
+
+/// A connector in a `TopologyGraph`, identified by the connector ID.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopologyEdge {
+    pub id: String,
+    pub source_id: String,
+    pub source_port: String,
+    pub target_id: String,
+    pub target_port: String,
+}
+
+/// This function counts the messages with each distinct content, such as
+/// the messages from `step_until`, for job mix and routing fairness
+/// analysis.
+pub fn content_histogram(messages: &[Message]) -> HashMap<String, u64> {
+    let mut histogram: HashMap<String, u64> = HashMap::new();
+    messages.iter().for_each(|message| {
+        *histogram.entry(message.content().to_string()).or_insert(0) += 1;
+    });
+    histogram
+}
+
+/// This function counts the messages routed between each source and target
+/// model pair, such as the messages from `step_until`, as a compact textual
+/// alternative to the topology diagram.  Flows are sorted by descending
+/// count, with ties in source and target order.
+pub fn flow_summary(messages: &[Message]) -> Vec<(String, String, u64)> {
+    let mut counts: HashMap<(&str, &str), u64> = HashMap::new();
+    messages.iter().for_each(|message| {
+        *counts
+            .entry((message.source_id(), message.target_id()))
+            .or_insert(0) += 1;
+    });
+    let mut flows: Vec<(String, String, u64)> = counts
+        .into_iter()
+        .map(|((source_id, target_id), count)| {
+            (source_id.to_string(), target_id.to_string(), count)
+        })
+        .collect();
+    flows.sort_by(|a, b| {
+        b.2.cmp(&a.2)
+            .then_with(|| a.0.cmp(&b.0))
+            .then_with(|| a.1.cmp(&b.1))
+    });
+    flows
+}
+
+/// This function computes a fingerprint of a run, from the ordered
+/// sequence of messages, such as the messages from `step_until` - the
+/// source and target models and ports, the time, and the content of each
+/// message.  The fingerprint is a 64-bit FNV-1a hash, which is stable
+/// across platforms and compiler versions, for golden-fingerprint
+/// regression tests.
+pub fn run_fingerprint(messages: &[Message]) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+    let hash_bytes = |hash: u64, bytes: &[u8]| {
+        bytes.iter().fold(hash, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+        })
+    };
+    // Fields are length-prefixed, so that adjacent fields cannot run
+    // together
+    let hash_field = |hash: u64, field: &str| {
+        hash_bytes(
+            hash_bytes(hash, &(field.len() as u64).to_le_bytes()),
+            field.as_bytes(),
+        )
+    };
+    messages.iter().fold(FNV_OFFSET_BASIS, |hash, message| {
+        let hash = hash_field(hash, message.source_id());
+        let hash = hash_field(hash, message.source_port());
+        let hash = hash_field(hash, message.target_id());
+        let hash = hash_field(hash, message.target_port());
+        let hash = hash_bytes(hash, &message.time().to_bits().to_le_bytes());
+        hash_field(hash, message.content())
+    })
+}
+
+/// This function exports messages, such as the messages from `step_until`,
+/// as an XES event log for process mining tools.  Messages are grouped into
+/// traces by the case identifier extracted from each message - typically,
+/// a job identifier in the message content.  Each message is an event, with
+/// the source port as the activity, the source model as the resource, and
+/// the message time as the timestamp, in seconds from the Unix epoch.
+pub fn export_xes(messages: &[Message], case_id: impl Fn(&Message) -> String) -> String {
+    let mut traces: Vec<(String, Vec<&Message>)> = Vec::new();
+    messages.iter().for_each(|message| {
+        let case = case_id(message);
+        match traces
+            .iter_mut()
+            .find(|(trace_case, _)| *trace_case == case)
+        {
+            Some((_, events)) => events.push(message),
+            None => traces.push((case, vec![message])),
+        }
+    });
+    let mut xes = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xes.push_str("<log xes.version=\"1.0\" xmlns=\"http://www.xes-standard.org/\">\n");
+    xes.push_str("  <extension name=\"Concept\" prefix=\"concept\" uri=\"http://www.xes-standard.org/concept.xesext\"/>\n");
+    xes.push_str("  <extension name=\"Organizational\" prefix=\"org\" uri=\"http://www.xes-standard.org/org.xesext\"/>\n");
+    xes.push_str("  <extension name=\"Time\" prefix=\"time\" uri=\"http://www.xes-standard.org/time.xesext\"/>\n");
+    traces.iter().for_each(|(case, events)| {
+        xes.push_str("  <trace>\n");
+        xes.push_str(&format![
+            "    <string key=\"concept:name\" value=\"{}\"/>\n",
+            escape_xml(case)
+        ]);
+        events.iter().for_each(|message| {
+            xes.push_str("    <event>\n");
+            xes.push_str(&format![
+                "      <string key=\"concept:name\" value=\"{}\"/>\n",
+                escape_xml(message.source_port())
+            ]);
+            xes.push_str(&format![
+                "      <string key=\"org:resource\" value=\"{}\"/>\n",
+                escape_xml(message.source_id())
+            ]);
+            xes.push_str(&format![
+                "      <date key=\"time:timestamp\" value=\"{}\"/>\n",
+                xes_timestamp(*message.time())
+            ]);
+            xes.push_str("    </event>\n");
+        });
+        xes.push_str("  </trace>\n");
+    });
+    xes.push_str("</log>\n");
+    xes
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Escape a model, connector, or port name for a quoted DOT ID or label.
+fn escape_dot(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Format a simulation time, in seconds from the Unix epoch, as an
+/// ISO 8601 UTC timestamp with millisecond precision.
+fn xes_timestamp(time: f64) -> String {
+    let milliseconds = (time * 1000.0).round() as i64;
+    let (days, day_milliseconds) = (
+        milliseconds.div_euclid(86_400_000),
+        milliseconds.rem_euclid(86_400_000),
+    );
+    // Civil date from days since the epoch, per Howard Hinnant's algorithm
+    let shifted_days = days + 719_468;
+    let era = shifted_days.div_euclid(146_097);
+    let day_of_era = shifted_days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format![
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        day_milliseconds / 3_600_000,
+        day_milliseconds / 60_000 % 60,
+        day_milliseconds / 1000 % 60,
+        day_milliseconds % 1000
+    ]
+}
+
+/// The `Report` trait provides exports of a simulation for visualization
+/// and analysis.  Each export is available as a writer-based method, for
+/// streaming to a file or socket, and as a `String`-returning method that
+/// delegates to the writer-based method.
+pub trait Report {
+    /// Write the simulation topology as a DOT graph - models as nodes and
+    /// connectors as edges, labeled with the connector ID.
+    fn write_dot_graph<W: Write>(&self, writer: &mut W) -> std::io::Result<()>;
+
+    /// Generate the simulation topology as a DOT graph.
+    fn generate_dot_graph(&self) -> String {
+        let mut buffer = Vec::new();
+        // Writes to a Vec<u8> are infallible
+        self.write_dot_graph(&mut buffer)
+            .expect("writing to an in-memory buffer failed");
+        String::from_utf8_lossy(&buffer).into_owned()
+    }
+
+    /// Write the simulation topology as a DOT graph, with parallel
+    /// connectors between the same source and target models collapsed into
+    /// a single edge.  The edge label lists each connector ID and its ports,
+    /// one per line, for readable diagrams of dense topologies.
+    fn write_dot_graph_merged<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let graph = self.topology_graph();
+        // Edges are grouped by model pair, in order of first appearance
+        let mut merged_edges: Vec<((&str, &str), Vec<String>)> = Vec::new();
+        for edge in &graph.edges {
+            let label = format!(
+                "{} ({} -> {})",
+                escape_dot(&edge.id),
+                escape_dot(&edge.source_port),
+                escape_dot(&edge.target_port)
+            );
+            let pair = (edge.source_id.as_str(), edge.target_id.as_str());
+            match merged_edges
+                .iter_mut()
+                .find(|(edge_pair, _)| *edge_pair == pair)
+            {
+                Some((_, labels)) => labels.push(label),
+                None => merged_edges.push((pair, vec![label])),
+            }
+        }
+        writeln!(writer, "digraph simulation {{")?;
+        for node in &graph.nodes {
+            writeln!(writer, "    \"{}\";", escape_dot(&node.id))?;
+        }
+        for ((source_id, target_id), labels) in merged_edges {
+            writeln!(
+                writer,
+                "    \"{}\" -> \"{}\" [label=\"{}\"];",
+                escape_dot(source_id),
+                escape_dot(target_id),
+                labels.join("\\n")
+            )?;
+        }
+        writeln!(writer, "}}")
+    }
+
+    /// Generate the simulation topology as a DOT graph, with parallel
+    /// connectors collapsed into a single edge.
+    fn generate_dot_graph_merged(&self) -> String {
+        let mut buffer = Vec::new();
+        // Writes to a Vec<u8> are infallible
+        self.write_dot_graph_merged(&mut buffer)
+            .expect("writing to an in-memory buffer failed");
+        String::from_utf8_lossy(&buffer).into_owned()
+    }
+
+    /// Write the simulation topology as a DOT graph, with each edge labeled
+    /// with the connector ID and its source and target ports, so that port
+    /// mismatches are visible in the diagram.
+    fn write_dot_graph_with_ports<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let graph = self.topology_graph();
+        writeln!(writer, "digraph simulation {{")?;
+        for node in &graph.nodes {
+            writeln!(writer, "    \"{}\";", escape_dot(&node.id))?;
+        }
+        for edge in &graph.edges {
+            writeln!(
+                writer,
+                "    \"{}\" -> \"{}\" [label=\"{} ({} -> {})\"];",
+                escape_dot(&edge.source_id),
+                escape_dot(&edge.target_id),
+                escape_dot(&edge.id),
+                escape_dot(&edge.source_port),
+                escape_dot(&edge.target_port)
+            )?;
+        }
+        writeln!(writer, "}}")
+    }
+
+    /// Generate the simulation topology as a DOT graph, with port-labeled
+    /// edges.
+    fn generate_dot_graph_with_ports(&self) -> String {
+        let mut buffer = Vec::new();
+        // Writes to a Vec<u8> are infallible
+        self.write_dot_graph_with_ports(&mut buffer)
+            .expect("writing to an in-memory buffer failed");
+        String::from_utf8_lossy(&buffer).into_owned()
+    }
+
+    /// Build the simulation topology as a `TopologyGraph`.
+    fn topology_graph(&self) -> TopologyGraph;
+
+    /// Write the simulation topology as a JSON graph, with `nodes` and
+    /// `edges` arrays.
+    fn write_json_graph<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        serde_json::to_writer(writer, &self.topology_graph()).map_err(std::io::Error::from)
+    }
+
+    /// Generate the simulation topology as a JSON graph.
+    fn generate_json_graph(&self) -> String {
+        let mut buffer = Vec::new();
+        // Writes to a Vec<u8> are infallible
+        self.write_json_graph(&mut buffer)
+            .expect("writing to an in-memory buffer failed");
+        String::from_utf8_lossy(&buffer).into_owned()
+    }
+}
+
+impl Report for Simulation {
+    fn write_dot_graph<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writeln!(writer, "digraph simulation {{")?;
+        for model in &self.models {
+            writeln!(writer, "    \"{}\";", escape_dot(model.id()))?;
+        }
+        for connector in &self.connectors {
+            writeln!(
+                writer,
+                "    \"{}\" -> \"{}\" [label=\"{}\"];",
+                escape_dot(connector.source_id()),
+                escape_dot(connector.target_id()),
+                escape_dot(connector.id())
+            )?;
+        }
+        writeln!(writer, "}}")
+    }
+
+    fn topology_graph(&self) -> TopologyGraph {
+        TopologyGraph {
+            nodes: self
+                .models
+                .iter()
+                .map(|model| TopologyNode {
+                    id: model.id().to_string(),
+                    model_type: model.model_type().to_string(),
+                })
+                .collect(),
+            edges: self
+                .connectors
+                .iter()
+                .map(|connector| TopologyEdge {
+                    id: connector.id().to_string(),
+                    source_id: connector.source_id().to_string(),
+                    source_port: connector.source_port().to_string(),
+                    target_id: connector.target_id().to_string(),
+                    target_port: connector.target_port().to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Simulation {
+    /// This method traces a job through the simulation, from messages such
+    /// as those from `step_until` - every message with the given content,
+    /// in time order, giving the path of the job through the topology.
+    /// Jobs are identified by content, so traces are per-job where job
+    /// contents are unique, as with the default generator content or a
+    /// `{seq}` content template.  Messages with equal times retain their
+    /// collection order.
+    pub fn trace_content<'a>(&self, content: &str, messages: &'a [Message]) -> Vec<&'a Message> {
+        let mut trace: Vec<&Message> = messages
+            .iter()
+            .filter(|message| message.content() == content)
+            .collect();
+        trace.sort_by(|a, b| a.time().total_cmp(b.time()));
+        trace
+    }
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use super::time::{Clock, TimeRepresentation};
 use crate::input_modeling::dynamic_rng::{default_rng, DynRng};
+use crate::utils::errors::SimulationError;
 
 /// The simulator provides a uniform random number generator and simulation
 /// clock to models during the execution of a simulation
@@ -9,28 +11,65 @@ use crate::input_modeling::dynamic_rng::{default_rng, DynRng};
 pub struct Services {
     #[serde(skip, default = "default_rng")]
     pub(crate) global_rng: DynRng,
-    pub(crate) global_time: f64,
+    #[serde(skip)]
+    pub(crate) model_rng: Option<DynRng>,
+    #[serde(skip)]
+    pub(crate) observation: Option<f64>,
+    pub(crate) global_time: Clock,
 }
 
 impl Default for Services {
     fn default() -> Self {
         Self {
             global_rng: default_rng(),
-            global_time: 0.0,
+            model_rng: None,
+            observation: None,
+            global_time: Clock::default(),
         }
     }
 }
 
 impl Services {
+    /// The random number generator for the model currently handling an
+    /// event - the model-specific generator, where one is set on the
+    /// simulation, and otherwise the global generator.
     pub fn global_rng(&self) -> DynRng {
-        self.global_rng.clone()
+        match &self.model_rng {
+            Some(rng) => rng.clone(),
+            None => self.global_rng.clone(),
+        }
+    }
+
+    /// The observed metric value for the model currently handling an
+    /// internal event, where the model reports an observation target.
+    pub fn observation(&self) -> Option<f64> {
+        self.observation
     }
 
     pub fn global_time(&self) -> f64 {
-        self.global_time
+        self.global_time.time()
     }
 
     pub fn set_global_time(&mut self, time: f64) {
-        self.global_time = time;
+        self.global_time.set(time);
+    }
+
+    /// Advance the global time by a time advance, with the arithmetic of
+    /// the clock representation.
+    pub fn advance_global_time(&mut self, delta: f64) -> Result<(), SimulationError> {
+        self.global_time.advance(delta)
+    }
+
+    /// The representation of the simulation clock.
+    pub fn time_representation(&self) -> TimeRepresentation {
+        self.global_time.representation()
+    }
+
+    /// Switch the simulation clock representation, converting the current
+    /// global time.
+    pub fn set_time_representation(&mut self, representation: TimeRepresentation) {
+        let time = self.global_time();
+        self.global_time = Clock::new(representation);
+        self.global_time.set(time);
     }
 }
@@ -0,0 +1,85 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs::File;
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::{BufWriter, Write};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+
+use super::{Message, Simulation};
+use crate::utils::errors::SimulationError;
+
+/// A `MessageSink` consumes the messages of each simulation step, as they
+/// are produced, so that long runs can stream messages to disk or over a
+/// network instead of accumulating them in memory.
+pub trait MessageSink {
+    /// Consume the messages produced by one simulation step.
+    fn consume(&mut self, messages: &[Message]) -> Result<(), SimulationError>;
+}
+
+/// A `CsvFileSink` writes messages to a CSV file, with one message per
+/// row, in the column order
+/// `time,source_id,source_port,target_id,target_port,content`.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct CsvFileSink {
+    writer: BufWriter<File>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl CsvFileSink {
+    /// Create (or truncate) the CSV file at the given path, and write the
+    /// header row.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, SimulationError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(
+            writer,
+            "time,source_id,source_port,target_id,target_port,content"
+        )?;
+        Ok(Self { writer })
+    }
+
+    /// Flush any buffered rows to the file.  Buffered rows are also flushed
+    /// when the sink is dropped, but errors are then ignored.
+    pub fn flush(&mut self) -> Result<(), SimulationError> {
+        Ok(self.writer.flush()?)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl MessageSink for CsvFileSink {
+    fn consume(&mut self, messages: &[Message]) -> Result<(), SimulationError> {
+        messages.iter().try_for_each(|message| {
+            writeln!(
+                self.writer,
+                "{},{},{},{},{},{}",
+                message.time(),
+                super::csv_field(message.source_id()),
+                super::csv_field(message.source_port()),
+                super::csv_field(message.target_id()),
+                super::csv_field(message.target_port()),
+                super::csv_field(message.content())
+            )
+        })?;
+        Ok(())
+    }
+}
+
+impl Simulation {
+    /// This method executes simulation `step` calls, until a global time
+    /// has been exceeded, as with `step_until`.  Rather than accumulating
+    /// the messages of the run, the messages of each step are pushed to the
+    /// sink, so memory use is independent of the run length.
+    pub fn step_streaming(
+        &mut self,
+        until: f64,
+        sink: &mut dyn MessageSink,
+    ) -> Result<(), SimulationError> {
+        loop {
+            self.step()?;
+            if !self.is_paused() && self.get_global_time() < until {
+                sink.consume(self.get_messages())?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+}
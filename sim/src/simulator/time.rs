@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::errors::SimulationError;
+
+/// The number of fixed-point ticks in one unit of simulation time.
+pub const TICKS_PER_UNIT: i64 = 1_000_000_000;
+
+/// The bound of the finite times representable by a `FixedTime`, about
+/// 9.2e9 time units.
+pub const MAX_FIXED_TIME: f64 = i64::MAX as f64 / TICKS_PER_UNIT as f64;
+
+/// The `SimTime` trait captures the arithmetic of a simulation clock
+/// representation.  Model time advances remain `f64` values, and each
+/// representation determines how those advances accumulate into the clock.
+pub trait SimTime: Copy + PartialOrd + std::fmt::Debug {
+    /// The time at the start of a simulation.
+    fn zero() -> Self;
+
+    /// The representation of a floating-point time.
+    fn from_f64(time: f64) -> Self;
+
+    /// The floating-point value of the time.
+    fn to_f64(self) -> f64;
+
+    /// The time following a floating-point time advance.
+    fn advance(self, delta: f64) -> Self;
+}
+
+impl SimTime for f64 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn from_f64(time: f64) -> Self {
+        time
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn advance(self, delta: f64) -> Self {
+        self + delta
+    }
+}
+
+/// A `FixedTime` is a fixed-point time, counted in integer ticks of
+/// `1 / TICKS_PER_UNIT` time units.  Each time advance is rounded to the
+/// nearest tick, so the clock accumulates no floating-point error over long
+/// runs.  Finite times are representable below `MAX_FIXED_TIME` in
+/// magnitude.  Infinite times, and finite times beyond the range, saturate
+/// the tick count, which represents an infinite time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct FixedTime {
+    ticks: i64,
+}
+
+impl FixedTime {
+    /// A fixed-point time from an integer count of ticks.
+    pub fn from_ticks(ticks: i64) -> Self {
+        Self { ticks }
+    }
+
+    /// The integer count of ticks in the time.
+    pub fn ticks(&self) -> i64 {
+        self.ticks
+    }
+
+    /// The time following a floating-point time advance, or `None` if a
+    /// finite time advanced by a finite advance is beyond the representable
+    /// range.
+    pub fn checked_advance(self, delta: f64) -> Option<Self> {
+        let finite = self.ticks != i64::MAX && self.ticks != i64::MIN;
+        if !finite || !delta.is_finite() {
+            return Some(self.advance(delta));
+        }
+        if delta.abs() >= MAX_FIXED_TIME {
+            return None;
+        }
+        self.ticks
+            .checked_add(Self::ticks_of(delta))
+            .filter(|ticks| *ticks != i64::MAX && *ticks != i64::MIN)
+            .map(Self::from_ticks)
+    }
+
+    fn ticks_of(time: f64) -> i64 {
+        // Float-to-integer casts saturate, so infinite times map to the
+        // tick bounds
+        (time * TICKS_PER_UNIT as f64).round() as i64
+    }
+}
+
+impl SimTime for FixedTime {
+    fn zero() -> Self {
+        Self { ticks: 0 }
+    }
+
+    fn from_f64(time: f64) -> Self {
+        Self {
+            ticks: Self::ticks_of(time),
+        }
+    }
+
+    fn to_f64(self) -> f64 {
+        match self.ticks {
+            i64::MAX => f64::INFINITY,
+            i64::MIN => f64::NEG_INFINITY,
+            ticks => {
+                (ticks / TICKS_PER_UNIT) as f64
+                    + (ticks % TICKS_PER_UNIT) as f64 / TICKS_PER_UNIT as f64
+            }
+        }
+    }
+
+    fn advance(self, delta: f64) -> Self {
+        Self {
+            ticks: self.ticks.saturating_add(Self::ticks_of(delta)),
+        }
+    }
+}
+
+/// The `TimeRepresentation` selects the representation of the simulation
+/// clock.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TimeRepresentation {
+    /// A floating-point clock
+    #[default]
+    Float,
+    /// A fixed-point clock, for exact discrete time
+    Fixed,
+}
+
+/// The `Clock` holds the simulation global time, in the selected
+/// representation, and dispatches the clock arithmetic to the `SimTime`
+/// implementation of that representation.  A floating-point clock
+/// serializes as a bare number, for compatibility with simulations
+/// serialized before fixed-point clocks were available.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Clock {
+    Float(f64),
+    Fixed(FixedTime),
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::Float(f64::zero())
+    }
+}
+
+impl Clock {
+    /// A clock at time zero, in the given representation.
+    pub fn new(representation: TimeRepresentation) -> Self {
+        match representation {
+            TimeRepresentation::Float => Self::Float(f64::zero()),
+            TimeRepresentation::Fixed => Self::Fixed(FixedTime::zero()),
+        }
+    }
+
+    /// The representation of the clock.
+    pub fn representation(&self) -> TimeRepresentation {
+        match self {
+            Self::Float(_) => TimeRepresentation::Float,
+            Self::Fixed(_) => TimeRepresentation::Fixed,
+        }
+    }
+
+    /// The clock time, as a floating-point value.
+    pub fn time(&self) -> f64 {
+        match self {
+            Self::Float(time) => time.to_f64(),
+            Self::Fixed(time) => time.to_f64(),
+        }
+    }
+
+    /// Set the clock to a floating-point time, retaining the clock
+    /// representation.
+    pub fn set(&mut self, time: f64) {
+        *self = match self {
+            Self::Float(_) => Self::Float(f64::from_f64(time)),
+            Self::Fixed(_) => Self::Fixed(FixedTime::from_f64(time)),
+        };
+    }
+
+    /// Advance the clock by a floating-point time advance.  A fixed-point
+    /// clock advanced beyond its representable range is an error, and the
+    /// clock is left unchanged.
+    pub fn advance(&mut self, delta: f64) -> Result<(), SimulationError> {
+        *self = match self {
+            Self::Float(time) => Self::Float(time.advance(delta)),
+            Self::Fixed(time) => Self::Fixed(
+                time.checked_advance(delta)
+                    .ok_or(SimulationError::TimeOverflow)?,
+            ),
+        };
+        Ok(())
+    }
+}
@@ -4,6 +4,8 @@ use wasm_bindgen::prelude::*;
 
 use crate::utils::set_panic_hook;
 
+use super::Message;
+use super::Report;
 use super::Simulation as CoreSimulation;
 
 /// The web `Simulation` provides JS/WASM-compatible interfaces to the core
@@ -94,6 +96,28 @@ impl Simulation {
         serde_yaml::to_string(&self.simulation.get_messages()).unwrap()
     }
 
+    /// Seed the simulation random number generator, replacing it with the
+    /// default generator constructed from the seed, for reproducible runs.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.simulation.set_seed(seed);
+    }
+
+    /// Get the seed of the simulation random number generator.
+    pub fn get_seed(&self) -> u64 {
+        self.simulation.get_seed()
+    }
+
+    /// A JS/WASM interface for `Simulation.set_paused`, which freezes the
+    /// simulation for a UI pause, so that steps are no-ops until resumed.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.simulation.set_paused(paused);
+    }
+
+    /// A JS/WASM interface for `Simulation.is_paused`.
+    pub fn is_paused(&self) -> bool {
+        self.simulation.is_paused()
+    }
+
     /// An interface to `Simulation.get_global_time`.
     pub fn get_global_time(&self) -> f64 {
         self.simulation.get_global_time()
@@ -145,6 +169,32 @@ impl Simulation {
             .inject_input(serde_yaml::from_str(message).unwrap());
     }
 
+    /// A JS/WASM interface for `Simulation.inject_input`, which uses a
+    /// JavaScript object representation of the injected message.  Unlike
+    /// the other web `Simulation` methods, errors are thrown as JavaScript
+    /// errors - for an invalid message object, or a message targeting a
+    /// model that does not exist in the simulation.
+    pub fn inject_input_js(&mut self, message: JsValue) -> Result<(), JsValue> {
+        let message: Message = serde_wasm_bindgen::from_value(message)?;
+        self.simulation
+            .get_status(message.target_id())
+            .map_err(|err| js_sys::Error::new(&err.to_string()))?;
+        self.simulation.inject_input(message);
+        Ok(())
+    }
+
+    /// A JS/WASM interface for `Report.generate_dot_graph`, providing the
+    /// simulation topology as a DOT graph.
+    pub fn to_dot(&self) -> String {
+        self.simulation.generate_dot_graph()
+    }
+
+    /// A JS/WASM interface for `Report.topology_graph`, which converts the
+    /// topology graph to a JavaScript object with `nodes` and `edges`.
+    pub fn to_graph_json(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.simulation.topology_graph()).unwrap()
+    }
+
     /// A JS/WASM interface for `Simulation.step`, which converts the
     /// returned messages to a JavaScript Array.
     pub fn step_js(&mut self) -> Array {
@@ -7,14 +7,60 @@ pub enum SimulationError {
     #[error("An invalid model configuration was encountered during simulation")]
     InvalidModelConfiguration,
 
+    /// Represents a model reporting a NaN time until its next event, naming the model, since
+    /// the simulation cannot determine the next event time
+    #[error("Model {0} reported a non-finite (NaN) time until its next event")]
+    InvalidTimeAdvance(String),
+
+    /// Represents a simulation configuration failing the default checks,
+    /// with the report of the failures
+    #[error("The simulation configuration failed its checks:\n{0}")]
+    ConfigurationCheckFailed(String),
+
     /// Represents an operation requested on a model that does not exist
     #[error("A specified model cannot be found in the simulation")]
     ModelNotFound,
 
+    /// Represents multiple models configured with the same model ID
+    #[error("Multiple models in the simulation share the same model ID")]
+    DuplicateModelId,
+
+    /// Represents a model that cannot be reached from any source model
+    #[error("Model {0} is not reachable from any source model")]
+    UnreachableModel(String),
+
+    /// Represents a cycle of connectors, through the named model
+    #[error("Model {0} is part of a connector cycle")]
+    CyclicConnectors(String),
+
     /// Represents an operation requested on a model port that does not exist
     #[error("A specified model port cannot be found in the simulation")]
     PortNotFound,
 
+    /// Represents a message delivered to a port that the target model does not recognize
+    #[error("Model {0} does not recognize input port {1}")]
+    UnrecognizedPort(String, String),
+
+    /// Represents a message built without a required field, or with an empty field
+    #[error("A message cannot be built without the {0}")]
+    IncompleteMessage(String),
+
+    /// Represents a malformed row in an imported CSV file
+    #[error("CSV row {0} is malformed: {1}")]
+    MalformedCsvRow(usize, String),
+
+    /// Represents active messages in excess of the configured limit, naming the flooded target model
+    #[error("The active message limit was exceeded, with messages flooding model {0}")]
+    MessageLimitExceeded(String),
+
+    /// Represents a deadlocked simulation, naming the models involved
+    #[error("The simulation is deadlocked, with messages held between models {0}")]
+    Deadlock(String),
+
+    /// Represents a simulation that has processed its full budget of internal events
+    #[error("The event budget of {0} internal events was exhausted")]
+    EventBudgetExceeded(u64),
+
     /// Represents a failed clone operation on a model
     #[error("A model failed to clone during simulation")]
     ModelCloneError,
@@ -43,6 +89,30 @@ pub enum SimulationError {
     #[error("An internal logic error occured, where prerequisite calculations were not executed")]
     PrerequisiteCalcError,
 
+    /// Represents a random variable configured with parameters outside of the distribution domain
+    #[error("A random variable distribution was configured with an invalid parameter")]
+    InvalidDistributionParameter,
+
+    /// Represents a random variable parameter outside of the distribution domain, with a description
+    #[error("Invalid random variable: {0}")]
+    InvalidRandomVariable(String),
+
+    /// Represents a queueing system without a steady state, with the given utilization
+    #[error("A queue with utilization {0} is unstable, and has no steady state")]
+    UnstableQueue(f64),
+
+    /// Represents an input scheduled for delivery before the current global time
+    #[error("An input cannot be scheduled before the current global time")]
+    InvalidScheduleTime,
+
+    /// Represents a message timestamped before the current global time, naming the message
+    #[error("The message {0} is timestamped before the current global time")]
+    CausalityViolation(String),
+
+    /// Represents a real-time step requested with an invalid time scale
+    #[error("A real-time step requires a positive, finite time scale")]
+    InvalidTimeScale,
+
     /// Represents a failed conversion to num-traits Float
     #[error("Failed to convert to a Float value")]
     FloatConvError,
@@ -51,10 +121,30 @@ pub enum SimulationError {
     #[error("A message was unexpectedly lost, dropped, or stuck during simulation execution")]
     DroppedMessageError,
 
+    /// Represents a step back requested without a retained simulation snapshot
+    #[error("No simulation snapshot is available to step back to")]
+    SnapshotNotFound,
+
+    /// Represents a sequential stopping rule configured with an invalid parameter, with a description
+    #[error("Invalid sequential stopping rule: {0}")]
+    InvalidStoppingRule(String),
+
+    /// Represents a fixed-point clock advanced beyond its representable range
+    #[error("The simulation time exceeds the range of the fixed-point clock")]
+    TimeOverflow,
+
     /// Transparent serde_json errors
     #[error(transparent)]
     JSONError(#[from] serde_json::error::Error),
 
+    /// Transparent serde_yaml errors
+    #[error(transparent)]
+    YAMLError(#[from] serde_yaml::Error),
+
+    /// Transparent I/O errors
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+
     /// Transparent Beta distribution errors
     #[error(transparent)]
     BetaError(#[from] rand_distr::BetaError),
@@ -5,6 +5,8 @@
 
 pub mod errors;
 
+use serde::{Deserialize, Deserializer};
+
 use errors::SimulationError;
 
 /// The function evaluates a polynomial at a single value, with coefficients
@@ -79,6 +81,14 @@ pub fn set_panic_hook() {
     console_error_panic_hook::set_once();
 }
 
+/// JSON has no representation of infinite values, so serde_json serializes
+/// them as `null`.  This deserializer maps `null` back to infinity, enabling
+/// JSON round trips of model states with no scheduled events (an infinite
+/// `until_next_event`).
+pub fn deserialize_infinity<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or(f64::INFINITY))
+}
+
 /// Integer square root calculation, using the Babylonian square-root
 /// algorithm.
 pub fn usize_sqrt(n: usize) -> usize {
@@ -21,6 +21,7 @@ fn closure_under_coupling() -> Result<(), SimulationError> {
                 String::from("job"),
                 false,
                 None,
+                None,
             )),
         ),
         Model::new(
@@ -75,6 +76,7 @@ fn closure_under_coupling() -> Result<(), SimulationError> {
                             String::from("job"),
                             false,
                             None,
+                            None,
                         )),
                     ),
                     Model::new(
@@ -205,3 +207,111 @@ fn closure_under_coupling() -> Result<(), SimulationError> {
     ];
     Ok(())
 }
+
+#[test]
+fn coupled_internal_messages_follow_parent_clock() -> Result<(), SimulationError> {
+    let models = vec![
+        Model::new(
+            String::from("coupled-01"),
+            Box::new(Coupled::new(
+                Vec::new(),
+                vec![String::from("start"), String::from("stop")],
+                vec![
+                    Model::new(
+                        String::from("generator-01"),
+                        Box::new(Generator::new(
+                            ContinuousRandomVariable::Uniform {
+                                min: 10.0,
+                                max: 10.000001,
+                            },
+                            None,
+                            String::from("job"),
+                            false,
+                            None,
+                            None,
+                        )),
+                    ),
+                    Model::new(
+                        String::from("processor-01"),
+                        Box::new(Processor::new(
+                            ContinuousRandomVariable::Uniform {
+                                min: 2.0,
+                                max: 2.000001,
+                            },
+                            Some(14),
+                            String::from("job"),
+                            String::from("processed"),
+                            false,
+                            None,
+                        )),
+                    ),
+                ],
+                Vec::new(),
+                vec![
+                    ExternalOutputCoupling {
+                        source_id: String::from("generator-01"),
+                        source_port: String::from("job"),
+                        target_port: String::from("start"),
+                    },
+                    ExternalOutputCoupling {
+                        source_id: String::from("processor-01"),
+                        source_port: String::from("processed"),
+                        target_port: String::from("stop"),
+                    },
+                ],
+                vec![InternalCoupling {
+                    source_id: String::from("generator-01"),
+                    target_id: String::from("processor-01"),
+                    source_port: String::from("job"),
+                    target_port: String::from("job"),
+                }],
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = vec![
+        Connector::new(
+            String::from("connector-01"),
+            String::from("coupled-01"),
+            String::from("storage-01"),
+            String::from("start"),
+            String::from("store"),
+        ),
+        Connector::new(
+            String::from("connector-02"),
+            String::from("coupled-01"),
+            String::from("storage-01"),
+            String::from("stop"),
+            String::from("store"),
+        ),
+    ];
+    let mut simulation = Simulation::post(models, connectors);
+    let message_records = simulation.step_until(100.0)?;
+    let departures: Vec<&Message> = message_records
+        .iter()
+        .filter(|message_record| message_record.source_port() == "stop")
+        .collect();
+    assert!(departures.len() >= 5);
+    departures
+        .iter()
+        .try_for_each(|departure| -> Result<(), SimulationError> {
+            let arrival = message_records
+                .iter()
+                .filter(|message_record| message_record.source_port() == "start")
+                .find(|arrival| {
+                    get_message_number(arrival.content()) == get_message_number(departure.content())
+                })
+                .ok_or(SimulationError::DroppedMessageError)?;
+            assert_eq!(arrival.target_id(), "storage-01");
+            assert!((departure.time() - arrival.time() - 2.0).abs() < 0.001);
+            Ok(())
+        })
+}
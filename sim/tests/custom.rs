@@ -1,9 +1,10 @@
+use std::collections::HashMap;
 use std::f64::INFINITY;
 
 use serde::{Deserialize, Serialize};
 use sim::input_modeling::ContinuousRandomVariable;
 use sim::models::model_trait::{DevsModel, Reportable, ReportableModel, SerializableModel};
-use sim::models::{Generator, Model, ModelMessage, ModelRecord};
+use sim::models::{model_config_schema, Generator, Model, ModelMessage, ModelRecord, Processor};
 use sim::simulator::{Connector, Message, Services, Simulation, WebSimulation};
 use sim::utils::errors::SimulationError;
 use sim_derive::{register, SerializableModel};
@@ -85,6 +86,303 @@ impl Reportable for Passive {
 
 impl ReportableModel for Passive {}
 
+/// The echo model immediately re-emits the content of received jobs
+#[derive(Debug, Clone, Serialize, Deserialize, SerializableModel)]
+#[serde(rename_all = "camelCase")]
+pub struct Echo {
+    ports_in: PortsIn,
+    ports_out: EchoPortsOut,
+    #[serde(default)]
+    state: State,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EchoPortsOut {
+    echo: String,
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl Echo {
+    pub fn new(job_port: String, echo_port: String) -> Self {
+        Self {
+            ports_in: PortsIn { job: job_port },
+            ports_out: EchoPortsOut { echo: echo_port },
+            state: State {
+                records: Vec::new(),
+            },
+        }
+    }
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl DevsModel for Echo {
+    fn events_ext(
+        &mut self,
+        _incoming_message: &ModelMessage,
+        _services: &mut Services,
+    ) -> Result<(), SimulationError> {
+        Ok(())
+    }
+
+    fn events_ext_with_output(
+        &mut self,
+        incoming_message: &ModelMessage,
+        _services: &mut Services,
+    ) -> Result<Vec<ModelMessage>, SimulationError> {
+        Ok(vec![ModelMessage {
+            port_name: self.ports_out.echo.clone(),
+            content: incoming_message.content.clone(),
+            attributes: HashMap::new(),
+        }])
+    }
+
+    fn events_int(
+        &mut self,
+        _services: &mut Services,
+    ) -> Result<Vec<ModelMessage>, SimulationError> {
+        Ok(Vec::new())
+    }
+
+    fn time_advance(&mut self, _time_delta: f64) {
+        // No future events list to advance
+    }
+
+    fn until_next_event(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+impl Reportable for Echo {
+    fn status(&self) -> String {
+        "Echoing".into()
+    }
+
+    fn records(&self) -> &Vec<ModelRecord> {
+        &self.state.records
+    }
+}
+
+impl ReportableModel for Echo {}
+
+/// The ticker model emits a tick at a fixed period
+#[derive(Debug, Clone, Serialize, Deserialize, SerializableModel)]
+#[serde(rename_all = "camelCase")]
+pub struct Ticker {
+    period: f64,
+    ports_out: TickerPortsOut,
+    #[serde(default)]
+    state: TickerState,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TickerPortsOut {
+    tick: String,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TickerState {
+    until_next_event: f64,
+    records: Vec<ModelRecord>,
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl Ticker {
+    pub fn new(period: f64, tick_port: String) -> Self {
+        Self {
+            period,
+            ports_out: TickerPortsOut { tick: tick_port },
+            state: TickerState {
+                until_next_event: period,
+                records: Vec::new(),
+            },
+        }
+    }
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl DevsModel for Ticker {
+    fn events_ext(
+        &mut self,
+        _incoming_message: &ModelMessage,
+        _services: &mut Services,
+    ) -> Result<(), SimulationError> {
+        Ok(())
+    }
+
+    fn events_int(
+        &mut self,
+        _services: &mut Services,
+    ) -> Result<Vec<ModelMessage>, SimulationError> {
+        self.state.until_next_event = self.period;
+        Ok(vec![ModelMessage {
+            port_name: self.ports_out.tick.clone(),
+            content: String::from("tick"),
+            attributes: HashMap::new(),
+        }])
+    }
+
+    fn time_advance(&mut self, time_delta: f64) {
+        self.state.until_next_event -= time_delta;
+    }
+
+    fn until_next_event(&self) -> f64 {
+        self.state.until_next_event
+    }
+}
+
+impl Reportable for Ticker {
+    fn status(&self) -> String {
+        "Ticking".into()
+    }
+
+    fn records(&self) -> &Vec<ModelRecord> {
+        &self.state.records
+    }
+}
+
+impl ReportableModel for Ticker {}
+
+/// The burst model emits a burst of numbered jobs on one port, once
+#[derive(Debug, Clone, Serialize, Deserialize, SerializableModel)]
+#[serde(rename_all = "camelCase")]
+pub struct Burst {
+    size: usize,
+    ports_out: TickerPortsOut,
+    #[serde(default)]
+    state: TickerState,
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl Burst {
+    pub fn new(size: usize, job_port: String) -> Self {
+        Self {
+            size,
+            ports_out: TickerPortsOut { tick: job_port },
+            state: TickerState {
+                until_next_event: 1.0,
+                records: Vec::new(),
+            },
+        }
+    }
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl DevsModel for Burst {
+    fn events_ext(
+        &mut self,
+        _incoming_message: &ModelMessage,
+        _services: &mut Services,
+    ) -> Result<(), SimulationError> {
+        Ok(())
+    }
+
+    fn events_int(
+        &mut self,
+        _services: &mut Services,
+    ) -> Result<Vec<ModelMessage>, SimulationError> {
+        self.state.until_next_event = f64::INFINITY;
+        Ok((1..=self.size)
+            .map(|job| ModelMessage {
+                port_name: self.ports_out.tick.clone(),
+                content: format!["job {}", job],
+                attributes: HashMap::new(),
+            })
+            .collect())
+    }
+
+    fn time_advance(&mut self, time_delta: f64) {
+        self.state.until_next_event -= time_delta;
+    }
+
+    fn until_next_event(&self) -> f64 {
+        self.state.until_next_event
+    }
+}
+
+impl Reportable for Burst {
+    fn status(&self) -> String {
+        "Bursting".into()
+    }
+
+    fn records(&self) -> &Vec<ModelRecord> {
+        &self.state.records
+    }
+}
+
+impl ReportableModel for Burst {}
+
+/// The holder model holds a single job indefinitely, refusing others
+#[derive(Debug, Clone, Serialize, Deserialize, SerializableModel)]
+#[serde(rename_all = "camelCase")]
+pub struct Holder {
+    ports_in: PortsIn,
+    #[serde(default)]
+    state: HolderState,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+struct HolderState {
+    job: Option<String>,
+    records: Vec<ModelRecord>,
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl Holder {
+    pub fn new(job_port: String) -> Self {
+        Self {
+            ports_in: PortsIn { job: job_port },
+            state: HolderState::default(),
+        }
+    }
+}
+
+#[cfg_attr(feature = "simx", event_rules)]
+impl DevsModel for Holder {
+    fn events_ext(
+        &mut self,
+        incoming_message: &ModelMessage,
+        _services: &mut Services,
+    ) -> Result<(), SimulationError> {
+        self.state.job = Some(incoming_message.content.clone());
+        Ok(())
+    }
+
+    fn events_int(
+        &mut self,
+        _services: &mut Services,
+    ) -> Result<Vec<ModelMessage>, SimulationError> {
+        Ok(Vec::new())
+    }
+
+    fn time_advance(&mut self, _time_delta: f64) {
+        // No future events list to advance
+    }
+
+    fn until_next_event(&self) -> f64 {
+        f64::INFINITY
+    }
+
+    fn can_accept(&self, _port: &str) -> bool {
+        self.state.job.is_none()
+    }
+}
+
+impl Reportable for Holder {
+    fn status(&self) -> String {
+        match &self.state.job {
+            Some(job) => format!["Holding {}", job],
+            None => "Empty".into(),
+        }
+    }
+
+    fn records(&self) -> &Vec<ModelRecord> {
+        &self.state.records
+    }
+}
+
+impl ReportableModel for Holder {}
+
 #[test]
 fn step_n_with_custom_passive_model() -> Result<(), SimulationError> {
     let models = [
@@ -96,6 +394,7 @@ fn step_n_with_custom_passive_model() -> Result<(), SimulationError> {
                 String::from("job"),
                 false,
                 None,
+                None,
             )),
         ),
         Model::new(
@@ -151,3 +450,233 @@ fn step_n_with_custom_passive_model_wasm() {
     let expected = 4; // 4 interarrivals from 9 steps
     assert_eq!(generations_count, expected);
 }
+
+#[test]
+fn echo_emits_on_external_event() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("echo-01"),
+            Box::new(Echo::new(String::from("job"), String::from("echo"))),
+        ),
+        Model::new(
+            String::from("passive-01"),
+            Box::new(Passive::new(String::from("job"))),
+        ),
+    ];
+    let connectors = [Connector::new(
+        String::from("connector-01"),
+        String::from("echo-01"),
+        String::from("passive-01"),
+        String::from("echo"),
+        String::from("job"),
+    )];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.inject_input(Message::new(
+        String::from("manual"),
+        String::from("manual"),
+        String::from("echo-01"),
+        String::from("job"),
+        simulation.get_global_time(),
+        String::from("job 1"),
+    ));
+    let messages = simulation.step()?;
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].source_id(), "echo-01");
+    assert_eq!(messages[0].target_id(), "passive-01");
+    assert_eq!(messages[0].content(), "job 1");
+    assert_eq!(*messages[0].time(), 0.0);
+    Ok(())
+}
+
+#[test]
+fn near_zero_events_fire_within_tolerance() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("ticker-01"),
+            Box::new(Ticker::new(0.1, String::from("tick"))),
+        ),
+        Model::new(
+            String::from("ticker-02"),
+            Box::new(Ticker::new(0.3, String::from("tick"))),
+        ),
+        Model::new(
+            String::from("passive-01"),
+            Box::new(Passive::new(String::from("job"))),
+        ),
+    ];
+    let connectors = [
+        Connector::new(
+            String::from("connector-01"),
+            String::from("ticker-01"),
+            String::from("passive-01"),
+            String::from("tick"),
+            String::from("job"),
+        ),
+        Connector::new(
+            String::from("connector-02"),
+            String::from("ticker-02"),
+            String::from("passive-01"),
+            String::from("tick"),
+            String::from("job"),
+        ),
+    ];
+    // Three ticks of 0.1 accumulate rounding error relative to one of 0.3,
+    // leaving the third tick of ticker-01 a hair after the tick of ticker-02
+    let tick_sources = |simulation: &mut Simulation| -> Result<Vec<String>, SimulationError> {
+        loop {
+            let messages = simulation.step()?;
+            if messages
+                .iter()
+                .any(|message| message.source_id() == "ticker-02")
+            {
+                return Ok(messages
+                    .iter()
+                    .map(|message| message.source_id().to_string())
+                    .collect());
+            }
+        }
+    };
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.set_event_tolerance(0.0);
+    assert_eq!(tick_sources(&mut simulation)?, vec!["ticker-02"]);
+    // With the default tolerance, the ticks coincide
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    assert_eq!(simulation.event_tolerance(), 1e-9);
+    assert_eq!(
+        tick_sources(&mut simulation)?,
+        vec!["ticker-01", "ticker-02"]
+    );
+    Ok(())
+}
+
+#[test]
+fn registered_custom_model_round_trips_through_yaml() -> Result<(), SimulationError> {
+    let config = r#"
+type: "Echo"
+id: "echo-01"
+portsIn:
+  job: "job"
+portsOut:
+  echo: "echo"
+"#;
+    // Unregistered types are rejected
+    assert!(serde_yaml::from_str::<Model>(&config.replace("Echo", "Unknown")).is_err());
+    register![Echo];
+    let model: Model = serde_yaml::from_str(config).unwrap();
+    assert_eq!(model.id(), "echo-01");
+    assert_eq!(model.model_type(), "Echo");
+    assert_eq!(model.status(), "Echoing");
+    let round_trip: Model = serde_yaml::from_str(&serde_yaml::to_string(&model).unwrap()).unwrap();
+    assert_eq!(round_trip.model_type(), "Echo");
+    // Registered types are described in the model configuration schema
+    let schema = model_config_schema();
+    assert!(schema["properties"]["type"]["enum"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!("Echo")));
+    Ok(())
+}
+
+#[test]
+fn mutually_blocked_models_are_deadlocked() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("holder-01"),
+            Box::new(Holder::new(String::from("job"))),
+        ),
+        Model::new(
+            String::from("holder-02"),
+            Box::new(Holder::new(String::from("job"))),
+        ),
+        Model::new(
+            String::from("passive-01"),
+            Box::new(Passive::new(String::from("job"))),
+        ),
+    ];
+    let mut simulation = Simulation::post(models.to_vec(), Vec::new());
+    simulation.set_backpressure(true);
+    let message = |source_id: &str, target_id: &str, content: &str| {
+        Message::builder()
+            .from(source_id, "job")
+            .to(target_id, "job")
+            .at(0.0)
+            .content(content)
+            .build()
+    };
+    // Each holder is filled, and then each is sent a job by the other
+    simulation.inject_input(message("manual", "holder-01", "job 1")?);
+    simulation.inject_input(message("manual", "holder-02", "job 2")?);
+    simulation.step()?;
+    assert!(simulation.detect_deadlock().is_none());
+    simulation.inject_input(message("holder-01", "holder-02", "job 3")?);
+    simulation.inject_input(message("holder-02", "holder-01", "job 4")?);
+    simulation.step()?;
+    let report = simulation.detect_deadlock().unwrap();
+    assert_eq!(report.model_ids, vec!["holder-01", "holder-02"]);
+    assert_eq!(report.blocked_messages.len(), 2);
+    // The deadlock can be raised as an error, without stepping
+    simulation.set_error_on_deadlock(true);
+    let global_time = simulation.get_global_time();
+    assert!(matches!(
+        simulation.step(),
+        Err(SimulationError::Deadlock(model_ids)) if model_ids == "holder-01, holder-02"
+    ));
+    assert_eq!(simulation.get_global_time(), global_time);
+    Ok(())
+}
+
+#[test]
+fn same_port_emissions_preserve_emission_order() -> Result<(), SimulationError> {
+    let processor = || {
+        Box::new(Processor::new(
+            ContinuousRandomVariable::Exp { lambda: 1.0 },
+            None,
+            String::from("job"),
+            String::from("processed"),
+            true,
+            None,
+        ))
+    };
+    let models = [
+        Model::new(
+            String::from("burst-01"),
+            Box::new(Burst::new(3, String::from("job"))),
+        ),
+        Model::new(String::from("processor-01"), processor()),
+        Model::new(String::from("processor-02"), processor()),
+    ];
+    let connectors = [1, 2].map(|index| {
+        Connector::new(
+            format!("connector-0{}", index),
+            String::from("burst-01"),
+            format!("processor-0{}", index),
+            String::from("job"),
+            String::from("job"),
+        )
+    });
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let messages = simulation.step_until_events(1)?;
+    let contents = |target_id: &str| {
+        messages
+            .iter()
+            .filter(|message| message.target_id() == target_id)
+            .map(|message| message.content().to_string())
+            .collect::<Vec<String>>()
+    };
+    assert_eq!(contents("processor-01"), ["job 1", "job 2", "job 3"]);
+    assert_eq!(contents("processor-02"), ["job 1", "job 2", "job 3"]);
+    // Each downstream model receives the jobs in emission order
+    simulation.step()?;
+    ["processor-01", "processor-02"].iter().try_for_each(
+        |model_id| -> Result<(), SimulationError> {
+            let arrivals: Vec<String> = simulation
+                .get_records(model_id)?
+                .iter()
+                .filter(|record| record.action == "Arrival")
+                .map(|record| record.subject.clone())
+                .collect();
+            assert_eq!(arrivals, ["job 1", "job 2", "job 3"]);
+            Ok(())
+        },
+    )
+}
@@ -36,6 +36,7 @@ fn generator_event_rules() {
         String::from("job"),
         false,
         None,
+        None,
     );
 
     let generator_event_rules = fs::read_to_string("tests/data/generator_event_rules.json")
@@ -64,6 +65,7 @@ fn coupled_event_rules() {
                         String::from("job"),
                         false,
                         None,
+                        None,
                     )),
                 ),
                 Model::new(
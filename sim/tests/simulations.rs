@@ -1,11 +1,30 @@
-use sim::input_modeling::{BooleanRandomVariable, ContinuousRandomVariable, IndexRandomVariable};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use sim::input_modeling::{
+    dyn_rng, BooleanRandomVariable, ContinuousRandomVariable, IndexRandomVariable, SubstreamScheme,
+};
 use sim::models::stopwatch::Metric as StopwatchMetric;
 use sim::models::{
-    Batcher, ExclusiveGateway, Gate, Generator, LoadBalancer, Model, ParallelGateway, Processor,
-    StochasticGate, Stopwatch, Storage,
+    model_config_schema, Batcher, ExclusiveGateway, Gate, Generator, LoadBalancer, LossyChannel,
+    Merger, Model, ParallelGateway, Processor, Resource, Responder, Statistics, StochasticGate,
+    Stopwatch, Storage, Transform, TransitTime, LOSS_PORT,
+};
+use sim::output_analysis::{connector_latencies, IndependentSample, SteadyStateOutput};
+use sim::simulator::time::MAX_FIXED_TIME;
+use sim::simulator::{
+    compare_variants, connectors_from_csv, content_histogram, export_xes, flow_summary,
+    merge_message_collections, parse_numeric_content, run_fingerprint, BreakResult, CheckSeverity,
+    Checker, Connector, ContentTransform, CsvFileSink, FixedTime, MaxQueueMetric,
+    MeanSojournMetric, Message, MessageSink, Metric, PartialSteps, ProgressInfo, Report, SimTime,
+    Simulation, ThroughputMetric, TimeRepresentation, TopologyGraph,
 };
-use sim::output_analysis::{IndependentSample, SteadyStateOutput};
-use sim::simulator::{Connector, Message, Simulation};
 use sim::utils::errors::SimulationError;
 
 fn epsilon() -> f64 {
@@ -16,6 +35,90 @@ fn get_message_number(message: &str) -> Option<&str> {
     message.split_whitespace().last()
 }
 
+/// A Poisson generator feeding a processor, with the given generation rate,
+/// processor service time, queue capacity, and processor record keeping.
+fn generator_processor_models(
+    generation_rate: f64,
+    service_time: ContinuousRandomVariable,
+    queue_capacity: Option<usize>,
+    store_records: bool,
+) -> Vec<Model> {
+    vec![
+        Model::new(
+            String::from("generator-01"),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Exp {
+                    lambda: generation_rate,
+                },
+                None,
+                String::from("job"),
+                false,
+                None,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("processor-01"),
+            Box::new(Processor::new(
+                service_time,
+                queue_capacity,
+                String::from("job"),
+                String::from("processed"),
+                store_records,
+                None,
+            )),
+        ),
+    ]
+}
+
+/// The generator, processor, and storage (GPS) models shared by most tests -
+/// the generator and processor of `generator_processor_models`, with a
+/// storage collecting the processed jobs.
+fn gps_models(
+    generation_rate: f64,
+    service_time: ContinuousRandomVariable,
+    queue_capacity: Option<usize>,
+    store_records: bool,
+) -> Vec<Model> {
+    let mut models =
+        generator_processor_models(generation_rate, service_time, queue_capacity, store_records);
+    models.push(Model::new(
+        String::from("storage-01"),
+        Box::new(Storage::new(
+            String::from("store"),
+            String::from("read"),
+            String::from("stored"),
+            false,
+        )),
+    ));
+    models
+}
+
+/// The connector from the generator to the processor.
+fn generator_processor_connector() -> Connector {
+    Connector::new(
+        String::from("connector-01"),
+        String::from("generator-01"),
+        String::from("processor-01"),
+        String::from("job"),
+        String::from("job"),
+    )
+}
+
+/// The connectors of the GPS models.
+fn gps_connectors() -> Vec<Connector> {
+    vec![
+        generator_processor_connector(),
+        Connector::new(
+            String::from("connector-02"),
+            String::from("processor-01"),
+            String::from("storage-01"),
+            String::from("processed"),
+            String::from("store"),
+        ),
+    ]
+}
+
 #[test]
 fn poisson_generator_processor_with_capacity() -> Result<(), SimulationError> {
     let models = [
@@ -27,6 +130,7 @@ fn poisson_generator_processor_with_capacity() -> Result<(), SimulationError> {
                 String::from("job"),
                 false,
                 None,
+                None,
             )),
         ),
         Model::new(
@@ -139,6 +243,7 @@ fn step_until_activities() -> Result<(), SimulationError> {
                 String::from("job"),
                 false,
                 None,
+                None,
             )),
         ),
         Model::new(
@@ -188,6 +293,7 @@ fn non_stationary_generation() -> Result<(), SimulationError> {
                 String::from("job"),
                 false,
                 None,
+                None,
             )),
         ),
         Model::new(
@@ -265,6 +371,7 @@ fn exclusive_gateway_proportions_chi_square() -> Result<(), SimulationError> {
                 String::from("job"),
                 false,
                 None,
+                None,
             )),
         ),
         Model::new(
@@ -390,6 +497,7 @@ fn gate_blocking_proportions() -> Result<(), SimulationError> {
                 String::from("job"),
                 false,
                 None,
+                None,
             )),
         ),
         Model::new(
@@ -400,6 +508,7 @@ fn gate_blocking_proportions() -> Result<(), SimulationError> {
                 String::from("job"),
                 false,
                 None,
+                None,
             )),
         ),
         Model::new(
@@ -410,6 +519,7 @@ fn gate_blocking_proportions() -> Result<(), SimulationError> {
                 String::from("job"),
                 false,
                 None,
+                None,
             )),
         ),
         Model::new(
@@ -507,6 +617,7 @@ fn load_balancer_round_robin_outputs() -> Result<(), SimulationError> {
                 String::from("job"),
                 false,
                 None,
+                None,
             )),
         ),
         Model::new(
@@ -688,6 +799,7 @@ fn parallel_gateway_splits_and_joins() -> Result<(), SimulationError> {
                 String::from("job"),
                 false,
                 None,
+                None,
             )),
         ),
         Model::new(
@@ -797,6 +909,7 @@ fn match_status_reporting() -> Result<(), SimulationError> {
                 String::from("job"),
                 false,
                 None,
+                None,
             )),
         ),
         Model::new(
@@ -833,6 +946,7 @@ fn stochastic_gate_blocking() -> Result<(), SimulationError> {
                 String::from("job"),
                 false,
                 None,
+                None,
             )),
         ),
         Model::new(
@@ -903,6 +1017,7 @@ fn batch_sizing() -> Result<(), SimulationError> {
                 String::from("job"),
                 false,
                 None,
+                None,
             )),
         ),
         Model::new(
@@ -974,6 +1089,7 @@ fn min_and_max_stopwatch() -> Result<(), SimulationError> {
                 String::from("job"),
                 false,
                 None,
+                None,
             )),
         ),
         Model::new(
@@ -1102,3 +1218,4317 @@ fn min_and_max_stopwatch() -> Result<(), SimulationError> {
     assert![responses[0].content() != responses[1].content()];
     Ok(())
 }
+
+#[test]
+fn step_back_restores_prior_state() -> Result<(), SimulationError> {
+    let models = gps_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        Some(14),
+        false,
+    );
+    let connectors = gps_connectors();
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.set_snapshot_depth(2);
+    assert!(simulation.step_back().is_err());
+    simulation.step()?;
+    let expected_time = simulation.get_global_time();
+    let expected_messages = serde_json::to_string(simulation.get_messages()).unwrap();
+    simulation.step()?;
+    simulation.step()?;
+    assert!(simulation.get_global_time() > expected_time);
+    simulation.step_back()?;
+    simulation.step_back()?;
+    assert_eq!(simulation.get_global_time(), expected_time);
+    assert_eq!(
+        serde_json::to_string(simulation.get_messages()).unwrap(),
+        expected_messages
+    );
+    // The ring depth bounds the retained snapshots
+    assert!(simulation.step_back().is_err());
+    Ok(())
+}
+
+#[test]
+fn lowered_snapshot_depth_keeps_most_recent_snapshots() -> Result<(), SimulationError> {
+    let models = gps_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        Some(14),
+        false,
+    );
+    let mut simulation = Simulation::post(models, gps_connectors());
+    simulation.set_snapshot_depth(5);
+    let mut global_times: Vec<f64> = Vec::new();
+    for _ in 0..5 {
+        global_times.push(simulation.get_global_time());
+        simulation.step()?;
+    }
+    assert!(global_times[3] > global_times[1]);
+    simulation.set_snapshot_depth(2);
+    simulation.step_back()?;
+    assert_eq!(simulation.get_global_time(), global_times[4]);
+    simulation.step_back()?;
+    assert_eq!(simulation.get_global_time(), global_times[3]);
+    assert!(simulation.step_back().is_err());
+    Ok(())
+}
+
+#[test]
+fn cumulative_message_and_event_counts() -> Result<(), SimulationError> {
+    let models = gps_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        Some(14),
+        false,
+    );
+    let connectors = gps_connectors();
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let mut messages_routed = simulation.total_messages_routed();
+    let mut events = simulation.total_events();
+    let mut message_records: Vec<Message> = Vec::new();
+    for _ in 0..100 {
+        message_records.extend(simulation.step()?);
+        assert!(simulation.total_messages_routed() >= messages_routed);
+        assert!(simulation.total_events() >= events);
+        messages_routed = simulation.total_messages_routed();
+        events = simulation.total_events();
+    }
+    assert!(messages_routed > 0);
+    assert!(events > 0);
+    assert_eq!(messages_routed, message_records.len() as u64);
+    simulation.reset();
+    assert_eq!(simulation.total_messages_routed(), 0);
+    assert_eq!(simulation.total_events(), 0);
+    Ok(())
+}
+
+#[test]
+fn simulation_json_round_trip() -> Result<(), SimulationError> {
+    let models = gps_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        Some(14),
+        false,
+    );
+    let connectors = gps_connectors();
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.step_n(2)?;
+    assert!(!simulation.get_messages().is_empty());
+    let json = simulation.to_json()?;
+    let mut restored = Simulation::from_json(&json)?;
+    assert_eq!(restored.models().len(), models.len());
+    assert_eq!(restored.get_connectors().len(), connectors.len());
+    assert_eq!(
+        restored.get_messages().len(),
+        simulation.get_messages().len()
+    );
+    assert!((restored.get_global_time() - simulation.get_global_time()).abs() < 1e-12);
+    assert_eq!(
+        restored.get_status("processor-01")?,
+        simulation.get_status("processor-01")?
+    );
+    Ok(())
+}
+
+#[test]
+fn merger_round_robin_outputs() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("merger-01"),
+            Box::new(Merger::new(
+                vec![String::from("alpha"), String::from("beta")],
+                String::from("job"),
+                false,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [Connector::new(
+        String::from("connector-01"),
+        String::from("merger-01"),
+        String::from("storage-01"),
+        String::from("job"),
+        String::from("store"),
+    )];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    // A burst of jobs from a busy alpha source, alongside a smaller burst
+    // from the beta source
+    (1..=4).for_each(|job_number| {
+        simulation.inject_input(Message::new(
+            String::from("source-alpha"),
+            String::from("job"),
+            String::from("merger-01"),
+            String::from("alpha"),
+            simulation.get_global_time(),
+            format!["alpha {}", job_number],
+        ))
+    });
+    (1..=2).for_each(|job_number| {
+        simulation.inject_input(Message::new(
+            String::from("source-beta"),
+            String::from("job"),
+            String::from("merger-01"),
+            String::from("beta"),
+            simulation.get_global_time(),
+            format!["beta {}", job_number],
+        ))
+    });
+    let merged: Vec<String> = simulation
+        .step_n(8)?
+        .iter()
+        .map(|message| message.content().to_string())
+        .collect();
+    assert_eq!(
+        merged,
+        vec!["alpha 1", "beta 1", "alpha 2", "beta 2", "alpha 3", "alpha 4"]
+    );
+    assert_eq!(simulation.get_status("merger-01")?, "Listening for jobs");
+    Ok(())
+}
+
+#[test]
+fn processor_records_csv_export() -> Result<(), SimulationError> {
+    let models = generator_processor_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        Some(14),
+        true,
+    );
+    let connectors = [generator_processor_connector()];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.step_n(20)?;
+    let records = simulation.get_records("processor-01")?;
+    assert!(!records.is_empty());
+    let csv = simulation.records_to_csv("processor-01")?;
+    let rows: Vec<&str> = csv.lines().collect();
+    assert_eq!(rows[0], "time,action,subject");
+    assert_eq!(rows.len(), records.len() + 1);
+    assert_eq!(
+        rows[1],
+        format![
+            "{},{},{}",
+            records[0].time, records[0].action, records[0].subject
+        ]
+    );
+    assert!(simulation.records_to_csv("processor-02").is_err());
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct PriorityJob {
+    id: usize,
+    priority: u8,
+    tags: Vec<String>,
+}
+
+#[test]
+fn structured_message_payload_round_trip() -> Result<(), SimulationError> {
+    let job = PriorityJob {
+        id: 7,
+        priority: 2,
+        tags: vec![String::from("expedite")],
+    };
+    let message = Message::new_json(
+        String::from("generator-01"),
+        String::from("job"),
+        String::from("processor-01"),
+        String::from("job"),
+        0.0,
+        &job,
+    )?;
+    assert_eq!(message.content_as::<PriorityJob>()?, job);
+    // Plain string content remains available, and fails typed retrieval
+    let plain = Message::new(
+        String::from("generator-01"),
+        String::from("job"),
+        String::from("processor-01"),
+        String::from("job"),
+        0.0,
+        String::from("job 1"),
+    );
+    assert_eq!(plain.content(), "job 1");
+    assert!(plain.content_as::<PriorityJob>().is_err());
+    Ok(())
+}
+
+#[test]
+fn builder_matches_hand_built_simulation() -> Result<(), SimulationError> {
+    let models = gps_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        Some(14),
+        false,
+    );
+    let connectors = gps_connectors();
+    let mut hand_built = Simulation::post(models.to_vec(), connectors.to_vec());
+    let mut built = Simulation::builder()
+        .add_model(models[0].clone())
+        .add_model(models[1].clone())
+        .add_model(models[2].clone())
+        .connect("generator-01", "job", "processor-01", "job")
+        .connect("processor-01", "processed", "storage-01", "store")
+        .with_rng(rand_pcg::Pcg64Mcg::new(42))
+        .build()?;
+    assert_eq!(built.to_json()?, hand_built.to_json()?);
+    built.step_n(10)?;
+    hand_built.step_n(10)?;
+    assert_eq!(built.to_json()?, hand_built.to_json()?);
+    // Connectors to unknown models fail validation
+    assert!(Simulation::builder()
+        .add_model(models[0].clone())
+        .connect("generator-01", "job", "processor-01", "job")
+        .build()
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn simulation_diff_reports_added_connector() -> Result<(), SimulationError> {
+    let models = gps_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        Some(14),
+        false,
+    );
+    let connectors = vec![generator_processor_connector()];
+    let extra_connector = gps_connectors()[1].clone();
+    let simulation = Simulation::post(models.to_vec(), connectors.clone());
+    let copy = simulation.clone();
+    assert!(simulation.structural_eq(&copy));
+    assert!(simulation.diff(&copy).is_empty());
+    let mut extended_connectors = connectors;
+    extended_connectors.push(extra_connector.clone());
+    let extended = Simulation::post(models.to_vec(), extended_connectors);
+    assert!(!simulation.structural_eq(&extended));
+    let diff = simulation.diff(&extended);
+    assert_eq!(diff.added_connectors, vec![extra_connector]);
+    assert!(diff.removed_connectors.is_empty());
+    assert!(diff.added_models.is_empty());
+    assert!(diff.removed_models.is_empty());
+    assert_eq!(diff.global_time_delta, 0.0);
+    Ok(())
+}
+
+#[test]
+fn backpressure_holds_messages_for_saturated_processor() -> Result<(), SimulationError> {
+    let mut models = generator_processor_models(
+        5.0,
+        ContinuousRandomVariable::Exp { lambda: 0.5 },
+        Some(2),
+        true,
+    );
+    models[0] = Model::new(
+        String::from("generator-01"),
+        Box::new(Generator::new(
+            ContinuousRandomVariable::Exp { lambda: 5.0 },
+            None,
+            String::from("job"),
+            true,
+            None,
+            None,
+        )),
+    );
+    let connectors = [generator_processor_connector()];
+    // Without backpressure, the saturated processor drops jobs
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.step_n(200)?;
+    assert!(simulation
+        .get_records("processor-01")?
+        .iter()
+        .any(|record| record.action == "Drop"));
+    let blocked = |simulation: &Simulation| -> Result<usize, SimulationError> {
+        Ok(simulation
+            .get_records("generator-01")?
+            .iter()
+            .filter(|record| record.action == "Blocked")
+            .count())
+    };
+    assert_eq!(blocked(&simulation)?, 0);
+    // With backpressure, jobs are held until the processor has capacity,
+    // and the generator is notified of each held job
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.set_backpressure(true);
+    let messages = simulation.step_n(200)?;
+    let records = simulation.get_records("processor-01")?;
+    assert!(!records.iter().any(|record| record.action == "Drop"));
+    assert!(!simulation.get_blocked_messages().is_empty());
+    assert!(blocked(&simulation)? >= simulation.get_blocked_messages().len());
+    // Every generated job is either blocked or has arrived at the processor
+    let arrivals = records
+        .iter()
+        .filter(|record| record.action == "Arrival")
+        .count();
+    assert_eq!(
+        messages.len(),
+        arrivals + simulation.get_blocked_messages().len() + simulation.get_messages().len()
+    );
+    Ok(())
+}
+
+#[test]
+fn connector_traffic_counts() -> Result<(), SimulationError> {
+    let models = gps_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        Some(14),
+        false,
+    );
+    let connectors = gps_connectors();
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let messages = simulation.step_n(500)?;
+    let traffic = simulation.connector_traffic();
+    assert!(traffic["connector-01"] > 0);
+    assert!(traffic["connector-02"] > 0);
+    assert!(traffic["connector-01"] >= traffic["connector-02"]);
+    assert_eq!(traffic.values().sum::<u64>(), messages.len() as u64);
+    simulation.reset();
+    assert!(simulation.connector_traffic().is_empty());
+    Ok(())
+}
+
+#[test]
+fn model_rng_override_pins_generator() -> Result<(), SimulationError> {
+    let models = generator_processor_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        None,
+        false,
+    );
+    let connectors = [generator_processor_connector()];
+    // The scripted interarrival times, from the pinned random number stream
+    let pinned_rng = dyn_rng(rand_pcg::Pcg64Mcg::new(7));
+    let mut interarrival = ContinuousRandomVariable::Exp { lambda: 0.5 };
+    let expected_times: Vec<f64> = (0..10)
+        .scan(0.0, |time, _| {
+            *time += interarrival.random_variate(pinned_rng.clone()).unwrap();
+            Some(*time)
+        })
+        .collect();
+    // Regardless of the global random number generator seed, the generator
+    // follows the pinned random number stream
+    [1, 2]
+        .iter()
+        .try_for_each(|seed| -> Result<(), SimulationError> {
+            let mut simulation = Simulation::post_with_rng(
+                models.to_vec(),
+                connectors.to_vec(),
+                rand_pcg::Pcg64Mcg::new(*seed),
+            );
+            simulation.set_model_rng("generator-01", rand_pcg::Pcg64Mcg::new(7))?;
+            let generation_times: Vec<f64> = simulation
+                .step_until(expected_times[9] + 1.0)?
+                .iter()
+                .filter(|message| message.source_id() == "generator-01")
+                .map(|message| *message.time())
+                .take(10)
+                .collect();
+            generation_times
+                .iter()
+                .zip(expected_times.iter())
+                .for_each(|(actual, expected)| assert!((actual - expected).abs() < 1e-9));
+            assert_eq!(generation_times.len(), 10);
+            Ok(())
+        })?;
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    assert!(simulation
+        .set_model_rng("generator-02", rand_pcg::Pcg64Mcg::new(7))
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn dot_graph_writer_matches_string() -> Result<(), SimulationError> {
+    let models = gps_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        Some(14),
+        false,
+    );
+    let connectors = gps_connectors();
+    let simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let mut buffer: Vec<u8> = Vec::new();
+    simulation.write_dot_graph(&mut buffer).unwrap();
+    let dot_graph = simulation.generate_dot_graph();
+    assert_eq!(buffer, dot_graph.as_bytes());
+    assert!(dot_graph.contains("\"generator-01\" -> \"processor-01\" [label=\"connector-01\"];"));
+    assert!(dot_graph.contains("\"processor-01\" -> \"storage-01\" [label=\"connector-02\"];"));
+    let ported_dot_graph = simulation.generate_dot_graph_with_ports();
+    assert!(ported_dot_graph.contains("[label=\"connector-01 (job -> job)\"]"));
+    assert!(ported_dot_graph.contains("[label=\"connector-02 (processed -> store)\"]"));
+    let graph: TopologyGraph = serde_json::from_str(&simulation.generate_json_graph())?;
+    assert_eq!(graph, simulation.topology_graph());
+    assert_eq!(graph.nodes[1].model_type, "Processor");
+    assert_eq!(graph.edges[1].source_port, "processed");
+    Ok(())
+}
+
+#[test]
+fn step_realtime_tracks_scaled_wall_clock() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("generator-01"),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Exp { lambda: 0.5 },
+                None,
+                String::from("job"),
+                false,
+                None,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [Connector::new(
+        String::from("connector-01"),
+        String::from("generator-01"),
+        String::from("storage-01"),
+        String::from("job"),
+        String::from("store"),
+    )];
+    // 1000 simulated time units per second of wall-clock time
+    let scale = 1000.0;
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    // The generator initialization event has zero delay
+    assert_eq!(simulation.until_next_event(), 0.0);
+    let start = std::time::Instant::now();
+    simulation.step_realtime(scale)?;
+    assert!(start.elapsed().as_secs_f64() < 0.05);
+    let mut expected_wall_clock = 0.0;
+    let start = std::time::Instant::now();
+    (0..6).try_for_each(|_| -> Result<(), SimulationError> {
+        let until_next_event = simulation.until_next_event();
+        let global_time = simulation.get_global_time();
+        simulation.step_realtime(scale)?;
+        assert_eq!(simulation.get_global_time(), global_time + until_next_event);
+        expected_wall_clock += until_next_event / scale;
+        Ok(())
+    })?;
+    let elapsed = start.elapsed().as_secs_f64();
+    assert!(elapsed >= expected_wall_clock);
+    assert!(elapsed < expected_wall_clock + 0.5);
+    assert!(simulation.step_realtime(0.0).is_err());
+    Ok(())
+}
+
+#[test]
+fn checker_flags_models_unreachable_from_sources() -> Result<(), SimulationError> {
+    let mut models = generator_processor_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        Some(14),
+        false,
+    );
+    models.extend([
+        Model::new(
+            String::from("processor-02"),
+            Box::new(Processor::new(
+                ContinuousRandomVariable::Exp { lambda: 0.333333 },
+                Some(14),
+                String::from("job"),
+                String::from("processed"),
+                false,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("processor-03"),
+            Box::new(Processor::new(
+                ContinuousRandomVariable::Exp { lambda: 0.333333 },
+                Some(14),
+                String::from("job"),
+                String::from("processed"),
+                false,
+                None,
+            )),
+        ),
+    ]);
+    let connectors = [
+        Connector::new(
+            String::from("connector-01"),
+            String::from("generator-01"),
+            String::from("processor-01"),
+            String::from("job"),
+            String::from("job"),
+        ),
+        // A disconnected subgraph - a loop of processors, without a source
+        Connector::new(
+            String::from("connector-02"),
+            String::from("processor-02"),
+            String::from("processor-03"),
+            String::from("processed"),
+            String::from("job"),
+        ),
+        Connector::new(
+            String::from("connector-03"),
+            String::from("processor-03"),
+            String::from("processor-02"),
+            String::from("processed"),
+            String::from("job"),
+        ),
+    ];
+    let simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    // The default bundle of checks does not include source reachability
+    simulation.check()?;
+    match simulation.reachable_from_sources() {
+        Err(SimulationError::UnreachableModel(model_id)) => assert_eq!(model_id, "processor-02"),
+        _ => panic!("Expected an unreachable model error"),
+    }
+    let simulation = Simulation::post(models[..2].to_vec(), connectors[..1].to_vec());
+    simulation.reachable_from_sources()?;
+    Ok(())
+}
+
+#[test]
+fn scheduled_input_delivered_at_scheduled_time() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("generator-01"),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Exp { lambda: 2.0 },
+                None,
+                String::from("job"),
+                false,
+                None,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("load-balancer-01"),
+            Box::new(LoadBalancer::new(
+                String::from("request"),
+                vec![String::from("alpha")],
+                false,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [
+        Connector::new(
+            String::from("connector-01"),
+            String::from("generator-01"),
+            String::from("storage-01"),
+            String::from("job"),
+            String::from("store"),
+        ),
+        Connector::new(
+            String::from("connector-02"),
+            String::from("load-balancer-01"),
+            String::from("storage-01"),
+            String::from("alpha"),
+            String::from("store"),
+        ),
+    ];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.step_n(3)?;
+    let disruption = Message::new(
+        String::from("manual"),
+        String::from("manual"),
+        String::from("load-balancer-01"),
+        String::from("request"),
+        5.0,
+        String::from("disruption"),
+    );
+    assert!(simulation
+        .schedule_input(disruption.clone(), simulation.get_global_time() - 1.0)
+        .is_err());
+    simulation.schedule_input(disruption, 5.0)?;
+    let delivered = loop {
+        let messages = simulation.step()?;
+        if let Some(message) = messages
+            .iter()
+            .find(|message| message.source_id() == "load-balancer-01")
+        {
+            break message.clone();
+        }
+        assert!(simulation.get_global_time() <= 5.0);
+    };
+    assert_eq!(delivered.content(), "disruption");
+    assert_eq!(*delivered.time(), 5.0);
+    Ok(())
+}
+
+#[test]
+fn model_reports_factory_type() {
+    let generator = Model::new(
+        String::from("generator-01"),
+        Box::new(Generator::new(
+            ContinuousRandomVariable::Exp { lambda: 0.5 },
+            None,
+            String::from("job"),
+            false,
+            None,
+            None,
+        )),
+    );
+    let storage = Model::new(
+        String::from("storage-01"),
+        Box::new(Storage::new(
+            String::from("store"),
+            String::from("read"),
+            String::from("stored"),
+            false,
+        )),
+    );
+    assert_eq!(generator.model_type(), "Generator");
+    assert_eq!(storage.model_type(), "Storage");
+}
+
+#[test]
+fn subgraph_extracts_selected_models() -> Result<(), SimulationError> {
+    let models = gps_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        Some(14),
+        false,
+    );
+    let connectors = gps_connectors();
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let mut reference = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.step_n(10)?;
+    reference.step_n(10)?;
+    let mut subgraph = simulation.subgraph(&["processor-01", "storage-01"])?;
+    assert!(subgraph.get_status("generator-01").is_err());
+    assert!(subgraph.get_status("processor-01").is_ok());
+    assert!(subgraph.get_status("storage-01").is_ok());
+    let connector_ids: Vec<&str> = subgraph
+        .get_connectors()
+        .iter()
+        .map(|connector| connector.id())
+        .collect();
+    assert_eq!(connector_ids, vec!["connector-02"]);
+    assert!(subgraph
+        .get_messages()
+        .iter()
+        .all(|message| message.target_id() != "generator-01"));
+    assert_eq!(subgraph.get_global_time(), simulation.get_global_time());
+    subgraph.step_n(10)?;
+    // The subgraph draws from a copy of the random number generator, so
+    // the original simulation continues its own random number stream
+    assert_eq!(simulation.step_n(10)?, reference.step_n(10)?);
+    assert!(simulation
+        .subgraph(&["processor-01", "generator-99"])
+        .is_err());
+    // Configuration is carried over, with held and scheduled messages
+    // filtered to the selected models
+    simulation.set_fifo_delivery(true);
+    simulation.set_event_tolerance(1e-6);
+    simulation.set_model_enabled("generator-01", false)?;
+    simulation.set_model_enabled("storage-01", false)?;
+    let scheduled_time = simulation.get_global_time() + 100.0;
+    let job = |target_id: &str| {
+        Message::new(
+            String::from("manual"),
+            String::from("manual"),
+            target_id.to_string(),
+            String::from("job"),
+            scheduled_time,
+            String::from("job"),
+        )
+    };
+    simulation.schedule_input(job("generator-01"), scheduled_time)?;
+    simulation.schedule_input(job("processor-01"), scheduled_time)?;
+    simulation.set_paused(true);
+    let subgraph = simulation.subgraph(&["processor-01", "storage-01"])?;
+    assert!(subgraph.is_paused());
+    assert_eq!(subgraph.event_tolerance(), 1e-6);
+    let config = serde_json::to_value(&subgraph)?;
+    assert_eq!(config["fifoDelivery"], true);
+    assert_eq!(config["disabledModels"], serde_json::json!(["storage-01"]));
+    let scheduled = config["scheduledMessages"].as_array().unwrap();
+    assert_eq!(scheduled.len(), 1);
+    assert_eq!(scheduled[0][1]["targetId"], "processor-01");
+    Ok(())
+}
+
+#[test]
+fn step_until_events_collects_target_messages() -> Result<(), SimulationError> {
+    let models = gps_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        Some(14),
+        false,
+    );
+    let connectors = gps_connectors();
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let target_events = 25;
+    let messages = simulation.step_until_events(target_events)?;
+    assert!(messages.len() >= target_events);
+    assert_eq!(simulation.total_messages_routed(), messages.len() as u64);
+    Ok(())
+}
+
+#[test]
+fn check_report_lists_invalid_connectors() {
+    let models = [
+        Model::new(
+            String::from("generator-01"),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Exp { lambda: 0.5 },
+                None,
+                String::from("job"),
+                false,
+                None,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [
+        Connector::new(
+            String::from("connector-01"),
+            String::from("generator-01"),
+            String::from("storage-01"),
+            String::from("job"),
+            String::from("store"),
+        ),
+        Connector::new(
+            String::from("connector-99"),
+            String::from("generator-01"),
+            String::from("fake-01"),
+            String::from("job"),
+            String::from("store"),
+        ),
+    ];
+    let valid = Simulation::post(models.to_vec(), connectors[..1].to_vec());
+    assert!(valid.check_report().issues.is_empty());
+    let simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let report = simulation.check_report();
+    assert!(report.has_errors());
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].severity, CheckSeverity::Error);
+    assert_eq!(report.issues[0].rule, "connectors_target_to_model");
+    assert!(report.to_string().contains("connector-99"));
+    assert!(report.to_string().starts_with("- [error]"));
+}
+
+#[test]
+fn nan_time_advance_is_rejected() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("generator-01"),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Normal {
+                    mean: f64::NAN,
+                    std_dev: 1.0,
+                },
+                None,
+                String::from("job"),
+                false,
+                None,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [Connector::new(
+        String::from("connector-01"),
+        String::from("generator-01"),
+        String::from("storage-01"),
+        String::from("job"),
+        String::from("store"),
+    )];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let global_time = simulation.get_global_time();
+    match simulation.step_n(5) {
+        Err(SimulationError::InvalidTimeAdvance(model_id)) => {
+            assert_eq!(model_id, "generator-01")
+        }
+        _ => panic!("expected an invalid time advance error"),
+    }
+    assert_eq!(simulation.get_global_time(), global_time);
+    Ok(())
+}
+
+#[test]
+fn model_priority_orders_simultaneous_events() -> Result<(), SimulationError> {
+    let mut high_priority = Model::new(
+        String::from("load-balancer-02"),
+        Box::new(LoadBalancer::new(
+            String::from("request"),
+            vec![String::from("alpha")],
+            false,
+        )),
+    );
+    high_priority.set_priority(-1);
+    let models = [
+        Model::new(
+            String::from("load-balancer-01"),
+            Box::new(LoadBalancer::new(
+                String::from("request"),
+                vec![String::from("alpha")],
+                false,
+            )),
+        ),
+        high_priority,
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [
+        Connector::new(
+            String::from("connector-01"),
+            String::from("load-balancer-01"),
+            String::from("storage-01"),
+            String::from("alpha"),
+            String::from("store"),
+        ),
+        Connector::new(
+            String::from("connector-02"),
+            String::from("load-balancer-02"),
+            String::from("storage-01"),
+            String::from("alpha"),
+            String::from("store"),
+        ),
+    ];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    ["load-balancer-01", "load-balancer-02"]
+        .iter()
+        .for_each(|model_id| {
+            simulation.inject_input(Message::new(
+                String::from("manual"),
+                String::from("manual"),
+                model_id.to_string(),
+                String::from("request"),
+                simulation.get_global_time(),
+                String::from("job"),
+            ))
+        });
+    let messages = simulation.step()?;
+    let source_ids: Vec<&str> = messages.iter().map(|message| message.source_id()).collect();
+    assert_eq!(source_ids, vec!["load-balancer-02", "load-balancer-01"]);
+    let round_trip: Simulation = serde_json::from_str(&serde_json::to_string(&simulation)?)?;
+    assert!(serde_json::to_string(&round_trip)?.contains("\"priority\":-1"));
+    // Firing order is observable through the draws from the shared global
+    // random number generator - the first model to fire takes the first draw
+    let generators = |priority: i32| -> Vec<Model> {
+        ["generator-01", "generator-02"]
+            .iter()
+            .map(|model_id| {
+                let mut model = Model::new(
+                    model_id.to_string(),
+                    Box::new(Generator::new(
+                        ContinuousRandomVariable::Exp { lambda: 1.0 },
+                        None,
+                        String::from("job"),
+                        true,
+                        None,
+                        None,
+                    )),
+                );
+                if *model_id == "generator-02" {
+                    model.set_priority(priority);
+                }
+                model
+            })
+            .collect()
+    };
+    let first_draw = |simulation: &Simulation, model_id: &str| -> Result<String, SimulationError> {
+        Ok(simulation.get_records(model_id)?[0].subject.clone())
+    };
+    let mut model_order = Simulation::post(generators(0), Vec::new());
+    model_order.step()?;
+    let mut priority_order = Simulation::post(generators(-1), Vec::new());
+    priority_order.step()?;
+    assert_eq!(
+        first_draw(&model_order, "generator-01")?,
+        first_draw(&priority_order, "generator-02")?
+    );
+    assert_eq!(
+        first_draw(&model_order, "generator-02")?,
+        first_draw(&priority_order, "generator-01")?
+    );
+    Ok(())
+}
+
+#[test]
+fn dedup_messages_coalesces_fan_out_duplicates() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("load-balancer-01"),
+            Box::new(LoadBalancer::new(
+                String::from("request"),
+                vec![String::from("alpha")],
+                false,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                true,
+            )),
+        ),
+    ];
+    let connectors = [
+        Connector::new(
+            String::from("connector-01"),
+            String::from("load-balancer-01"),
+            String::from("storage-01"),
+            String::from("alpha"),
+            String::from("store"),
+        ),
+        Connector::new(
+            String::from("connector-02"),
+            String::from("load-balancer-01"),
+            String::from("storage-01"),
+            String::from("alpha"),
+            String::from("store"),
+        ),
+    ];
+    let arrivals = |dedup: bool| -> Result<usize, SimulationError> {
+        let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+        simulation.set_dedup_messages(dedup);
+        simulation.inject_input(Message::new(
+            String::from("manual"),
+            String::from("manual"),
+            String::from("load-balancer-01"),
+            String::from("request"),
+            simulation.get_global_time(),
+            String::from("job"),
+        ));
+        assert_eq!(simulation.step()?.len(), 2);
+        simulation.step()?;
+        Ok(simulation
+            .get_records("storage-01")?
+            .iter()
+            .filter(|record| record.action == "Arrival")
+            .count())
+    };
+    assert_eq!(arrivals(false)?, 2);
+    assert_eq!(arrivals(true)?, 1);
+    Ok(())
+}
+
+#[test]
+fn generator_records_interarrival_draws() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("generator-01"),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Exp { lambda: 0.5 },
+                None,
+                String::from("job"),
+                true,
+                None,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [Connector::new(
+        String::from("connector-01"),
+        String::from("generator-01"),
+        String::from("storage-01"),
+        String::from("job"),
+        String::from("store"),
+    )];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let messages = simulation.step_n(40)?;
+    let scheduled_times: Vec<f64> = simulation
+        .get_records("generator-01")?
+        .iter()
+        .filter(|record| record.action == "Interarrival")
+        .map(|record| {
+            let draw: Vec<f64> = record
+                .subject
+                .split(" until ")
+                .map(|value| value.parse().unwrap())
+                .collect();
+            assert!(draw[0] > 0.0);
+            assert!((record.time + draw[0] - draw[1]).abs() < epsilon());
+            draw[1]
+        })
+        .collect();
+    let emission_times: Vec<f64> = messages
+        .iter()
+        .filter(|message| message.source_id() == "generator-01")
+        .map(|message| *message.time())
+        .collect();
+    assert!(!emission_times.is_empty());
+    emission_times.iter().zip(scheduled_times.iter()).for_each(
+        |(emission_time, scheduled_time)| {
+            assert!((emission_time - scheduled_time).abs() < epsilon());
+        },
+    );
+    Ok(())
+}
+
+#[test]
+fn referenced_ports_by_model() {
+    let models = gps_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        Some(14),
+        false,
+    );
+    let mut connectors = gps_connectors();
+    connectors.extend([Connector::new(
+        String::from("connector-03"),
+        String::from("processor-01"),
+        String::from("storage-01"),
+        String::from("processed"),
+        String::from("store"),
+    )]);
+    let simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let referenced_ports = simulation.referenced_ports();
+    assert_eq!(
+        referenced_ports["processor-01"],
+        (vec![String::from("job")], vec![String::from("processed")])
+    );
+    assert_eq!(
+        referenced_ports["generator-01"],
+        (Vec::new(), vec![String::from("job")])
+    );
+    assert_eq!(
+        referenced_ports["storage-01"],
+        (vec![String::from("store")], Vec::new())
+    );
+}
+
+#[test]
+fn generator_content_template_sequence() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("generator-01"),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Exp { lambda: 0.5 },
+                None,
+                String::from("job"),
+                false,
+                None,
+                Some(String::from("job-{seq}")),
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [Connector::new(
+        String::from("connector-01"),
+        String::from("generator-01"),
+        String::from("storage-01"),
+        String::from("job"),
+        String::from("store"),
+    )];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let contents: Vec<String> = simulation
+        .step_n(20)?
+        .iter()
+        .map(|message| message.content().to_string())
+        .collect();
+    assert!(contents.len() >= 3);
+    contents
+        .iter()
+        .enumerate()
+        .for_each(|(seq, content)| assert_eq!(content, &format!["job-{}", seq]));
+    Ok(())
+}
+
+#[test]
+fn step_instrumented_reports_phase_timing() -> Result<(), SimulationError> {
+    let models = gps_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        Some(14),
+        false,
+    );
+    let connectors = gps_connectors();
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.step_n(10)?;
+    let start = std::time::Instant::now();
+    let (messages, timing) = simulation.step_instrumented()?;
+    let elapsed = start.elapsed().as_nanos();
+    assert_eq!(messages.len(), simulation.get_messages().len());
+    assert!(timing.internal_events_ns > 0);
+    assert!(timing.total_ns() > 0);
+    assert!(timing.total_ns() <= elapsed);
+    assert_eq!(
+        timing.total_ns(),
+        timing.external_events_ns + timing.next_event_ns + timing.internal_events_ns
+    );
+    Ok(())
+}
+
+#[test]
+fn statistics_time_weighted_queue_length() -> Result<(), SimulationError> {
+    let mut models = gps_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 1.0 },
+        None,
+        false,
+    );
+    models.extend([Model::new(
+        String::from("statistics-01"),
+        Box::new(Statistics::new(
+            0.25,
+            String::from("processor-01"),
+            String::from("queue_length"),
+            false,
+        )),
+    )]);
+    let connectors = gps_connectors();
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let mean_queue_length = |simulation: &Simulation| -> Result<f64, SimulationError> {
+        simulation
+            .get_status("statistics-01")?
+            .split_whitespace()
+            .nth(1)
+            .and_then(|mean| mean.parse().ok())
+            .ok_or(SimulationError::InvalidModelState)
+    };
+    simulation.step_until(5000.0)?;
+    let early_mean = mean_queue_length(&simulation)?;
+    simulation.step_until(10000.0)?;
+    let late_mean = mean_queue_length(&simulation)?;
+    // M/M/1 with utilization 0.5 has a mean number in system of 1.0
+    assert!(early_mean > 0.0);
+    assert!((early_mean - late_mean).abs() / late_mean < 0.2);
+    assert!((late_mean - 1.0).abs() < 0.25);
+    Ok(())
+}
+
+#[test]
+fn strict_ports_reject_unrecognized_ports() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("load-balancer-01"),
+            Box::new(LoadBalancer::new(
+                String::from("request"),
+                vec![String::from("alpha")],
+                false,
+            )),
+        ),
+        Model::new(
+            String::from("processor-01"),
+            Box::new(Processor::new(
+                ContinuousRandomVariable::Exp { lambda: 0.333333 },
+                Some(14),
+                String::from("job"),
+                String::from("processed"),
+                false,
+                None,
+            )),
+        ),
+    ];
+    let misrouted = |target_id: &str| {
+        Message::new(
+            String::from("manual"),
+            String::from("manual"),
+            target_id.to_string(),
+            String::from("nonexistent"),
+            0.0,
+            String::from("job 1"),
+        )
+    };
+    // Lenient delivery leaves unrecognized ports to the target model
+    let mut lenient = Simulation::post(models.to_vec(), Vec::new());
+    lenient.inject_input(misrouted("load-balancer-01"));
+    assert!(lenient.step().is_ok());
+    let mut strict = Simulation::post(models.to_vec(), Vec::new());
+    strict.set_strict_ports(true);
+    strict.inject_input(misrouted("load-balancer-01"));
+    match strict.step() {
+        Err(SimulationError::UnrecognizedPort(model_id, port)) => {
+            assert_eq!(model_id, "load-balancer-01");
+            assert_eq!(port, "nonexistent");
+        }
+        _ => panic!("expected an unrecognized port error"),
+    }
+    let mut strict = Simulation::post(models.to_vec(), Vec::new());
+    strict.set_strict_ports(true);
+    strict.inject_input(misrouted("processor-01"));
+    assert!(matches!(
+        strict.step(),
+        Err(SimulationError::UnrecognizedPort(_, _))
+    ));
+    Ok(())
+}
+
+#[test]
+fn connectors_imported_from_csv() -> Result<(), SimulationError> {
+    let csv = "id,source_id,source_port,target_id,target_port
+connector-01,generator-01,job,processor-01,job
+
+\"connector-02\", processor-01, processed, storage-01, store
+";
+    let connectors = connectors_from_csv(csv)?;
+    assert_eq!(
+        connectors,
+        vec![
+            Connector::new(
+                String::from("connector-01"),
+                String::from("generator-01"),
+                String::from("processor-01"),
+                String::from("job"),
+                String::from("job"),
+            ),
+            Connector::new(
+                String::from("connector-02"),
+                String::from("processor-01"),
+                String::from("storage-01"),
+                String::from("processed"),
+                String::from("store"),
+            ),
+        ]
+    );
+    match connectors_from_csv("connector-01,generator-01,job,processor-01") {
+        Err(SimulationError::MalformedCsvRow(row, _)) => assert_eq!(row, 1),
+        _ => panic!("expected a malformed row error"),
+    }
+    match connectors_from_csv("connector-01,generator-01,job,processor-01,job\n,a,b,c,d") {
+        Err(SimulationError::MalformedCsvRow(row, reason)) => {
+            assert_eq!(row, 2);
+            assert_eq!(reason, "empty id");
+        }
+        _ => panic!("expected a malformed row error"),
+    }
+    Ok(())
+}
+
+#[test]
+fn job_attributes_scale_service_times() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("generator-01"),
+            Box::new(
+                Generator::new(
+                    ContinuousRandomVariable::Exp { lambda: 0.01 },
+                    None,
+                    String::from("job"),
+                    false,
+                    None,
+                    None,
+                )
+                .with_attribute(
+                    String::from("size"),
+                    ContinuousRandomVariable::Uniform {
+                        min: 1.0,
+                        max: 10.0,
+                    },
+                ),
+            ),
+        ),
+        Model::new(
+            String::from("processor-01"),
+            Box::new(
+                Processor::new(
+                    ContinuousRandomVariable::Uniform {
+                        min: 1.0,
+                        max: 1.000001,
+                    },
+                    None,
+                    String::from("job"),
+                    String::from("processed"),
+                    true,
+                    None,
+                )
+                .with_service_time_scale(String::from("size")),
+            ),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = gps_connectors();
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let messages = simulation.step_n(200)?;
+    let records = simulation.get_records("processor-01")?;
+    let record_time = |action: &str, job: &str| -> Result<f64, SimulationError> {
+        records
+            .iter()
+            .find(|record| record.action == action && record.subject == job)
+            .map(|record| record.time)
+            .ok_or(SimulationError::DroppedMessageError)
+    };
+    let mut sized_service_times: Vec<(f64, f64)> = messages
+        .iter()
+        .filter(|message| message.source_id() == "processor-01")
+        .map(|message| -> Result<(f64, f64), SimulationError> {
+            let size = message.attributes()["size"];
+            let service_time = record_time("Departure", message.content())?
+                - record_time("Processing Start", message.content())?;
+            Ok((size, service_time))
+        })
+        .collect::<Result<Vec<(f64, f64)>, SimulationError>>()?;
+    assert!(sized_service_times.len() > 10);
+    sized_service_times.iter().for_each(|(size, service_time)| {
+        assert!((size - service_time).abs() < 0.001);
+    });
+    // Larger sizes yield longer service times
+    sized_service_times.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    assert!(sized_service_times
+        .windows(2)
+        .all(|pair| pair[0].1 <= pair[1].1));
+    Ok(())
+}
+
+#[test]
+fn job_attributes_pass_through_routing_models() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("generator-01"),
+            Box::new(
+                Generator::new(
+                    ContinuousRandomVariable::Exp { lambda: 0.01 },
+                    None,
+                    String::from("job"),
+                    false,
+                    None,
+                    None,
+                )
+                .with_attribute(
+                    String::from("size"),
+                    ContinuousRandomVariable::Uniform {
+                        min: 1.0,
+                        max: 10.0,
+                    },
+                ),
+            ),
+        ),
+        Model::new(
+            String::from("merger-01"),
+            Box::new(Merger::new(
+                vec![String::from("alpha"), String::from("beta")],
+                String::from("job"),
+                false,
+            )),
+        ),
+        Model::new(
+            String::from("processor-01"),
+            Box::new(
+                Processor::new(
+                    ContinuousRandomVariable::Uniform {
+                        min: 1.0,
+                        max: 1.000001,
+                    },
+                    None,
+                    String::from("job"),
+                    String::from("processed"),
+                    true,
+                    None,
+                )
+                .with_service_time_scale(String::from("size")),
+            ),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [
+        Connector::new(
+            String::from("connector-01"),
+            String::from("generator-01"),
+            String::from("merger-01"),
+            String::from("job"),
+            String::from("alpha"),
+        ),
+        Connector::new(
+            String::from("connector-02"),
+            String::from("merger-01"),
+            String::from("processor-01"),
+            String::from("job"),
+            String::from("job"),
+        ),
+        Connector::new(
+            String::from("connector-03"),
+            String::from("processor-01"),
+            String::from("storage-01"),
+            String::from("processed"),
+            String::from("store"),
+        ),
+    ];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let messages = simulation.step_n(300)?;
+    let generated_sizes: HashMap<String, f64> = messages
+        .iter()
+        .filter(|message| message.source_id() == "generator-01")
+        .map(|message| (message.content().to_string(), message.attributes()["size"]))
+        .collect();
+    let records = simulation.get_records("processor-01")?;
+    let record_time = |action: &str, job: &str| -> Result<f64, SimulationError> {
+        records
+            .iter()
+            .find(|record| record.action == action && record.subject == job)
+            .map(|record| record.time)
+            .ok_or(SimulationError::DroppedMessageError)
+    };
+    let processed: Vec<&Message> = messages
+        .iter()
+        .filter(|message| message.source_id() == "processor-01")
+        .collect();
+    assert!(processed.len() > 10);
+    processed
+        .iter()
+        .try_for_each(|message| -> Result<(), SimulationError> {
+            // The merger forwards the generated attributes unchanged, so the
+            // processor scales its service times by the generated sizes
+            let size = message.attributes()["size"];
+            assert_eq!(size, generated_sizes[message.content()]);
+            let service_time = record_time("Departure", message.content())?
+                - record_time("Processing Start", message.content())?;
+            assert!((size - service_time).abs() < 0.001);
+            Ok(())
+        })?;
+    Ok(())
+}
+
+#[test]
+fn expected_latency_bounds_accumulate_mean_delays() -> Result<(), SimulationError> {
+    let models = gps_models(
+        0.5,
+        ContinuousRandomVariable::Uniform { min: 1.0, max: 5.0 },
+        None,
+        false,
+    );
+    let mut connectors = gps_connectors();
+    connectors.extend([
+        // A bypass of the processor does not shorten the longest path
+        Connector::new(
+            String::from("connector-03"),
+            String::from("generator-01"),
+            String::from("storage-01"),
+            String::from("job"),
+            String::from("store"),
+        ),
+    ]);
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let latencies = simulation.expected_latency_bounds()?;
+    assert!((latencies["generator-01"] - 0.0).abs() < epsilon());
+    assert!((latencies["processor-01"] - 2.0).abs() < epsilon());
+    assert!((latencies["storage-01"] - 5.0).abs() < epsilon());
+    // A feedback loop has no latency bound
+    simulation.put(
+        models.to_vec(),
+        [
+            connectors.to_vec(),
+            vec![Connector::new(
+                String::from("connector-04"),
+                String::from("storage-01"),
+                String::from("processor-01"),
+                String::from("stored"),
+                String::from("job"),
+            )],
+        ]
+        .concat(),
+    );
+    assert!(matches!(
+        simulation.expected_latency_bounds(),
+        Err(SimulationError::CyclicConnectors(_))
+    ));
+    assert!(simulation.acyclic_connectors().is_err());
+    Ok(())
+}
+
+#[test]
+fn replaced_model_keeps_connectors() -> Result<(), SimulationError> {
+    let models = gps_models(
+        0.05,
+        ContinuousRandomVariable::Uniform { min: 0.5, max: 1.0 },
+        None,
+        false,
+    );
+    let connectors = gps_connectors();
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.step_until(200.0)?;
+    simulation.replace_model(
+        "processor-01",
+        Box::new(Processor::new(
+            ContinuousRandomVariable::Uniform {
+                min: 10.0,
+                max: 10.5,
+            },
+            None,
+            String::from("job"),
+            String::from("processed"),
+            true,
+            None,
+        )),
+    )?;
+    let messages = simulation.step_until(2000.0)?;
+    // Processed jobs are still routed to storage after the swap
+    assert!(messages.iter().any(|message| {
+        message.source_id() == "processor-01" && message.target_id() == "storage-01"
+    }));
+    // Service times reflect the new distribution
+    let records = simulation.get_records("processor-01")?;
+    let departures: Vec<_> = records
+        .iter()
+        .filter(|record| record.action == "Departure")
+        .collect();
+    assert!(!departures.is_empty());
+    departures.iter().for_each(|departure| {
+        let start = records
+            .iter()
+            .find(|record| {
+                record.action == "Processing Start" && record.subject == departure.subject
+            })
+            .unwrap();
+        assert!(departure.time - start.time >= 10.0);
+    });
+    assert!(matches!(
+        simulation.replace_model(
+            "processor-02",
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            ))
+        ),
+        Err(SimulationError::ModelNotFound)
+    ));
+    Ok(())
+}
+
+#[test]
+fn active_message_limit_stops_flooding() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("generator-01"),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Exp { lambda: 1000.0 },
+                None,
+                String::from("job"),
+                false,
+                None,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("processor-01"),
+            Box::new(Processor::new(
+                ContinuousRandomVariable::Uniform {
+                    min: 10.0,
+                    max: 11.0,
+                },
+                Some(1),
+                String::from("job"),
+                String::from("processed"),
+                false,
+                None,
+            )),
+        ),
+    ];
+    let connectors = [generator_processor_connector()];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.set_backpressure(true);
+    simulation.set_max_active_messages(Some(50));
+    let result = simulation.step_n(1000);
+    match result {
+        Err(SimulationError::MessageLimitExceeded(target_id)) => {
+            assert_eq!(target_id, "processor-01")
+        }
+        _ => panic!("Expected the active message limit to be exceeded"),
+    }
+    assert!(simulation.get_blocked_messages().len() <= 51);
+    // Without a limit, the same flood is retained in full
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.set_backpressure(true);
+    simulation.step_n(1000)?;
+    assert!(simulation.get_blocked_messages().len() > 50);
+    Ok(())
+}
+
+#[test]
+fn merged_dot_graph_collapses_parallel_connectors() {
+    let models = [
+        Model::new(
+            String::from("generator-01"),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Exp { lambda: 0.5 },
+                None,
+                String::from("job"),
+                false,
+                None,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [
+        Connector::new(
+            String::from("connector-01"),
+            String::from("generator-01"),
+            String::from("storage-01"),
+            String::from("job"),
+            String::from("store"),
+        ),
+        Connector::new(
+            String::from("connector-02"),
+            String::from("generator-01"),
+            String::from("storage-01"),
+            String::from("job"),
+            String::from("read"),
+        ),
+    ];
+    let simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    assert_eq!(simulation.generate_dot_graph().matches(" -> \"").count(), 2);
+    let merged_dot_graph = simulation.generate_dot_graph_merged();
+    assert_eq!(merged_dot_graph.matches(" -> \"").count(), 1);
+    assert!(merged_dot_graph.contains(
+        "\"generator-01\" -> \"storage-01\" [label=\"connector-01 (job -> store)\\nconnector-02 (job -> read)\"];"
+    ));
+}
+
+#[test]
+fn dot_graphs_escape_quotes_and_backslashes() {
+    let models = [
+        Model::new(
+            String::from("generator \"01\""),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Exp { lambda: 0.5 },
+                None,
+                String::from("job\"out"),
+                false,
+                None,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("storage\\01"),
+            Box::new(Storage::new(
+                String::from("store\\in"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [Connector::new(
+        String::from("connector \"01\""),
+        String::from("generator \"01\""),
+        String::from("storage\\01"),
+        String::from("job\"out"),
+        String::from("store\\in"),
+    )];
+    let simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let dot_graph = simulation.generate_dot_graph();
+    assert!(dot_graph.contains(r#"    "generator \"01\"";"#));
+    assert!(dot_graph.contains(r#"    "storage\\01";"#));
+    assert!(
+        dot_graph.contains(r#""generator \"01\"" -> "storage\\01" [label="connector \"01\""];"#)
+    );
+    let edge = r#""generator \"01\"" -> "storage\\01" [label="connector \"01\" (job\"out -> store\\in)"];"#;
+    assert!(simulation.generate_dot_graph_with_ports().contains(edge));
+    assert!(simulation.generate_dot_graph_merged().contains(edge));
+}
+
+#[test]
+fn seeded_state_is_reflected_before_stepping() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("merger-01"),
+            Box::new(Merger::new(
+                vec![String::from("flow-01"), String::from("flow-02")],
+                String::from("job"),
+                false,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [Connector::new(
+        String::from("connector-01"),
+        String::from("merger-01"),
+        String::from("storage-01"),
+        String::from("job"),
+        String::from("store"),
+    )];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    assert_eq!(simulation.get_status("merger-01")?, "Listening for jobs");
+    let state: serde_yaml::Value = serde_yaml::from_str(
+        "
+phase: Merging
+untilNextEvent: 0.0
+jobs:
+  - [job-01, job-02]
+  - [job-03]
+",
+    )
+    .unwrap();
+    simulation.seed_state("merger-01", state)?;
+    assert_eq!(simulation.get_status("merger-01")?, "Merging 3 jobs");
+    let messages = simulation.step_n(3)?;
+    let contents: Vec<&str> = messages.iter().map(|message| message.content()).collect();
+    assert_eq!(contents, vec!["job-01", "job-03", "job-02"]);
+    assert!(matches!(
+        simulation.seed_state("merger-02", serde_yaml::Value::Null),
+        Err(SimulationError::ModelNotFound)
+    ));
+    assert!(matches!(
+        simulation.seed_state("merger-01", serde_yaml::from_str("phase: Unknown").unwrap()),
+        Err(SimulationError::InvalidModelState)
+    ));
+    Ok(())
+}
+
+#[test]
+fn loaded_state_keeps_model_random_number_generator() -> Result<(), SimulationError> {
+    let simulation = || {
+        let models = [
+            Model::new(
+                String::from("generator-01"),
+                Box::new(Generator::new(
+                    ContinuousRandomVariable::Exp { lambda: 0.5 },
+                    None,
+                    String::from("job"),
+                    false,
+                    Some(dyn_rng(rand_pcg::Pcg64Mcg::new(7))),
+                    None,
+                )),
+            ),
+            Model::new(
+                String::from("storage-01"),
+                Box::new(Storage::new(
+                    String::from("store"),
+                    String::from("read"),
+                    String::from("stored"),
+                    false,
+                )),
+            ),
+        ];
+        let connectors = [Connector::new(
+            String::from("connector-01"),
+            String::from("generator-01"),
+            String::from("storage-01"),
+            String::from("job"),
+            String::from("store"),
+        )];
+        Simulation::post(models.to_vec(), connectors.to_vec())
+    };
+    let (mut reloaded, mut untouched) = (simulation(), simulation());
+    reloaded.step_n(5)?;
+    untouched.step_n(5)?;
+    // Save the generator state, and load it back
+    let state = serde_json::to_value(&reloaded)?["models"][0]["state"].clone();
+    reloaded.seed_state(
+        "generator-01",
+        serde_yaml::to_value(state).map_err(SimulationError::YAMLError)?,
+    )?;
+    let times = |messages: Vec<Message>| -> Vec<f64> {
+        messages.iter().map(|message| *message.time()).collect()
+    };
+    assert_eq!(times(reloaded.step_n(10)?), times(untouched.step_n(10)?));
+    Ok(())
+}
+
+#[test]
+fn imminent_models_produce_the_next_outputs() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("generator-01"),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Exp { lambda: 0.5 },
+                None,
+                String::from("job"),
+                false,
+                None,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("generator-02"),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Exp { lambda: 0.5 },
+                None,
+                String::from("job"),
+                false,
+                None,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [
+        Connector::new(
+            String::from("connector-01"),
+            String::from("generator-01"),
+            String::from("storage-01"),
+            String::from("job"),
+            String::from("store"),
+        ),
+        Connector::new(
+            String::from("connector-02"),
+            String::from("generator-02"),
+            String::from("storage-01"),
+            String::from("job"),
+            String::from("store"),
+        ),
+    ];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    // Both generators initialize at the start of the simulation
+    assert_eq!(
+        simulation.imminent_models(),
+        vec!["generator-01", "generator-02"]
+    );
+    simulation.step()?;
+    (0..20).try_for_each(|_| -> Result<(), SimulationError> {
+        let imminent_models = simulation.imminent_models();
+        let mut sources: Vec<String> = simulation
+            .step()?
+            .iter()
+            .map(|message| message.source_id().to_string())
+            .collect();
+        sources.dedup();
+        // While jobs are delivered to storage, no model is imminent
+        assert_eq!(imminent_models, sources);
+        Ok(())
+    })
+}
+
+#[test]
+fn replayed_trace_reproduces_outputs() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("processor-01"),
+            Box::new(Processor::new(
+                ContinuousRandomVariable::Exp { lambda: 0.5 },
+                None,
+                String::from("job"),
+                String::from("processed"),
+                false,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [Connector::new(
+        String::from("connector-01"),
+        String::from("processor-01"),
+        String::from("storage-01"),
+        String::from("processed"),
+        String::from("store"),
+    )];
+    let trace: Vec<Message> = [0.5, 1.0, 4.0, 4.5, 10.0]
+        .iter()
+        .enumerate()
+        .map(|(index, time)| {
+            Message::new(
+                String::from("operator"),
+                String::from("job"),
+                String::from("processor-01"),
+                String::from("job"),
+                *time,
+                format!["job {}", index],
+            )
+        })
+        .collect();
+    // The original run, with inputs injected as they occur
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let mut original_outputs: Vec<Message> = Vec::new();
+    let mut pending_inputs = trace.clone();
+    while !pending_inputs.is_empty() || simulation.until_next_event().is_finite() {
+        let next_input_time = pending_inputs
+            .first()
+            .map(|message| *message.time())
+            .unwrap_or(f64::INFINITY);
+        if next_input_time <= simulation.get_global_time() + simulation.until_next_event() {
+            simulation.schedule_input(pending_inputs.remove(0), next_input_time)?;
+        }
+        original_outputs.extend(simulation.step()?);
+    }
+    // The replay, in a fresh simulation
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let replayed_outputs = simulation.replay(&trace)?;
+    let summarize = |messages: &[Message]| -> Vec<(String, String, f64)> {
+        messages
+            .iter()
+            .map(|message| {
+                (
+                    message.target_id().to_string(),
+                    message.content().to_string(),
+                    *message.time(),
+                )
+            })
+            .collect()
+    };
+    assert_eq!(original_outputs.len(), trace.len());
+    assert_eq!(summarize(&original_outputs), summarize(&replayed_outputs));
+    Ok(())
+}
+
+#[test]
+fn disabled_model_pauses_and_resumes() -> Result<(), SimulationError> {
+    let models = gps_models(
+        1.0,
+        ContinuousRandomVariable::Uniform { min: 0.1, max: 0.5 },
+        None,
+        false,
+    );
+    let connectors = gps_connectors();
+    let processed = |messages: &[Message]| {
+        messages
+            .iter()
+            .filter(|message| message.source_id() == "processor-01")
+            .count()
+    };
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    assert!(processed(&simulation.step_until(20.0)?) > 0);
+    simulation.set_model_enabled("processor-01", false)?;
+    assert!(!simulation.is_model_enabled("processor-01"));
+    // Jobs continue to arrive, but are held while the processor is offline
+    let messages = simulation.step_until(40.0)?;
+    assert_eq!(processed(&messages), 0);
+    let held_jobs = simulation.get_blocked_messages().len();
+    assert!(held_jobs > 5);
+    simulation.set_model_enabled("processor-01", true)?;
+    let messages = simulation.step_until(60.0)?;
+    assert!(processed(&messages) >= held_jobs);
+    assert!(simulation.get_blocked_messages().is_empty());
+    // Dropped messages are not retained for the disabled model
+    simulation.set_drop_disabled_messages(true);
+    simulation.set_model_enabled("processor-01", false)?;
+    simulation.step_until(80.0)?;
+    assert!(simulation.get_blocked_messages().is_empty());
+    assert!(matches!(
+        simulation.set_model_enabled("processor-02", false),
+        Err(SimulationError::ModelNotFound)
+    ));
+    Ok(())
+}
+
+#[test]
+fn run_and_collect_evaluates_named_metrics() -> Result<(), SimulationError> {
+    let models = gps_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 1.0 },
+        None,
+        false,
+    );
+    let connectors = gps_connectors();
+    let metrics: Vec<Box<dyn Metric>> = vec![
+        Box::new(ThroughputMetric {
+            model_id: String::from("processor-01"),
+        }),
+        Box::new(MeanSojournMetric {
+            model_id: String::from("processor-01"),
+        }),
+        Box::new(MaxQueueMetric {
+            model_id: String::from("processor-01"),
+        }),
+    ];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let results = simulation.run_and_collect(2000.0, &metrics)?;
+    assert_eq!(results.len(), 3);
+    // Throughput matches the arrival rate of 0.5, for a stable queue
+    let throughput = results["throughput of processor-01"];
+    assert!((throughput - 0.5).abs() < 0.1);
+    // The M/M/1 mean sojourn time is 1 / (mu - lambda) = 2
+    let mean_sojourn = results["mean sojourn time in processor-01"];
+    assert!((1.0..4.0).contains(&mean_sojourn));
+    let max_queue = results["max queue length of processor-01"];
+    assert!((1.0..50.0).contains(&max_queue));
+    Ok(())
+}
+
+#[test]
+fn fan_out_messages_are_ordered_deterministically() -> Result<(), SimulationError> {
+    let storage = |model_id: &str| {
+        Model::new(
+            model_id.to_string(),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        )
+    };
+    let models = [
+        Model::new(
+            String::from("parallel-gateway-02"),
+            Box::new(ParallelGateway::new(
+                vec![String::from("request")],
+                vec![String::from("alpha"), String::from("beta")],
+                false,
+            )),
+        ),
+        Model::new(
+            String::from("parallel-gateway-01"),
+            Box::new(ParallelGateway::new(
+                vec![String::from("request")],
+                vec![String::from("alpha"), String::from("beta")],
+                false,
+            )),
+        ),
+        storage("storage-01"),
+        storage("storage-02"),
+    ];
+    let connector = |connector_id: &str, source_id: &str, target_id: &str, port: &str| {
+        Connector::new(
+            connector_id.to_string(),
+            source_id.to_string(),
+            target_id.to_string(),
+            port.to_string(),
+            String::from("store"),
+        )
+    };
+    let connectors = [
+        connector("connector-04", "parallel-gateway-02", "storage-02", "beta"),
+        connector("connector-03", "parallel-gateway-02", "storage-01", "alpha"),
+        connector("connector-02", "parallel-gateway-01", "storage-02", "alpha"),
+        connector("connector-01", "parallel-gateway-01", "storage-01", "alpha"),
+        connector("connector-05", "parallel-gateway-01", "storage-01", "beta"),
+    ];
+    let run = || -> Result<String, SimulationError> {
+        let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+        ["parallel-gateway-02", "parallel-gateway-01"]
+            .iter()
+            .for_each(|model_id| {
+                simulation.inject_input(Message::new(
+                    String::from("manual"),
+                    String::from("manual"),
+                    model_id.to_string(),
+                    String::from("request"),
+                    simulation.get_global_time(),
+                    String::from("job"),
+                ))
+            });
+        let messages = simulation.step_n(2)?;
+        let routes: Vec<(&str, &str, &str)> = messages
+            .iter()
+            .map(|message| {
+                (
+                    message.source_id(),
+                    message.source_port(),
+                    message.target_id(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            routes,
+            vec![
+                ("parallel-gateway-01", "alpha", "storage-01"),
+                ("parallel-gateway-01", "alpha", "storage-02"),
+                ("parallel-gateway-01", "beta", "storage-01"),
+                ("parallel-gateway-02", "alpha", "storage-01"),
+                ("parallel-gateway-02", "beta", "storage-02"),
+            ]
+        );
+        Ok(serde_json::to_string(&messages)?)
+    };
+    assert_eq!(run()?, run()?);
+    Ok(())
+}
+
+#[test]
+fn connector_transform_rewrites_content_in_transit() -> Result<(), SimulationError> {
+    let storage = |model_id: &str| {
+        Model::new(
+            model_id.to_string(),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        )
+    };
+    let models = [
+        Model::new(
+            String::from("generator-01"),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Exp { lambda: 0.5 },
+                None,
+                String::from("job"),
+                false,
+                None,
+                None,
+            )),
+        ),
+        storage("storage-01"),
+        storage("storage-02"),
+    ];
+    let connectors = [
+        Connector::new(
+            String::from("connector-01"),
+            String::from("generator-01"),
+            String::from("storage-01"),
+            String::from("job"),
+            String::from("store"),
+        )
+        .with_transform(ContentTransform::Prefix {
+            prefix: String::from("raw: "),
+        }),
+        Connector::new(
+            String::from("connector-02"),
+            String::from("generator-01"),
+            String::from("storage-02"),
+            String::from("job"),
+            String::from("store"),
+        ),
+    ];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let messages = simulation.step_n(10)?;
+    let contents = |target_id: &str| -> Vec<String> {
+        messages
+            .iter()
+            .filter(|message| message.target_id() == target_id)
+            .map(|message| message.content().to_string())
+            .collect()
+    };
+    let transformed = contents("storage-01");
+    let untransformed = contents("storage-02");
+    assert!(!untransformed.is_empty());
+    assert_eq!(transformed.len(), untransformed.len());
+    transformed
+        .iter()
+        .zip(untransformed.iter())
+        .for_each(|(transformed, untransformed)| {
+            assert_eq!(*transformed, format!["raw: {}", untransformed]);
+        });
+    // Transforms are configurable through JSON
+    let connector: Connector = serde_json::from_str(
+        r#"{"id": "connector-03", "sourceID": "a", "targetID": "b", "sourcePort": "x", "targetPort": "y",
+            "transform": {"replace": {"from": "job", "to": "task"}}}"#,
+    )?;
+    assert_eq!(
+        connector
+            .transform()
+            .map(|transform| transform.apply("job 1")),
+        Some(String::from("task 1"))
+    );
+    assert_eq!(ContentTransform::Uppercase.apply("job 1"), "JOB 1");
+    Ok(())
+}
+
+#[test]
+fn cancelled_step_until_returns_partial_results() -> Result<(), SimulationError> {
+    let models = gps_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 1.0 },
+        None,
+        false,
+    );
+    let connectors = gps_connectors();
+    // A cancellation before the run begins executes no steps
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let (messages, cancelled) =
+        simulation.step_until_cancellable(100.0, Arc::new(AtomicBool::new(true)))?;
+    assert!(cancelled);
+    assert!(messages.is_empty());
+    assert_eq!(simulation.get_global_time(), 0.0);
+    // An uncancelled run completes as with step_until
+    let (messages, cancelled) =
+        simulation.step_until_cancellable(100.0, Arc::new(AtomicBool::new(false)))?;
+    assert!(!cancelled);
+    assert!(!messages.is_empty());
+    // A run to a far-future time is cancelled from another thread
+    let cancel = Arc::new(AtomicBool::new(false));
+    let canceller = {
+        let cancel = cancel.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            cancel.store(true, Ordering::Relaxed);
+        })
+    };
+    let (messages, cancelled) = simulation.step_until_cancellable(1.0e15, cancel)?;
+    canceller.join().unwrap();
+    assert!(cancelled);
+    assert!(!messages.is_empty());
+    let global_time = simulation.get_global_time();
+    assert!(global_time < 1.0e15);
+    assert!(messages
+        .iter()
+        .all(|message| *message.time() <= global_time));
+    // The cancelled simulation remains consistent, and may be resumed
+    let messages = simulation.step_until(global_time + 100.0)?;
+    assert!(messages
+        .iter()
+        .all(|message| *message.time() >= global_time));
+    Ok(())
+}
+
+#[test]
+fn message_builder_matches_positional_constructor() {
+    let built = Message::builder()
+        .from("generator-01", "job")
+        .to("processor-01", "job-in")
+        .at(1.5)
+        .content("job 1")
+        .build()
+        .unwrap();
+    let positional = Message::new(
+        String::from("generator-01"),
+        String::from("job"),
+        String::from("processor-01"),
+        String::from("job-in"),
+        1.5,
+        String::from("job 1"),
+    );
+    assert_eq!(built, positional);
+    // Missing and empty fields are rejected, by name
+    let incomplete = Message::builder()
+        .from("generator-01", "job")
+        .at(1.5)
+        .content("job 1")
+        .build();
+    assert!(matches!(
+        incomplete,
+        Err(SimulationError::IncompleteMessage(field)) if field == "target"
+    ));
+    let empty = Message::builder()
+        .from("generator-01", "")
+        .to("processor-01", "job")
+        .at(1.5)
+        .content("job 1")
+        .build();
+    assert!(matches!(
+        empty,
+        Err(SimulationError::IncompleteMessage(field)) if field == "source port"
+    ));
+}
+
+#[test]
+fn active_message_series_grows_for_unstable_queues() -> Result<(), SimulationError> {
+    let models = gps_models(
+        2.0,
+        ContinuousRandomVariable::Exp { lambda: 1.0 },
+        Some(1),
+        false,
+    );
+    let connectors = gps_connectors();
+    // Arrivals outpace service, and backpressure holds the excess jobs
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.set_backpressure(true);
+    simulation.step_n(10)?;
+    assert!(simulation.active_message_series().is_empty());
+    simulation.set_record_active_messages(true);
+    simulation.step_n(2000)?;
+    let series = simulation.active_message_series();
+    assert_eq!(series.len(), 2000);
+    assert!(series.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+    // The active message count trends upward, quarter over quarter
+    let quarter_means: Vec<f64> = series
+        .chunks(500)
+        .map(|chunk| chunk.iter().map(|(_, count)| *count as f64).sum::<f64>() / chunk.len() as f64)
+        .collect();
+    assert!(quarter_means.windows(2).all(|pair| pair[0] < pair[1]));
+    simulation.reset();
+    assert!(simulation.active_message_series().is_empty());
+    Ok(())
+}
+
+#[test]
+fn numeric_content_parsing() {
+    assert_eq!(parse_numeric_content("42").unwrap(), 42.0);
+    assert_eq!(parse_numeric_content(" -1.5e2 ").unwrap(), -150.0);
+    assert_eq!(parse_numeric_content("inf").unwrap(), f64::INFINITY);
+    ["job 1", "", "NaN", "1,5"].iter().for_each(|content| {
+        assert!(matches!(
+            parse_numeric_content(content),
+            Err(SimulationError::InvalidMessage)
+        ));
+    });
+}
+
+#[test]
+fn faster_processor_variant_has_higher_throughput() -> Result<(), SimulationError> {
+    let queueing_simulation = |service_rate: f64| {
+        Simulation::post(
+            vec![
+                Model::new(
+                    String::from("generator-01"),
+                    Box::new(Generator::new(
+                        ContinuousRandomVariable::Exp { lambda: 0.5 },
+                        None,
+                        String::from("job"),
+                        false,
+                        None,
+                        None,
+                    )),
+                ),
+                Model::new(
+                    String::from("processor-01"),
+                    Box::new(Processor::new(
+                        ContinuousRandomVariable::Exp {
+                            lambda: service_rate,
+                        },
+                        None,
+                        String::from("job"),
+                        String::from("processed"),
+                        false,
+                        None,
+                    )),
+                ),
+                Model::new(
+                    String::from("storage-01"),
+                    Box::new(Storage::new(
+                        String::from("store"),
+                        String::from("read"),
+                        String::from("stored"),
+                        false,
+                    )),
+                ),
+            ],
+            vec![
+                Connector::new(
+                    String::from("connector-01"),
+                    String::from("generator-01"),
+                    String::from("processor-01"),
+                    String::from("job"),
+                    String::from("job"),
+                ),
+                Connector::new(
+                    String::from("connector-02"),
+                    String::from("processor-01"),
+                    String::from("storage-01"),
+                    String::from("processed"),
+                    String::from("store"),
+                ),
+            ],
+        )
+    };
+    let comparison = compare_variants(queueing_simulation(0.2), queueing_simulation(1.0), 1000.0)?;
+    let (base_throughput, variant_throughput) = comparison.throughput["processor-01"];
+    assert!(variant_throughput > base_throughput);
+    assert!(comparison.throughput_delta("processor-01").unwrap() > 0.0);
+    assert!(comparison.message_count_delta() > 0);
+    // Both variants draw common random numbers from the base simulation seed
+    let seeded = |seed: u64| -> Result<Vec<Message>, SimulationError> {
+        let mut simulation = queueing_simulation(0.2);
+        simulation.set_seed(seed);
+        simulation.step_until(1000.0)
+    };
+    let (mut base, mut variant) = (queueing_simulation(0.2), queueing_simulation(0.2));
+    base.set_seed(7);
+    variant.set_seed(11);
+    assert_eq!(base.get_seed(), 7);
+    assert_ne!(seeded(7)?.len(), seeded(11)?.len());
+    let comparison = compare_variants(base, variant, 1000.0)?;
+    assert_eq!(
+        comparison.message_counts,
+        (seeded(7)?.len(), seeded(7)?.len())
+    );
+    Ok(())
+}
+
+#[test]
+fn stochastic_models_exclude_storage() {
+    let models = gps_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        None,
+        false,
+    );
+    let simulation = Simulation::post(models.to_vec(), Vec::new());
+    assert_eq!(
+        simulation.stochastic_models(),
+        vec![String::from("generator-01"), String::from("processor-01")]
+    );
+}
+
+#[test]
+fn exported_topology_omits_run_state() -> Result<(), SimulationError> {
+    let models = generator_processor_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        None,
+        false,
+    );
+    let connectors = [generator_processor_connector()];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.step_until(10.0)?;
+    simulation.step()?;
+    assert!(simulation.get_global_time() > 0.0);
+    let topology = simulation.export_topology()?;
+    assert!(!topology.contains("state"));
+    let imported = Simulation::import_topology(&topology)?;
+    assert_eq!(imported.get_global_time(), 0.0);
+    assert!(imported.get_messages().is_empty());
+    assert_eq!(imported.get_connectors(), &connectors.to_vec());
+    assert!(imported.structural_eq(&Simulation::post(models.to_vec(), connectors.to_vec())));
+    Ok(())
+}
+
+#[test]
+fn progress_callback_fires_periodically() -> Result<(), SimulationError> {
+    let models = [Model::new(
+        String::from("generator-01"),
+        Box::new(Generator::new(
+            ContinuousRandomVariable::Exp { lambda: 0.5 },
+            None,
+            String::from("job"),
+            false,
+            None,
+            None,
+        )),
+    )];
+    let mut simulation = Simulation::post(models.to_vec(), Vec::new());
+    let reports: Rc<RefCell<Vec<ProgressInfo>>> = Rc::new(RefCell::new(Vec::new()));
+    let callback_reports = reports.clone();
+    simulation.set_progress_callback(10, move |progress| {
+        callback_reports.borrow_mut().push(progress)
+    });
+    simulation.step_n(105)?;
+    let reports = reports.borrow();
+    assert_eq!(reports.len(), 10);
+    assert_eq!(
+        reports
+            .iter()
+            .map(|report| report.steps)
+            .collect::<Vec<u64>>(),
+        (1..=10).map(|report| report * 10).collect::<Vec<u64>>()
+    );
+    assert!(reports
+        .windows(2)
+        .all(|pair| pair[0].global_time <= pair[1].global_time));
+    Ok(())
+}
+
+#[test]
+fn checker_rejects_invalid_distribution_parameters() {
+    let models = [Model::new(
+        String::from("generator-01"),
+        Box::new(Generator::new(
+            ContinuousRandomVariable::Exp { lambda: 0.0 },
+            None,
+            String::from("job"),
+            false,
+            None,
+            None,
+        )),
+    )];
+    let simulation = Simulation::post(models.to_vec(), Vec::new());
+    assert!(matches!(
+        simulation.check(),
+        Err(SimulationError::InvalidRandomVariable(_))
+    ));
+    let report = simulation.check_report();
+    assert!(report.has_errors());
+    assert_eq!(report.issues[0].rule, "valid_model_parameters");
+}
+
+#[test]
+fn content_histogram_counts_contents() -> Result<(), SimulationError> {
+    let messages = ["a", "a", "b"]
+        .iter()
+        .map(|content| {
+            Message::builder()
+                .from("generator-01", "job")
+                .to("processor-01", "job")
+                .at(0.0)
+                .content(content)
+                .build()
+        })
+        .collect::<Result<Vec<Message>, SimulationError>>()?;
+    let histogram = content_histogram(&messages);
+    assert_eq!(histogram.len(), 2);
+    assert_eq!(histogram["a"], 2);
+    assert_eq!(histogram["b"], 1);
+    Ok(())
+}
+
+#[test]
+fn zero_capacity_processor_loses_excess_arrivals() -> Result<(), SimulationError> {
+    let loss_system = |loss_port: Option<&str>| {
+        let processor = Processor::new(
+            ContinuousRandomVariable::Exp { lambda: 0.5 },
+            Some(0),
+            String::from("job"),
+            String::from("processed"),
+            true,
+            None,
+        );
+        let processor = match loss_port {
+            Some(loss_port) => processor.with_loss_port(loss_port.to_string()),
+            None => processor,
+        };
+        let models = [
+            Model::new(
+                String::from("generator-01"),
+                Box::new(Generator::new(
+                    ContinuousRandomVariable::Exp { lambda: 5.0 },
+                    None,
+                    String::from("job"),
+                    false,
+                    None,
+                    None,
+                )),
+            ),
+            Model::new(String::from("processor-01"), Box::new(processor)),
+            Model::new(
+                String::from("storage-01"),
+                Box::new(Storage::new(
+                    String::from("store"),
+                    String::from("read"),
+                    String::from("stored"),
+                    false,
+                )),
+            ),
+        ];
+        let connectors = [
+            Connector::new(
+                String::from("connector-01"),
+                String::from("generator-01"),
+                String::from("processor-01"),
+                String::from("job"),
+                String::from("job"),
+            ),
+            Connector::new(
+                String::from("connector-02"),
+                String::from("processor-01"),
+                String::from("storage-01"),
+                String::from("lost"),
+                String::from("store"),
+            ),
+        ];
+        Simulation::post(models.to_vec(), connectors.to_vec())
+    };
+    let mut simulation = loss_system(Some("lost"));
+    let mut lost_jobs = 0;
+    while simulation.get_global_time() < 100.0 {
+        simulation.step()?;
+        assert!(simulation.get_metric("processor-01", "queue_length")? <= Some(1.0));
+        lost_jobs += simulation
+            .get_messages()
+            .iter()
+            .filter(|message| message.source_port() == "lost")
+            .count();
+    }
+    // Arrivals are ten times the service rate, so most jobs are lost
+    let processed_jobs = simulation
+        .get_records("processor-01")?
+        .iter()
+        .filter(|record| record.action == "Departure")
+        .count();
+    assert!(lost_jobs > processed_jobs);
+    // Without a connected loss port, lost jobs are dropped
+    let mut simulation = loss_system(None);
+    let messages = simulation.step_until(100.0)?;
+    assert!(messages
+        .iter()
+        .all(|message| message.source_port() != "lost"));
+    assert!(simulation
+        .get_records("processor-01")?
+        .iter()
+        .any(|record| record.action == "Drop"));
+    Ok(())
+}
+
+#[test]
+fn event_budget_stops_stepping() -> Result<(), SimulationError> {
+    let models = [Model::new(
+        String::from("generator-01"),
+        Box::new(Generator::new(
+            ContinuousRandomVariable::Exp { lambda: 0.5 },
+            None,
+            String::from("job"),
+            false,
+            None,
+            None,
+        )),
+    )];
+    let mut simulation = Simulation::post(models.to_vec(), Vec::new());
+    simulation.set_event_budget(5);
+    while simulation.total_events() < 5 {
+        simulation.step()?;
+    }
+    let global_time = simulation.get_global_time();
+    let before = simulation.clone();
+    assert!(matches!(
+        simulation.step(),
+        Err(SimulationError::EventBudgetExceeded(5))
+    ));
+    assert_eq!(simulation.total_events(), 5);
+    assert_eq!(simulation.get_global_time(), global_time);
+    assert!(simulation.structural_eq(&before));
+    Ok(())
+}
+
+#[test]
+fn bottleneck_is_the_most_utilized_processor() -> Result<(), SimulationError> {
+    let mut models = generator_processor_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 2.0 },
+        None,
+        true,
+    );
+    models.extend([
+        Model::new(
+            String::from("processor-02"),
+            Box::new(Processor::new(
+                ContinuousRandomVariable::Exp { lambda: 0.625 },
+                None,
+                String::from("job"),
+                String::from("processed"),
+                true,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ]);
+    let connectors = [
+        Connector::new(
+            String::from("connector-01"),
+            String::from("generator-01"),
+            String::from("processor-01"),
+            String::from("job"),
+            String::from("job"),
+        ),
+        Connector::new(
+            String::from("connector-02"),
+            String::from("processor-01"),
+            String::from("processor-02"),
+            String::from("processed"),
+            String::from("job"),
+        ),
+        Connector::new(
+            String::from("connector-03"),
+            String::from("processor-02"),
+            String::from("storage-01"),
+            String::from("processed"),
+            String::from("store"),
+        ),
+    ];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    assert_eq!(simulation.bottleneck()?, None);
+    simulation.step_until(1000.0)?;
+    let (model_id, utilization) = simulation.bottleneck()?.unwrap();
+    assert_eq!(model_id, "processor-02");
+    // The expected utilization is 0.5 / 0.625 = 0.8
+    assert!((utilization - 0.8).abs() < 0.1);
+    Ok(())
+}
+
+#[test]
+fn processor_loss_port_routes_rejected_jobs() -> Result<(), SimulationError> {
+    let models = gps_models(
+        2.0,
+        ContinuousRandomVariable::Exp { lambda: 0.5 },
+        Some(2),
+        true,
+    );
+    let connectors = [
+        Connector::new(
+            String::from("connector-01"),
+            String::from("generator-01"),
+            String::from("processor-01"),
+            String::from("job"),
+            String::from("job"),
+        ),
+        Connector::new(
+            String::from("connector-02"),
+            String::from("processor-01"),
+            String::from("storage-01"),
+            String::from(LOSS_PORT),
+            String::from("store"),
+        ),
+    ];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.step_until(100.0)?;
+    let rejected_jobs = simulation
+        .get_records("processor-01")?
+        .iter()
+        .filter(|record| record.action == "Drop")
+        .count() as u64;
+    assert!(rejected_jobs > 0);
+    assert_eq!(
+        simulation.connector_traffic()["connector-02"],
+        rejected_jobs
+    );
+    Ok(())
+}
+
+#[test]
+fn transform_model_rewrites_content() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("transform-01"),
+            Box::new(Transform::new(
+                ContentTransform::Uppercase,
+                2.0,
+                String::from("job"),
+                String::from("job"),
+                true,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [Connector::new(
+        String::from("connector-01"),
+        String::from("transform-01"),
+        String::from("storage-01"),
+        String::from("job"),
+        String::from("store"),
+    )];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.inject_input(
+        Message::builder()
+            .from("source", "job")
+            .to("transform-01", "job")
+            .at(0.0)
+            .content("job")
+            .build()?,
+    );
+    let messages = simulation.step_n(3)?;
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].content(), "JOB");
+    assert_eq!(*messages[0].time(), 2.0);
+    let records = simulation.get_records("transform-01")?;
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[1].subject, "JOB");
+    // The transform model is registered with the model factory
+    let model: Model = serde_yaml::from_str(
+        r#"
+type: Transform
+id: transform-02
+transform:
+  prefix:
+    prefix: "ACCT-"
+portsIn:
+  job: job
+portsOut:
+  job: job
+"#,
+    )
+    .unwrap();
+    assert_eq!(model.model_type(), "Transform");
+    Ok(())
+}
+
+#[test]
+fn xes_export_groups_events_into_traces() -> Result<(), SimulationError> {
+    let messages = [
+        ("generator-01", "job", "job 1", 0.0),
+        ("generator-01", "job", "job 2", 1.5),
+        ("processor-01", "processed", "job 1", 90061.25),
+    ]
+    .iter()
+    .map(|(source_id, source_port, content, time)| {
+        Message::builder()
+            .from(source_id, source_port)
+            .to("storage-01", "store")
+            .at(*time)
+            .content(content)
+            .build()
+    })
+    .collect::<Result<Vec<Message>, SimulationError>>()?;
+    let xes = export_xes(&messages, |message| message.content().to_string());
+    assert!(xes.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+    assert!(xes.trim_end().ends_with("</log>"));
+    assert_eq!(xes.matches("<trace>").count(), 2);
+    assert_eq!(xes.matches("</trace>").count(), 2);
+    assert_eq!(xes.matches("<event>").count(), 3);
+    assert_eq!(xes.matches("</event>").count(), 3);
+    // The first trace holds both events of job 1, in order
+    let first_trace = &xes[xes.find("<trace>").unwrap()..xes.find("</trace>").unwrap()];
+    assert!(first_trace.contains("<string key=\"concept:name\" value=\"job 1\"/>"));
+    assert!(first_trace.contains("<string key=\"concept:name\" value=\"processed\"/>"));
+    assert!(first_trace.contains("<string key=\"org:resource\" value=\"processor-01\"/>"));
+    assert!(first_trace.contains("value=\"1970-01-01T00:00:00.000Z\""));
+    assert!(first_trace.contains("value=\"1970-01-02T01:01:01.250Z\""));
+    Ok(())
+}
+
+#[test]
+fn failed_step_rolls_back() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                true,
+            )),
+        ),
+        Model::new(
+            String::from("processor-01"),
+            Box::new(Processor::new(
+                ContinuousRandomVariable::Exp { lambda: 0.5 },
+                None,
+                String::from("job"),
+                String::from("processed"),
+                true,
+                None,
+            )),
+        ),
+    ];
+    // Rollback is enabled by default
+    let mut simulation = Simulation::post(models.to_vec(), Vec::new());
+    let message = |target_id: &str, target_port: &str| {
+        Message::builder()
+            .from("source", "job")
+            .to(target_id, target_port)
+            .at(0.0)
+            .content("job 1")
+            .build()
+    };
+    // The storage accepts its message before the processor rejects an
+    // unknown port, partway through the step
+    simulation.inject_input(message("storage-01", "store")?);
+    simulation.inject_input(message("processor-01", "unknown")?);
+    let before = simulation.clone();
+    assert!(matches!(
+        simulation.step(),
+        Err(SimulationError::InvalidMessage)
+    ));
+    assert!(simulation.structural_eq(&before));
+    assert!(simulation.get_records("storage-01")?.is_empty());
+    // Without rollback, the failed step leaves the storage updated
+    let mut simulation = before.clone();
+    simulation.set_rollback_on_error(false);
+    assert!(simulation.step().is_err());
+    assert!(!simulation.get_records("storage-01")?.is_empty());
+    // The processor draws a service time before the storage rejects an
+    // unknown port, and the rolled back draw is repeated on retry
+    let mut reversed = models.to_vec();
+    reversed.reverse();
+    let mut simulation = Simulation::post(reversed.clone(), Vec::new());
+    let mut reference = Simulation::post(reversed, Vec::new());
+    simulation.inject_input(message("processor-01", "job")?);
+    simulation.inject_input(message("storage-01", "unknown")?);
+    assert!(simulation.step().is_err());
+    assert!(simulation.get_records("processor-01")?.is_empty());
+    simulation.reset_messages();
+    [&mut simulation, &mut reference].iter_mut().try_for_each(
+        |simulation| -> Result<(), SimulationError> {
+            simulation.inject_input(message("processor-01", "job")?);
+            simulation.step_n(2)?;
+            Ok(())
+        },
+    )?;
+    assert_eq!(simulation.get_global_time(), reference.get_global_time());
+    assert_eq!(
+        simulation.get_records("processor-01")?.len(),
+        reference.get_records("processor-01")?.len()
+    );
+    Ok(())
+}
+
+#[test]
+fn parallel_replications_match_sequential_replications() -> Result<(), SimulationError> {
+    let models = generator_processor_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        None,
+        false,
+    );
+    let connectors = [generator_processor_connector()];
+    let simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let collector = |_: &Simulation, messages: &[Message]| messages.len() as f64;
+    let sequential = simulation.run_replications(8, 100.0, collector)?;
+    let parallel = simulation.run_replications_parallel(8, 100.0, collector)?;
+    assert_eq!(sequential.len(), 8);
+    assert_eq!(sequential, parallel);
+    // Each replication draws from its own random number substream
+    assert!(sequential.iter().any(|metric| *metric != sequential[0]));
+    // Under another seed, both runners derive the same substreams
+    let mut simulation = simulation;
+    simulation.set_seed(7);
+    let parallel_seeded = simulation.run_replications_parallel(8, 100.0, collector)?;
+    assert_eq!(
+        simulation.run_replications(8, 100.0, collector)?,
+        parallel_seeded
+    );
+    assert_ne!(parallel, parallel_seeded);
+    Ok(())
+}
+
+#[test]
+fn replication_substreams_derive_from_the_simulation_seed() -> Result<(), SimulationError> {
+    let models = generator_processor_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        None,
+        false,
+    );
+    let connectors = [generator_processor_connector()];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.set_seed(7);
+    simulation.set_substream_scheme(SubstreamScheme::Jump);
+    let collector = |_: &Simulation, messages: &[Message]| messages.len() as f64;
+    let replications = simulation.run_replications(4, 100.0, collector)?;
+    let expected = (0..4)
+        .map(|replication| -> Result<f64, SimulationError> {
+            let mut copy = Simulation::post(models.to_vec(), connectors.to_vec());
+            copy.set_rng(SubstreamScheme::Jump.rng(7, replication));
+            Ok(copy.step_until(100.0)?.len() as f64)
+        })
+        .collect::<Result<Vec<f64>, SimulationError>>()?;
+    assert_eq!(replications, expected);
+    Ok(())
+}
+
+#[test]
+fn resource_holds_seizers_until_release() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("resource-01"),
+            Box::new(Resource::new(
+                2,
+                String::from("seize"),
+                String::from("release"),
+                String::from("granted"),
+                true,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [Connector::new(
+        String::from("connector-01"),
+        String::from("resource-01"),
+        String::from("storage-01"),
+        String::from("granted"),
+        String::from("store"),
+    )];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let message = |target_port: &str, content: &str, time: f64| {
+        Message::builder()
+            .from("source", "job")
+            .to("resource-01", target_port)
+            .at(time)
+            .content(content)
+            .build()
+    };
+    ["job 1", "job 2", "job 3"]
+        .iter()
+        .try_for_each(|job| -> Result<(), SimulationError> {
+            simulation.inject_input(message("seize", job, 0.0)?);
+            Ok(())
+        })?;
+    simulation.schedule_input(message("release", "job 1", 5.0)?, 5.0)?;
+    let granted = simulation.step_n(2)?;
+    assert_eq!(
+        granted
+            .iter()
+            .map(|message| message.content())
+            .collect::<Vec<&str>>(),
+        vec!["job 1", "job 2"]
+    );
+    assert_eq!(
+        simulation.get_status("resource-01")?,
+        "0 of 2 units available, with 1 waiting"
+    );
+    let granted = simulation.step_until(10.0)?;
+    assert_eq!(granted.len(), 1);
+    assert_eq!(granted[0].content(), "job 3");
+    assert_eq!(*granted[0].time(), 5.0);
+    let seizures: Vec<(f64, &str)> = simulation
+        .get_records("resource-01")?
+        .iter()
+        .filter(|record| record.action == "Seize")
+        .map(|record| (record.time, record.subject.as_str()))
+        .collect();
+    assert_eq!(
+        seizures,
+        vec![(0.0, "job 1"), (0.0, "job 2"), (5.0, "job 3")]
+    );
+    // A seeded state with more units in use than the resource has grants
+    // no further units
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.seed_state(
+        "resource-01",
+        serde_yaml::from_str("unitsInUse: 5").unwrap(),
+    )?;
+    simulation.inject_input(message("seize", "job 4", 0.0)?);
+    assert!(simulation.step_n(2)?.is_empty());
+    assert_eq!(
+        simulation.get_status("resource-01")?,
+        "0 of 2 units available, with 1 waiting"
+    );
+    Ok(())
+}
+
+#[test]
+fn finite_simulation_becomes_quiescent() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("processor-01"),
+            Box::new(Processor::new(
+                ContinuousRandomVariable::Exp { lambda: 0.5 },
+                None,
+                String::from("job"),
+                String::from("processed"),
+                false,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [Connector::new(
+        String::from("connector-01"),
+        String::from("processor-01"),
+        String::from("storage-01"),
+        String::from("processed"),
+        String::from("store"),
+    )];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    assert!(simulation.is_quiescent());
+    ["job 1", "job 2"]
+        .iter()
+        .try_for_each(|job| -> Result<(), SimulationError> {
+            simulation.inject_input(
+                Message::builder()
+                    .from("source", "job")
+                    .to("processor-01", "job")
+                    .at(0.0)
+                    .content(job)
+                    .build()?,
+            );
+            Ok(())
+        })?;
+    assert!(!simulation.is_quiescent());
+    let mut steps = 0;
+    while !simulation.is_quiescent() {
+        simulation.step()?;
+        steps += 1;
+        assert!(steps < 100);
+    }
+    #[cfg(feature = "test-util")]
+    simulation.assert_quiescent();
+    Ok(())
+}
+
+#[test]
+fn scheduled_inputs_are_stamped_with_their_delivery_time() -> Result<(), SimulationError> {
+    let models = [Model::new(
+        String::from("processor-01"),
+        Box::new(Processor::new(
+            ContinuousRandomVariable::Exp { lambda: 1.0 },
+            None,
+            String::from("job"),
+            String::from("processed"),
+            true,
+            None,
+        )),
+    )];
+    let mut simulation = Simulation::post(models.to_vec(), Vec::new());
+    // A message timestamped at time 1, but scheduled for time 10
+    let message = Message::builder()
+        .from("source", "job")
+        .to("processor-01", "job")
+        .at(1.0)
+        .content("job 1")
+        .build()?;
+    simulation.schedule_input(message, 10.0)?;
+    // Delivery at time 10 is not a causality violation
+    simulation.step()?;
+    assert_eq!(simulation.get_global_time(), 10.0);
+    assert_eq!(simulation.get_records("processor-01")?[0].time, 10.0);
+    Ok(())
+}
+
+#[test]
+fn scheduled_input_is_not_passed_while_messages_are_held() -> Result<(), SimulationError> {
+    let processor = |queue_capacity: Option<usize>| {
+        Box::new(Processor::new(
+            ContinuousRandomVariable::Triangular {
+                min: 9.9,
+                max: 10.1,
+                mode: 10.0,
+            },
+            queue_capacity,
+            String::from("job"),
+            String::from("processed"),
+            true,
+            None,
+        ))
+    };
+    let models = [
+        Model::new(String::from("processor-01"), processor(Some(1))),
+        Model::new(String::from("processor-02"), processor(None)),
+    ];
+    let mut simulation = Simulation::post(models.to_vec(), Vec::new());
+    simulation.set_backpressure(true);
+    let job = |target_id: &str, content: &str| {
+        Message::builder()
+            .from("source", "job")
+            .to(target_id, "job")
+            .at(0.0)
+            .content(content)
+            .build()
+    };
+    simulation.inject_input(job("processor-01", "job 1")?);
+    simulation.inject_input(job("processor-01", "job 2")?);
+    simulation.schedule_input(job("processor-02", "job 3")?, 3.0)?;
+    simulation.step()?;
+    assert_eq!(simulation.get_blocked_messages().len(), 1);
+    // With the second job held by backpressure, the clock stops at the
+    // scheduled input rather than the end of the first job's processing
+    simulation.step()?;
+    assert_eq!(simulation.get_global_time(), 3.0);
+    simulation.step()?;
+    let arrival = &simulation.get_records("processor-02")?[0];
+    assert_eq!((arrival.action.as_str(), arrival.time), ("Arrival", 3.0));
+    Ok(())
+}
+
+#[test]
+fn past_dated_messages_violate_causality() -> Result<(), SimulationError> {
+    let models = [Model::new(
+        String::from("storage-01"),
+        Box::new(Storage::new(
+            String::from("store"),
+            String::from("read"),
+            String::from("stored"),
+            false,
+        )),
+    )];
+    let mut simulation = Simulation::post(models.to_vec(), Vec::new());
+    let message = |time: f64| {
+        Message::builder()
+            .from("source", "job")
+            .to("storage-01", "store")
+            .at(time)
+            .content("job 1")
+            .build()
+    };
+    simulation.schedule_input(message(5.0)?, 5.0)?;
+    simulation.step()?;
+    assert_eq!(simulation.get_global_time(), 5.0);
+    // A source injecting a message timestamped before the current time
+    simulation.inject_input(message(1.0)?);
+    match simulation.step() {
+        Err(SimulationError::CausalityViolation(description)) => {
+            assert!(description.contains("storage-01"));
+            assert!(description.contains("at 1"));
+        }
+        _ => panic!("Expected a causality violation"),
+    }
+    Ok(())
+}
+
+#[test]
+fn flow_summary_ranks_generator_to_processor_first() -> Result<(), SimulationError> {
+    let models = gps_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        Some(14),
+        false,
+    );
+    let connectors = gps_connectors();
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let messages = simulation.step_until(200.0)?;
+    let flows = flow_summary(&messages);
+    assert_eq!(flows.len(), 2);
+    assert_eq!(
+        (flows[0].0.as_str(), flows[0].1.as_str()),
+        ("generator-01", "processor-01")
+    );
+    assert!(flows[0].2 >= flows[1].2);
+    assert_eq!(
+        flows.iter().map(|flow| flow.2).sum::<u64>(),
+        messages.len() as u64
+    );
+    Ok(())
+}
+
+#[test]
+fn fixed_point_clock_does_not_drift() -> Result<(), SimulationError> {
+    let models = [Model::new(
+        String::from("generator-01"),
+        Box::new(Generator::new(
+            ContinuousRandomVariable::Triangular {
+                min: 1.0 - 1e-10,
+                max: 1.0 + 1e-10,
+                mode: 1.0,
+            },
+            None,
+            String::from("job"),
+            false,
+            None,
+            None,
+        )),
+    )];
+    let mut simulation = Simulation::post(models.to_vec(), Vec::new());
+    simulation.set_time_representation(TimeRepresentation::Fixed);
+    // The first step schedules the first job, without advancing the clock
+    simulation.step()?;
+    (0..1_000_000).try_for_each(|_| simulation.step().map(|_| ()))?;
+    assert_eq!(simulation.time_representation(), TimeRepresentation::Fixed);
+    assert_eq!(simulation.get_global_time(), 1_000_000.0);
+    Ok(())
+}
+
+#[test]
+fn fixed_point_clock_overflow_is_an_error() -> Result<(), SimulationError> {
+    // The largest finite fixed-point time is at the range bound
+    let largest = FixedTime::from_ticks(i64::MAX - 1);
+    assert!(largest.to_f64().is_finite());
+    assert!(largest.to_f64() <= MAX_FIXED_TIME);
+    assert!(largest.checked_advance(1e-9).is_none());
+    assert!(FixedTime::zero().checked_advance(MAX_FIXED_TIME).is_none());
+    assert_eq!(
+        FixedTime::zero().checked_advance(f64::INFINITY),
+        Some(FixedTime::from_ticks(i64::MAX))
+    );
+    // A simulation stepping past the range fails, leaving the clock in range
+    let models = [Model::new(
+        String::from("generator-01"),
+        Box::new(Generator::new(
+            ContinuousRandomVariable::Triangular {
+                min: 5e9 - 1.0,
+                max: 5e9 + 1.0,
+                mode: 5e9,
+            },
+            None,
+            String::from("job"),
+            false,
+            None,
+            None,
+        )),
+    )];
+    let mut simulation = Simulation::post(models.to_vec(), Vec::new());
+    simulation.set_time_representation(TimeRepresentation::Fixed);
+    simulation.step_n(2)?;
+    let global_time = simulation.get_global_time();
+    assert!(global_time > 4e9);
+    assert!(matches!(
+        simulation.step(),
+        Err(SimulationError::TimeOverflow)
+    ));
+    assert_eq!(simulation.get_global_time(), global_time);
+    Ok(())
+}
+
+#[test]
+fn fan_out_delivery_follows_connector_priority() -> Result<(), SimulationError> {
+    let storage = || {
+        Box::new(Storage::new(
+            String::from("store"),
+            String::from("read"),
+            String::from("stored"),
+            false,
+        ))
+    };
+    let models = [
+        Model::new(
+            String::from("generator-01"),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Exp { lambda: 0.5 },
+                None,
+                String::from("job"),
+                false,
+                None,
+                None,
+            )),
+        ),
+        Model::new(String::from("storage-01"), storage()),
+        Model::new(String::from("storage-02"), storage()),
+        Model::new(String::from("storage-03"), storage()),
+    ];
+    let connectors = [(1, 3), (2, 1), (3, 2)].map(|(index, priority)| {
+        Connector::new(
+            format!("connector-0{}", index),
+            String::from("generator-01"),
+            format!("storage-0{}", index),
+            String::from("job"),
+            String::from("store"),
+        )
+        .with_priority(priority)
+    });
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let messages = simulation.step_until_events(1)?;
+    let targets: Vec<&str> = messages.iter().map(|message| message.target_id()).collect();
+    assert_eq!(targets, ["storage-02", "storage-03", "storage-01"]);
+    Ok(())
+}
+
+struct CountingSink {
+    messages: usize,
+    steps: usize,
+}
+
+impl MessageSink for CountingSink {
+    fn consume(&mut self, messages: &[Message]) -> Result<(), SimulationError> {
+        self.messages += messages.len();
+        self.steps += 1;
+        Ok(())
+    }
+}
+
+#[test]
+fn streaming_sink_receives_every_message() -> Result<(), SimulationError> {
+    let simulation = || {
+        let models = gps_models(
+            0.5,
+            ContinuousRandomVariable::Exp { lambda: 0.333333 },
+            Some(14),
+            false,
+        );
+        let connectors = gps_connectors();
+        Simulation::post(models.to_vec(), connectors.to_vec())
+    };
+    let expected = simulation().step_until(100.0)?;
+    let mut sink = CountingSink {
+        messages: 0,
+        steps: 0,
+    };
+    simulation().step_streaming(100.0, &mut sink)?;
+    assert!(sink.steps > 0);
+    assert_eq!(sink.messages, expected.len());
+    // The CSV file sink writes a header row, then one row per message
+    let path = std::env::temp_dir().join(format!("sim-stream-{}.csv", std::process::id()));
+    let mut csv_sink = CsvFileSink::create(&path)?;
+    simulation().step_streaming(100.0, &mut csv_sink)?;
+    csv_sink.flush()?;
+    let csv = std::fs::read_to_string(&path)?;
+    std::fs::remove_file(&path)?;
+    assert_eq!(csv.lines().count(), expected.len() + 1);
+    assert!(csv.starts_with("time,source_id,source_port,target_id,target_port,content"));
+    Ok(())
+}
+
+#[test]
+fn reseeding_a_model_isolates_its_stream() -> Result<(), SimulationError> {
+    let generator = || {
+        Box::new(Generator::new(
+            ContinuousRandomVariable::Exp { lambda: 0.5 },
+            None,
+            String::from("job"),
+            false,
+            None,
+            None,
+        ))
+    };
+    let storage = || {
+        Box::new(Storage::new(
+            String::from("store"),
+            String::from("read"),
+            String::from("stored"),
+            false,
+        ))
+    };
+    // Each replication runs with a fixed stream for generator-02, and
+    // optionally reseeds generator-01
+    let replication = |seed: Option<u64>| -> Result<(Vec<f64>, Vec<f64>), SimulationError> {
+        let models = [
+            Model::new(String::from("generator-01"), generator()),
+            Model::new(String::from("generator-02"), generator()),
+            Model::new(String::from("storage-01"), storage()),
+            Model::new(String::from("storage-02"), storage()),
+        ];
+        let connectors = [1, 2].map(|index| {
+            Connector::new(
+                format!("connector-0{}", index),
+                format!("generator-0{}", index),
+                format!("storage-0{}", index),
+                String::from("job"),
+                String::from("store"),
+            )
+        });
+        let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+        simulation.reseed_model("generator-02", 7)?;
+        if let Some(seed) = seed {
+            simulation.reseed_model("generator-01", seed)?;
+        }
+        let messages = simulation.step_until(50.0)?;
+        let times = |source_id: &str| {
+            messages
+                .iter()
+                .filter(|message| message.source_id() == source_id)
+                .map(|message| *message.time())
+                .collect::<Vec<f64>>()
+        };
+        Ok((times("generator-01"), times("generator-02")))
+    };
+    let (unseeded, other) = replication(None)?;
+    let (reseeded, reseeded_other) = replication(Some(11))?;
+    let (repeated, repeated_other) = replication(Some(11))?;
+    assert!(!reseeded.is_empty());
+    assert_ne!(unseeded, reseeded);
+    // Reseeding resets the interarrival sequence of generator-01
+    assert_eq!(reseeded, repeated);
+    // The stream of generator-02 is unaffected by the reseeding, up to
+    // rounding in the global time, from the interleaved generator-01 events
+    let same_times = |a: &[f64], b: &[f64]| {
+        a.len() == b.len() && a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-9)
+    };
+    assert!(same_times(&other, &reseeded_other));
+    assert!(same_times(&other, &repeated_other));
+    assert!(matches!(
+        Simulation::post(Vec::new(), Vec::new()).reseed_model("generator-01", 11),
+        Err(SimulationError::ModelNotFound)
+    ));
+    Ok(())
+}
+
+#[test]
+fn model_config_schema_lists_model_types() {
+    let schema = model_config_schema();
+    let model_types = schema["properties"]["type"]["enum"].as_array().unwrap();
+    ["Generator", "Processor", "Storage"]
+        .iter()
+        .for_each(|model_type| assert!(model_types.contains(&serde_json::json!(model_type))));
+    let variant = |model_type: &str| {
+        schema["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|variant| variant["properties"]["type"]["const"] == model_type)
+            .unwrap()
+            .clone()
+    };
+    let required = |model_type: &str| {
+        variant(model_type)["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|field| field.as_str().unwrap().to_string())
+            .collect::<Vec<String>>()
+    };
+    assert_eq!(
+        required("Generator"),
+        ["type", "messageInterdepartureTime", "portsIn", "portsOut"]
+    );
+    assert_eq!(
+        required("Processor"),
+        ["type", "serviceTime", "portsIn", "portsOut"]
+    );
+    assert_eq!(required("Storage"), ["type", "portsIn", "portsOut"]);
+    // Optional fields are described, with their types, but not required
+    let processor = variant("Processor");
+    assert_eq!(processor["properties"]["queueCapacity"]["type"], "integer");
+    assert_eq!(processor["properties"]["storeRecords"]["type"], "boolean");
+    // Skipped runtime fields are omitted
+    assert!(processor["properties"].get("rng").is_none());
+}
+
+#[test]
+fn merged_message_collections_are_time_ordered() -> Result<(), SimulationError> {
+    let collection = |times: &[(f64, &str)]| {
+        times
+            .iter()
+            .map(|(time, content)| {
+                Message::builder()
+                    .from("generator-01", "job")
+                    .to("processor-01", "job")
+                    .at(*time)
+                    .content(content)
+                    .build()
+            })
+            .collect::<Result<Vec<Message>, SimulationError>>()
+    };
+    let first = collection(&[(3.0, "a"), (1.0, "b"), (5.0, "c")])?;
+    let second = collection(&[(4.0, "d"), (1.0, "e"), (0.5, "f")])?;
+    let merged = merge_message_collections(&[&first, &second]);
+    assert_eq!(merged.len(), 6);
+    assert!(merged
+        .windows(2)
+        .all(|pair| pair[0].time() <= pair[1].time()));
+    // Equal times retain the collection order
+    let contents: Vec<&str> = merged.iter().map(|message| message.content()).collect();
+    assert_eq!(contents, ["f", "b", "e", "a", "d", "c"]);
+    Ok(())
+}
+
+#[test]
+fn processor_json_status_reports_queue_length() -> Result<(), SimulationError> {
+    let models = generator_processor_models(
+        2.0,
+        ContinuousRandomVariable::Exp { lambda: 0.5 },
+        None,
+        false,
+    );
+    let connectors = [generator_processor_connector()];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.step_until(20.0)?;
+    let status = simulation.get_status_json("processor-01")?;
+    let queue_length = status["queueLength"].as_u64().unwrap();
+    assert!(queue_length > 0);
+    assert!(status["jobsProcessed"].is_u64());
+    assert_eq!(
+        status["status"],
+        simulation.get_status("processor-01")?.as_str()
+    );
+    let generator_status = simulation.get_status_json("generator-01")?;
+    assert!(generator_status["jobsGenerated"].as_u64().unwrap() >= queue_length);
+    Ok(())
+}
+
+#[test]
+fn run_fingerprints_detect_changed_runs() -> Result<(), SimulationError> {
+    let run = |service_rate: f64| -> Result<Vec<Message>, SimulationError> {
+        let models = [
+            Model::new(
+                String::from("generator-01"),
+                Box::new(Generator::new(
+                    ContinuousRandomVariable::Exp { lambda: 0.5 },
+                    None,
+                    String::from("job"),
+                    false,
+                    None,
+                    None,
+                )),
+            ),
+            Model::new(
+                String::from("processor-01"),
+                Box::new(Processor::new(
+                    ContinuousRandomVariable::Exp {
+                        lambda: service_rate,
+                    },
+                    None,
+                    String::from("job"),
+                    String::from("processed"),
+                    false,
+                    None,
+                )),
+            ),
+            Model::new(
+                String::from("storage-01"),
+                Box::new(Storage::new(
+                    String::from("store"),
+                    String::from("read"),
+                    String::from("stored"),
+                    false,
+                )),
+            ),
+        ];
+        let connectors = gps_connectors();
+        Simulation::post(models.to_vec(), connectors.to_vec()).step_until(100.0)
+    };
+    let baseline = run_fingerprint(&run(0.333333)?);
+    assert_eq!(baseline, run_fingerprint(&run(0.333333)?));
+    assert_ne!(baseline, run_fingerprint(&run(0.5)?));
+    assert_ne!(baseline, run_fingerprint(&[]));
+    Ok(())
+}
+
+#[test]
+fn lossy_channel_drops_half_of_messages() -> Result<(), SimulationError> {
+    let storage = || {
+        Box::new(Storage::new(
+            String::from("store"),
+            String::from("read"),
+            String::from("stored"),
+            false,
+        ))
+    };
+    let models = [
+        Model::new(
+            String::from("generator-01"),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Exp { lambda: 1.0 },
+                None,
+                String::from("job"),
+                false,
+                None,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("channel-01"),
+            Box::new(LossyChannel::new(
+                TransitTime::Random(ContinuousRandomVariable::Uniform { min: 0.5, max: 1.5 }),
+                BooleanRandomVariable::Bernoulli { p: 0.5 },
+                String::from("job"),
+                String::from("job"),
+                false,
+                None,
+            )),
+        ),
+        Model::new(String::from("storage-01"), storage()),
+        Model::new(String::from("storage-02"), storage()),
+    ];
+    let connectors = [
+        Connector::new(
+            String::from("connector-01"),
+            String::from("generator-01"),
+            String::from("channel-01"),
+            String::from("job"),
+            String::from("job"),
+        ),
+        Connector::new(
+            String::from("connector-02"),
+            String::from("channel-01"),
+            String::from("storage-01"),
+            String::from("job"),
+            String::from("store"),
+        ),
+        Connector::new(
+            String::from("connector-03"),
+            String::from("channel-01"),
+            String::from("storage-02"),
+            String::from(LOSS_PORT),
+            String::from("store"),
+        ),
+    ];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let messages = simulation.step_until(2000.0)?;
+    let count = |source_id: &str, source_port: &str| {
+        messages
+            .iter()
+            .filter(|message| {
+                message.source_id() == source_id && message.source_port() == source_port
+            })
+            .count() as f64
+    };
+    let sent = count("generator-01", "job");
+    let survived = count("channel-01", "job");
+    let dropped = count("channel-01", LOSS_PORT);
+    assert!(sent > 1000.0);
+    assert!((survived / sent - 0.5).abs() < 0.05);
+    // Every message survives or is dropped, apart from those in transit
+    assert!(survived + dropped <= sent);
+    assert!(sent - (survived + dropped) < 5.0);
+    assert!(simulation
+        .stochastic_models()
+        .contains(&"channel-01".to_string()));
+    Ok(())
+}
+
+#[test]
+fn step_counts_match_step_n_messages() -> Result<(), SimulationError> {
+    let simulation = || {
+        let models = generator_processor_models(
+            0.5,
+            ContinuousRandomVariable::Exp { lambda: 0.333333 },
+            None,
+            false,
+        );
+        let connectors = [generator_processor_connector()];
+        Simulation::post(models.to_vec(), connectors.to_vec())
+    };
+    let counts = simulation().step_counts(500)?;
+    let messages = simulation().step_n(500)?;
+    assert_eq!(counts.len(), 500);
+    assert_eq!(counts.iter().sum::<usize>(), messages.len());
+    assert!(counts.contains(&0));
+    Ok(())
+}
+
+#[test]
+fn content_trace_follows_a_job_through_the_network() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("generator-01"),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Exp { lambda: 0.5 },
+                None,
+                String::from("job"),
+                false,
+                None,
+                Some(String::from("job-{seq}")),
+            )),
+        ),
+        Model::new(
+            String::from("processor-01"),
+            Box::new(Processor::new(
+                ContinuousRandomVariable::Exp { lambda: 1.0 },
+                None,
+                String::from("job"),
+                String::from("processed"),
+                false,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = gps_connectors();
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let messages = simulation.step_until(100.0)?;
+    let trace = simulation.trace_content("job-3", &messages);
+    let path: Vec<(&str, &str)> = trace
+        .iter()
+        .map(|message| (message.source_id(), message.target_id()))
+        .collect();
+    assert_eq!(
+        path,
+        [
+            ("generator-01", "processor-01"),
+            ("processor-01", "storage-01")
+        ]
+    );
+    assert!(trace[0].time() <= trace[1].time());
+    Ok(())
+}
+
+#[test]
+fn breakpoint_pauses_at_first_processing() -> Result<(), SimulationError> {
+    let models = generator_processor_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 1.0 },
+        None,
+        false,
+    );
+    let connectors = [generator_processor_connector()];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let BreakResult { broke, messages } = simulation.step_until_break(
+        |simulation, _| simulation.get_status("processor-01").unwrap() != "Passive",
+        100.0,
+    )?;
+    assert!(broke);
+    // The processor starts on the first job at the time of its arrival
+    assert_eq!(messages.len(), 1);
+    assert_eq!(simulation.get_global_time(), *messages[0].time());
+    assert_eq!(simulation.get_status("processor-01")?, "Processing");
+    // The paused simulation resumes, and runs to the maximum time when the
+    // breakpoint never holds
+    let resumed = simulation.step_until_break(|_, _| false, 20.0)?;
+    assert!(!resumed.broke);
+    assert!(!resumed.messages.is_empty());
+    assert!(simulation.get_global_time() >= 20.0);
+    Ok(())
+}
+
+#[test]
+fn fifo_delivery_orders_arrivals_by_time() -> Result<(), SimulationError> {
+    let arrivals = |fifo_delivery: bool| -> Result<Vec<String>, SimulationError> {
+        let models = [Model::new(
+            String::from("processor-01"),
+            Box::new(Processor::new(
+                ContinuousRandomVariable::Exp { lambda: 1.0 },
+                None,
+                String::from("job"),
+                String::from("processed"),
+                true,
+                None,
+            )),
+        )];
+        let mut simulation = Simulation::post(models.to_vec(), Vec::new());
+        simulation.set_fifo_delivery(fifo_delivery);
+        [(3.0, "job 3"), (1.0, "job 1"), (2.0, "job 2")]
+            .iter()
+            .try_for_each(|(time, content)| -> Result<(), SimulationError> {
+                simulation.inject_input(
+                    Message::builder()
+                        .from("generator-01", "job")
+                        .to("processor-01", "job")
+                        .at(*time)
+                        .content(content)
+                        .build()?,
+                );
+                Ok(())
+            })?;
+        simulation.step()?;
+        Ok(simulation
+            .get_records("processor-01")?
+            .iter()
+            .filter(|record| record.action == "Arrival")
+            .map(|record| record.subject.clone())
+            .collect())
+    };
+    assert_eq!(arrivals(false)?, ["job 3", "job 1", "job 2"]);
+    assert_eq!(arrivals(true)?, ["job 1", "job 2", "job 3"]);
+    Ok(())
+}
+
+#[test]
+fn connector_latencies_measure_transit_delay() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("generator-01"),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Exp { lambda: 1.0 },
+                None,
+                String::from("job"),
+                false,
+                None,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("channel-01"),
+            Box::new(LossyChannel::new(
+                TransitTime::Fixed(2.5),
+                BooleanRandomVariable::Bernoulli { p: 0.0 },
+                String::from("job"),
+                String::from("job"),
+                false,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [
+        Connector::new(
+            String::from("connector-01"),
+            String::from("generator-01"),
+            String::from("channel-01"),
+            String::from("job"),
+            String::from("job"),
+        ),
+        Connector::new(
+            String::from("connector-02"),
+            String::from("channel-01"),
+            String::from("storage-01"),
+            String::from("job"),
+            String::from("store"),
+        ),
+    ];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let messages = simulation.step_until(100.0)?;
+    let latencies = connector_latencies(&messages);
+    let channel_latencies = &latencies["generator-01:job -> channel-01:job"];
+    assert!(channel_latencies.len() > 50);
+    assert!(channel_latencies
+        .iter()
+        .all(|latency| (latency - 2.5).abs() < 1e-9));
+    // The storage is a sink, with no onward emissions
+    assert!(!latencies.contains_key("channel-01:job -> storage-01:store"));
+    Ok(())
+}
+
+#[test]
+fn post_checked_rejects_invalid_connectors() {
+    let models = [
+        Model::new(
+            String::from("generator-01"),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Exp { lambda: 0.5 },
+                None,
+                String::from("job"),
+                false,
+                None,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [
+        Connector::new(
+            String::from("connector-01"),
+            String::from("generator-01"),
+            String::from("storage-01"),
+            String::from("job"),
+            String::from("store"),
+        ),
+        Connector::new(
+            String::from("connector-98"),
+            String::from("fake-01"),
+            String::from("storage-01"),
+            String::from("job"),
+            String::from("store"),
+        ),
+        Connector::new(
+            String::from("connector-99"),
+            String::from("generator-01"),
+            String::from("fake-02"),
+            String::from("job"),
+            String::from("store"),
+        ),
+    ];
+    assert!(Simulation::post_checked(models.to_vec(), connectors[..1].to_vec()).is_ok());
+    match Simulation::post_checked(models.to_vec(), connectors.to_vec()) {
+        Err(SimulationError::ConfigurationCheckFailed(report)) => {
+            // Every error is reported, not just the first
+            assert!(report.contains("connector-98"));
+            assert!(report.contains("connector-99"));
+        }
+        _ => panic!("expected the configuration checks to fail"),
+    }
+}
+
+#[test]
+fn overflow_callback_fires_for_each_dropped_job() -> Result<(), SimulationError> {
+    let models = generator_processor_models(
+        2.0,
+        ContinuousRandomVariable::Exp { lambda: 0.5 },
+        Some(2),
+        true,
+    );
+    let connectors = [generator_processor_connector()];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let overflows: Rc<RefCell<Vec<(String, String)>>> = Rc::new(RefCell::new(Vec::new()));
+    let callback_overflows = overflows.clone();
+    simulation.set_overflow_callback(move |model_id, message| {
+        callback_overflows
+            .borrow_mut()
+            .push((model_id.to_string(), message.content().to_string()))
+    });
+    simulation.step_until(100.0)?;
+    let dropped_jobs: Vec<(String, String)> = simulation
+        .get_records("processor-01")?
+        .iter()
+        .filter(|record| record.action == "Drop")
+        .map(|record| (String::from("processor-01"), record.subject.clone()))
+        .collect();
+    assert!(!dropped_jobs.is_empty());
+    assert_eq!(*overflows.borrow(), dropped_jobs);
+    Ok(())
+}
+
+#[test]
+fn sequential_stopping_stops_early_for_low_variance_metric() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("generator-01"),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Triangular {
+                    min: 0.9,
+                    max: 1.1,
+                    mode: 1.0,
+                },
+                None,
+                String::from("job"),
+                false,
+                None,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [Connector::new(
+        String::from("connector-01"),
+        String::from("generator-01"),
+        String::from("storage-01"),
+        String::from("job"),
+        String::from("store"),
+    )];
+    let jobs_per_batch = |_: &Simulation, messages: &[Message]| messages.len() as f64;
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let result = simulation.sequential_stopping(jobs_per_batch, 1.0, 0.95, 100.0, 50)?;
+    assert!(result.converged);
+    assert!(result.batch_means.len() < 50);
+    assert!(result.half_width <= 1.0);
+    assert!((result.mean - 100.0).abs() < 2.0);
+    // An unreachable precision runs until the batch cap
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    let result = simulation.sequential_stopping(jobs_per_batch, 1e-9, 0.95, 100.0, 5)?;
+    assert!(!result.converged);
+    assert_eq!(result.batch_means.len(), 5);
+    assert!(result.half_width > 1e-9);
+    Ok(())
+}
+
+#[test]
+fn responder_answers_with_matching_correlation_ids() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("responder-01"),
+            Box::new(Responder::new(
+                ContinuousRandomVariable::Triangular {
+                    min: 1.9,
+                    max: 2.1,
+                    mode: 2.0,
+                },
+                String::from("request"),
+                String::from("response"),
+                true,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [Connector::new(
+        String::from("connector-01"),
+        String::from("responder-01"),
+        String::from("storage-01"),
+        String::from("response"),
+        String::from("store"),
+    )];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    ["request:order-17", "request:order-42"]
+        .iter()
+        .for_each(|content| {
+            simulation.inject_input(Message::new(
+                String::from("client"),
+                String::from("request"),
+                String::from("responder-01"),
+                String::from("request"),
+                simulation.get_global_time(),
+                content.to_string(),
+            ))
+        });
+    let messages = simulation.step_until(10.0)?;
+    let mut responses: Vec<(&str, f64)> = messages
+        .iter()
+        .filter(|message| message.source_id() == "responder-01")
+        .map(|message| (message.content(), *message.time()))
+        .collect();
+    responses.sort_by(|a, b| a.0.cmp(b.0));
+    assert_eq!(
+        responses
+            .iter()
+            .map(|(content, _)| *content)
+            .collect::<Vec<&str>>(),
+        ["response:order-17", "response:order-42"]
+    );
+    assert!(responses.iter().all(|(_, time)| (1.9..=2.1).contains(time)));
+    assert_eq!(
+        simulation
+            .get_records("responder-01")?
+            .iter()
+            .filter(|record| record.action == "Request")
+            .map(|record| record.subject.as_str())
+            .collect::<Vec<&str>>(),
+        ["order-17", "order-42"]
+    );
+    Ok(())
+}
+
+#[test]
+fn step_n_stops_at_the_first_failed_step() -> Result<(), SimulationError> {
+    let models = [
+        Model::new(
+            String::from("generator-01"),
+            Box::new(Generator::new(
+                ContinuousRandomVariable::Exp { lambda: 0.5 },
+                None,
+                String::from("job"),
+                false,
+                None,
+                None,
+            )),
+        ),
+        Model::new(
+            String::from("storage-01"),
+            Box::new(Storage::new(
+                String::from("store"),
+                String::from("read"),
+                String::from("stored"),
+                false,
+            )),
+        ),
+    ];
+    let connectors = [Connector::new(
+        String::from("connector-01"),
+        String::from("generator-01"),
+        String::from("storage-01"),
+        String::from("job"),
+        String::from("store"),
+    )];
+    let simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    // Zero steps leave the simulation untouched
+    let mut zero_steps = simulation.clone();
+    assert!(zero_steps.step_n(0)?.is_empty());
+    assert_eq!(zero_steps.get_global_time(), 0.0);
+    // Normal completion returns the messages of every step
+    let mut completed = simulation.clone();
+    let completed_messages = completed.step_n(4)?;
+    assert_eq!(
+        completed_messages
+            .iter()
+            .map(|message| message.content())
+            .collect::<Vec<&str>>(),
+        ["job 1", "job 2"]
+    );
+    // The event budget fails the third step, retaining the first two steps
+    let mut budgeted = simulation.clone();
+    budgeted.set_event_budget(2);
+    let partial_steps: PartialSteps = budgeted.step_n_partial(5);
+    assert_eq!(partial_steps.steps, 2);
+    assert_eq!(
+        partial_steps
+            .messages
+            .iter()
+            .map(|message| message.content())
+            .collect::<Vec<&str>>(),
+        ["job 1"]
+    );
+    assert!(matches!(
+        partial_steps.error,
+        Some(SimulationError::EventBudgetExceeded(2))
+    ));
+    let mut budgeted = simulation.clone();
+    budgeted.set_event_budget(2);
+    assert!(matches!(
+        budgeted.step_n(5),
+        Err(SimulationError::EventBudgetExceeded(2))
+    ));
+    Ok(())
+}
+
+#[test]
+fn paused_simulation_steps_are_no_ops() -> Result<(), SimulationError> {
+    let models = generator_processor_models(
+        0.5,
+        ContinuousRandomVariable::Exp { lambda: 0.333333 },
+        None,
+        true,
+    );
+    let connectors = [generator_processor_connector()];
+    let mut simulation = Simulation::post(models.to_vec(), connectors.to_vec());
+    simulation.step_n(10)?;
+    let global_time = simulation.get_global_time();
+    let state = |simulation: &Simulation| -> Result<Vec<serde_json::Value>, SimulationError> {
+        ["generator-01", "processor-01"]
+            .iter()
+            .map(|model_id| simulation.get_status_json(model_id))
+            .collect()
+    };
+    let paused_state = state(&simulation)?;
+    simulation.set_paused(true);
+    assert!(simulation.is_paused());
+    assert!(simulation.step()?.is_empty());
+    assert!(simulation.step_n(5)?.is_empty());
+    assert!(simulation.step_until(global_time + 100.0)?.is_empty());
+    assert_eq!(simulation.get_global_time(), global_time);
+    assert_eq!(state(&simulation)?, paused_state);
+    // Resuming continues the simulation where it left off
+    simulation.set_paused(false);
+    let messages = simulation.step_until(global_time + 100.0)?;
+    assert!(!messages.is_empty());
+    assert!(simulation.get_global_time() > global_time);
+    Ok(())
+}
@@ -3,7 +3,7 @@ use std::f64::INFINITY;
 
 use sim::models::{Model, ModelRecord};
 use sim::output_analysis::IndependentSample;
-use sim::simulator::{Connector, Message, WebSimulation};
+use sim::simulator::{Connector, Message, TopologyGraph, WebSimulation};
 use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
 
 wasm_bindgen_test_configure!(run_in_browser);
@@ -615,3 +615,135 @@ fn ci_half_width_for_average_waiting_time() {
         }
     }
 }
+
+#[wasm_bindgen_test]
+fn inject_input_from_js_object() {
+    let models = r#"
+- type: "LoadBalancer"
+  id: "load-balancer-01"
+  portsIn:
+    job: "job"
+  portsOut:
+    flowPaths:
+      - "alpha"
+- type: "Storage"
+  id: "storage-01"
+  portsIn:
+    put: "store"
+    get: "read"
+  portsOut:
+    stored: "stored"
+"#;
+    let connectors = r#"
+- id: "connector-01"
+  sourceID: "load-balancer-01"
+  targetID: "storage-01"
+  sourcePort: "alpha"
+  targetPort: "store"
+"#;
+    let mut simulation = WebSimulation::post_yaml(models, connectors);
+    let message = js_sys::JSON::parse(
+        r#"{
+            "sourceId": "manual",
+            "sourcePort": "manual",
+            "targetId": "load-balancer-01",
+            "targetPort": "job",
+            "time": 0.0,
+            "content": "job 1"
+        }"#,
+    )
+    .unwrap();
+    simulation.inject_input_js(message).unwrap();
+    let messages: Vec<Message> = serde_json::from_str(&simulation.step_json()).unwrap();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].content(), "job 1");
+    assert_eq!(messages[0].target_id(), "storage-01");
+    // Messages targeting models outside the simulation are rejected
+    let unknown_target = js_sys::JSON::parse(
+        r#"{
+            "sourceId": "manual",
+            "sourcePort": "manual",
+            "targetId": "processor-01",
+            "targetPort": "job",
+            "time": 0.0,
+            "content": "job 2"
+        }"#,
+    )
+    .unwrap();
+    assert!(simulation.inject_input_js(unknown_target).is_err());
+}
+
+#[wasm_bindgen_test]
+fn topology_exports_from_js() {
+    let models = r#"
+- type: "LoadBalancer"
+  id: "load-balancer-01"
+  portsIn:
+    job: "job"
+  portsOut:
+    flowPaths:
+      - "alpha"
+- type: "Storage"
+  id: "storage-01"
+  portsIn:
+    put: "store"
+    get: "read"
+  portsOut:
+    stored: "stored"
+"#;
+    let connectors = r#"
+- id: "connector-01"
+  sourceID: "load-balancer-01"
+  targetID: "storage-01"
+  sourcePort: "alpha"
+  targetPort: "store"
+"#;
+    let simulation = WebSimulation::post_yaml(models, connectors);
+    let dot = simulation.to_dot();
+    assert!(dot.contains("\"load-balancer-01\";"));
+    assert!(dot.contains("\"storage-01\";"));
+    assert!(dot.contains("\"load-balancer-01\" -> \"storage-01\" [label=\"connector-01\"];"));
+    let graph: TopologyGraph = serde_wasm_bindgen::from_value(simulation.to_graph_json()).unwrap();
+    assert_eq!(graph.nodes.len(), 2);
+    assert_eq!(graph.edges[0].id, "connector-01");
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn seeded_simulations_are_reproducible() {
+    let models = r#"
+- type: "Generator"
+  id: "generator-01"
+  portsIn: {}
+  portsOut:
+    job: "job"
+  messageInterdepartureTime:
+    exp:
+      lambda: 0.5
+- type: "Processor"
+  id: "processor-01"
+  portsIn:
+    job: "job"
+  portsOut:
+    job: "processed job"
+  serviceTime:
+    exp:
+      lambda: 0.333333
+"#;
+    let connectors = r#"
+- id: "connector-01"
+  sourceID: "generator-01"
+  targetID: "processor-01"
+  sourcePort: "job"
+  targetPort: "job"
+"#;
+    let seeded_run = |seed: u64| {
+        let mut web = WebSimulation::post_yaml(models, connectors);
+        assert_eq!(web.get_seed(), 42);
+        web.set_seed(seed);
+        assert_eq!(web.get_seed(), seed);
+        web.step_n_json(50)
+    };
+    assert_eq!(seeded_run(7), seeded_run(7));
+    assert_ne!(seeded_run(7), seeded_run(8));
+}
@@ -4,14 +4,183 @@ extern crate syn;
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput, Ident};
+use syn::meta::ParseNestedMeta;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, Ident, LitStr, Type};
+
+/// The serde attributes of a struct or struct field, relevant to the
+/// configuration fields of a model.
+#[derive(Default)]
+struct SerdeAttributes {
+    default: bool,
+    skip: bool,
+    rename: Option<String>,
+    rename_all_camel_case: bool,
+}
+
+fn serde_attributes(attrs: &[Attribute]) -> SerdeAttributes {
+    let mut serde_attributes = SerdeAttributes::default();
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .for_each(|attr| {
+            // Unparseable attributes are left for serde to report
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("default") {
+                    serde_attributes.default = true;
+                } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing") {
+                    serde_attributes.skip = true;
+                } else if meta.path.is_ident("rename") && meta.input.peek(syn::Token![=]) {
+                    serde_attributes.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                    return Ok(());
+                } else if meta.path.is_ident("rename_all") && meta.input.peek(syn::Token![=]) {
+                    serde_attributes.rename_all_camel_case =
+                        meta.value()?.parse::<LitStr>()?.value() == "camelCase";
+                    return Ok(());
+                }
+                skip_meta_value(&meta)
+            });
+        });
+    serde_attributes
+}
+
+/// Consume the value of a serde attribute, if any, without interpreting it.
+fn skip_meta_value(meta: &ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|nested| skip_meta_value(&nested))?;
+    }
+    Ok(())
+}
+
+fn camel_case(name: &str) -> String {
+    let mut parts = name.split('_');
+    let mut camel = parts.next().unwrap_or_default().to_string();
+    parts.for_each(|part| {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            camel.extend(first.to_uppercase());
+            camel.push_str(chars.as_str());
+        }
+    });
+    camel
+}
+
+/// The last path segment of a type, and its first generic type argument,
+/// if any.
+fn type_segment(ty: &Type) -> Option<(String, Option<&Type>)> {
+    match ty {
+        Type::Path(type_path) => type_path.path.segments.last().map(|segment| {
+            let argument = match &segment.arguments {
+                syn::PathArguments::AngleBracketed(arguments) => {
+                    arguments.args.iter().find_map(|argument| match argument {
+                        syn::GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    })
+                }
+                _ => None,
+            };
+            (segment.ident.to_string(), argument)
+        }),
+        _ => None,
+    }
+}
+
+/// The JSON Schema type of a field type, where the type maps directly to
+/// one.
+fn json_type(ty: &Type) -> Option<&'static str> {
+    if let Type::Array(_) | Type::Slice(_) = ty {
+        return Some("array");
+    }
+    let (name, argument) = type_segment(ty)?;
+    match name.as_str() {
+        "bool" => Some("boolean"),
+        "f32" | "f64" => Some("number"),
+        "u8" | "u16" | "u32" | "u64" | "usize" | "i8" | "i16" | "i32" | "i64" | "isize" => {
+            Some("integer")
+        }
+        "String" => Some("string"),
+        "Vec" | "VecDeque" => Some("array"),
+        "HashMap" | "BTreeMap" => Some("object"),
+        "Option" | "Box" => argument.and_then(json_type),
+        _ => None,
+    }
+}
+
+fn is_option(ty: &Type) -> bool {
+    matches!(type_segment(ty), Some((name, _)) if name == "Option")
+}
 
 #[proc_macro_derive(SerializableModel)]
 pub fn model(item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
     let name = input.ident;
+    let container = serde_attributes(&input.attrs);
+    // The configuration fields are the deserialized struct fields, as
+    // (name, JSON Schema type, required) tuples
+    let config_fields: Vec<_> = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields
+                .named
+                .iter()
+                .filter_map(|field| {
+                    let attributes = serde_attributes(&field.attrs);
+                    if attributes.skip {
+                        return None;
+                    }
+                    let ident = field.ident.as_ref()?.to_string();
+                    let field_name = match attributes.rename {
+                        Some(rename) => rename,
+                        None if container.rename_all_camel_case => camel_case(&ident),
+                        None => ident,
+                    };
+                    let field_type = match json_type(&field.ty) {
+                        Some(json_type) => quote! { Some(#json_type) },
+                        None => quote! { None },
+                    };
+                    let required =
+                        !(attributes.default || container.default || is_option(&field.ty));
+                    Some(quote! { (#field_name, #field_type, #required) })
+                })
+                .collect(),
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+    // Only the state field is replaced on state loading, so that fields
+    // skipped by serde - such as a model random number generator - are kept
+    let has_state = match &input.data {
+        Data::Struct(data) => data
+            .fields
+            .iter()
+            .any(|field| field.ident.as_ref().is_some_and(|ident| ident == "state")),
+        _ => false,
+    };
+    let load_state = if has_state {
+        quote! {
+            fn load_state(&mut self, state: serde_yaml::Value) -> Result<(), serde_yaml::Error> {
+                let merged_state = match (serde_yaml::to_value(&self.state)?, state) {
+                    (serde_yaml::Value::Mapping(mut current), serde_yaml::Value::Mapping(seeded)) => {
+                        current.extend(seeded);
+                        serde_yaml::Value::Mapping(current)
+                    }
+                    (_, state) => state,
+                };
+                self.state = serde_yaml::from_value(merged_state)?;
+                Ok(())
+            }
+        }
+    } else {
+        quote! {}
+    };
     let tokens = quote! {
         impl #name {
+            /// The configuration fields of the model, as (name, JSON Schema
+            /// type, required) tuples, for the model configuration schema.
+            pub fn config_fields() -> &'static [(&'static str, Option<&'static str>, bool)] {
+                &[#(#config_fields),*]
+            }
+
             pub fn from_value(value: serde_yaml::Value) -> Option<Box<dyn ReportableModel>> {
                 match serde_yaml::from_value::<Self>(value) {
                     Ok(model) => Some(Box::new(model)),
@@ -26,6 +195,7 @@ pub fn model(item: TokenStream) -> TokenStream {
             fn serialize(&self) -> serde_yaml::Value {
                 serde_yaml::to_value(self).unwrap_or(serde_yaml::Value::Null)
             }
+            #load_state
         }
     };
     tokens.into()
@@ -39,6 +209,10 @@ pub fn register(item: TokenStream) -> TokenStream {
             stringify!(#name),
             #name::from_value as sim::models::model_factory::ModelConstructor
         );
+        sim::models::model_factory::register_config_fields(
+            stringify!(#name),
+            #name::config_fields as sim::models::model_factory::ModelConfigFields
+        );
     };
     tokens.into()
 }